@@ -0,0 +1,140 @@
+//! Converting frames between Modbus RTU and Modbus ASCII.
+//!
+//! Modbus ASCII carries the same slave id, function code and payload as RTU,
+//! hex encoded between a `:` and CR LF, with an LRC instead of the CRC.
+//! Converting at the line keeps the rest of the crate usable on ASCII links:
+//! requests are built with [`Request::to_bytes`](crate::Request::to_bytes)
+//! and converted with [`from_rtu`], responses are converted with [`to_rtu`]
+//! and decoded with [`Response::from_bytes`](crate::Response::from_bytes).
+
+use crate::error::{AsciiError, ResponsePacketError};
+use alloc::vec::Vec;
+
+
+/// Upper-case hexadecimal digits, as Modbus ASCII requires.
+const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+
+/// Shortest Modbus ASCII frame: `:`, slave id, function code and LRC, CR LF.
+const MIN_FRAME_LEN: usize = 9;
+
+
+/// Returns the LRC of `bytes`, the two's complement of their sum.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::ascii;
+///
+/// assert_eq!(ascii::lrc(&[0x11, 0x03, 0x00, 0x6B, 0x00, 0x03]), 0x7E);
+/// ```
+///
+pub fn lrc(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .fold(0u8, |sum, &byte| sum.wrapping_add(byte))
+        .wrapping_neg()
+}
+
+
+/// Writes the Modbus ASCII form of the complete RTU `frame` into `buf` and
+/// returns it.
+///
+/// `buf` is cleared first, so the same buffer can be reused for every frame.
+///
+/// ---
+/// # Errors
+/// Fails like [`raw::check`](crate::raw::check) when `frame` is too short or
+/// its CRC does not match.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::ascii;
+///
+/// let rtu = [0x11, 0x03, 0x00, 0x6B, 0x00, 0x03, 0x76, 0x87];
+/// let mut buf = Vec::new();
+/// assert_eq!(ascii::from_rtu(&rtu, &mut buf).unwrap(), b":1103006B00037E\r\n");
+/// ```
+///
+pub fn from_rtu<'a>(frame: &[u8], buf: &'a mut Vec<u8>) -> Result<&'a [u8], ResponsePacketError> {
+    crate::raw::check(frame)?;
+    let content = &frame[..frame.len() - 2];
+    buf.clear();
+    buf.reserve(content.len() * 2 + 5);
+    buf.push(b':');
+    for &byte in content.iter().chain(&[lrc(content)]) {
+        buf.push(HEX[usize::from(byte >> 4)]);
+        buf.push(HEX[usize::from(byte & 0x0F)]);
+    }
+    buf.extend_from_slice(b"\r\n");
+    Ok(buf)
+}
+
+
+/// Writes the RTU form of the complete Modbus ASCII `frame`, delimiters
+/// included, into `buf` and returns it.
+///
+/// `buf` is cleared first, so the same buffer can be reused for every frame.
+///
+/// ---
+/// # Errors
+/// Returns [`AsciiError::TooShort`] and [`AsciiError::MissingDelimiter`]
+/// for frames that cannot be one, [`AsciiError::InvalidCharacter`] for
+/// anything but pairs of upper-case hexadecimal digits between the
+/// delimiters and [`AsciiError::LRCMismatch`] when the LRC does not match.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::ascii;
+/// use modbus_rtu::error::AsciiError;
+///
+/// let mut buf = Vec::new();
+/// let rtu = ascii::to_rtu(b":1103006B00037E\r\n", &mut buf).unwrap();
+/// assert_eq!(rtu, &[0x11, 0x03, 0x00, 0x6B, 0x00, 0x03, 0x76, 0x87]);
+///
+/// assert_eq!(
+///     ascii::to_rtu(b":1103006B00037F\r\n", &mut buf),
+///     Err(AsciiError::LRCMismatch { expected: 0x7E, received: 0x7F }),
+/// );
+/// ```
+///
+pub fn to_rtu<'a>(frame: &[u8], buf: &'a mut Vec<u8>) -> Result<&'a [u8], AsciiError> {
+    let len = frame.len();
+    if len < MIN_FRAME_LEN {
+        return Err(AsciiError::TooShort(len));
+    }
+    let Some(digits) = frame
+        .strip_prefix(b":")
+        .and_then(|frame| frame.strip_suffix(b"\r\n"))
+    else {
+        return Err(AsciiError::MissingDelimiter);
+    };
+    if digits.len() % 2 != 0 {
+        return Err(AsciiError::InvalidCharacter(digits[digits.len() - 1]));
+    }
+    buf.clear();
+    buf.reserve(digits.len() / 2 + 1);
+    for pair in digits.chunks_exact(2) {
+        buf.push(digit(pair[0])? << 4 | digit(pair[1])?);
+    }
+    let received = buf.pop().unwrap_or_default();
+    let expected = lrc(buf);
+    if expected != received {
+        return Err(AsciiError::LRCMismatch { expected, received });
+    }
+    let crc = crate::crc::generate(buf);
+    buf.extend_from_slice(&crc.to_le_bytes());
+    Ok(buf)
+}
+
+
+/// Returns the value of the upper-case hexadecimal digit `c`.
+fn digit(c: u8) -> Result<u8, AsciiError> {
+    match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        _ => Err(AsciiError::InvalidCharacter(c)),
+    }
+}
@@ -0,0 +1,125 @@
+use crate::error::ResponsePacketError;
+use crate::{BitOrder, Function, Request};
+use alloc::{boxed::Box, vec::Vec};
+
+
+/// ## Assembly
+///
+/// What a [`ResponseAssembler`] holds after bytes were pushed into it.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Assembly {
+    /// The frame is not complete yet. At least this many more bytes are
+    /// needed, which also makes a good size for the next read.
+    NeedMore(usize),
+
+    /// The whole response frame, slave id and CRC included, passed
+    /// validation against the request.
+    Complete(Box<[u8]>),
+
+    /// The received bytes do not form a valid response to the request.
+    Invalid(ResponsePacketError),
+}
+
+
+/// ## ResponseAssembler
+///
+/// Collects the response to one request out of the chunks an application
+/// reads itself, e.g. from a `poll`/`select` driven loop, and tells when a
+/// whole frame is there.
+///
+/// Like [`RtuCodec`](crate::RtuCodec), it finds the end of the frame from
+/// its content, not from the T3.5 silence. Once the function code arrived,
+/// the length follows from [`Request::expected_response_len`], so
+/// [`NeedMore`](Assembly::NeedMore) names the exact remainder of the frame.
+/// Complete frames are validated with
+/// [`Response::from_bytes`](crate::Response::from_bytes), which also decodes
+/// them.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{test_vectors, Assembly, Function, Request, Response, ResponseAssembler};
+///
+/// let function = Function::ReadHoldingRegisters { starting_address: 0x006B, quantity: 3 };
+/// let request = Request::new(0x11, &function, std::time::Duration::from_millis(100));
+/// let mut assembler = ResponseAssembler::new(&request);
+///
+/// let (head, tail) = test_vectors::READ_HOLDING_REGISTERS.response.split_at(2);
+/// assert_eq!(assembler.push(head), Assembly::NeedMore(9));
+/// let Assembly::Complete(frame) = assembler.push(tail) else { panic!() };
+///
+/// let response = Response::from_bytes(&request, &frame).unwrap();
+/// assert_eq!(response, Response::Value(Box::new([0x022B, 0x0000, 0x0064])));
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseAssembler {
+    modbus_id: u8,
+    function: Function,
+    bit_order: BitOrder,
+    expected_len: Option<usize>,
+    buffer: Vec<u8>,
+}
+
+impl ResponseAssembler {
+    /// Creates an assembler for the response to `request`.
+    pub fn new(request: &Request) -> Self {
+        Self {
+            modbus_id: request.modbus_id(),
+            function: request.function().clone(),
+            bit_order: request.bit_order(),
+            expected_len: request.expected_response_len(),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Appends `bytes` and reports whether the response is complete.
+    ///
+    /// A complete or invalid frame is removed from the buffer; bytes that
+    /// arrived past its end stay in [`buffered`](Self::buffered).
+    pub fn push(&mut self, bytes: &[u8]) -> Assembly {
+        self.buffer.extend_from_slice(bytes);
+        let Some(len) = frame_len(&self.function, self.expected_len, &self.buffer) else {
+            if self.buffer.len() >= crate::codec::MAX_FRAME_LEN {
+                self.buffer.clear();
+                return Assembly::Invalid(ResponsePacketError::InvalidFormat);
+            }
+            // An exception response is the shortest possible frame.
+            return Assembly::NeedMore(5usize.saturating_sub(self.buffer.len()).max(1));
+        };
+        if self.buffer.len() < len {
+            return Assembly::NeedMore(len - self.buffer.len());
+        }
+        let frame: Box<[u8]> = self.buffer.drain(..len).collect();
+        let mut request = Request::new(self.modbus_id, &self.function, core::time::Duration::ZERO);
+        request.set_bit_order(self.bit_order);
+        match crate::Response::from_bytes(&request, &frame) {
+            Ok(_) => Assembly::Complete(frame),
+            Err(error) => Assembly::Invalid(error),
+        }
+    }
+
+    /// Returns the bytes received so far that are not part of a returned
+    /// frame.
+    pub fn buffered(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Drops the buffered bytes, e.g. before the request is retried.
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+    }
+}
+
+
+/// Predicts the length of the response to `function` starting `head`, from
+/// the frame itself where it tells and from the `expected_len` of a regular
+/// response otherwise, or returns [`None`] before the function code arrived.
+pub(crate) fn frame_len(
+    function: &Function,
+    expected_len: Option<usize>,
+    head: &[u8],
+) -> Option<usize> {
+    crate::codec::response_frame_len(function, head).or_else(|| head.get(1).and(expected_len))
+}
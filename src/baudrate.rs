@@ -0,0 +1,160 @@
+use alloc::string::ToString;
+
+use crate::error::BaudrateError;
+
+
+/// ## Baudrate
+///
+/// Standard serial line speeds used on Modbus RTU links.
+///
+/// Converts into the `u32` taken by [`serialport`](https://docs.rs/serialport)
+/// and by [`Master`](crate::Master), and parses from the notations found in
+/// configuration files and device manuals: `"9600"`, `"19200"`, `"19k2"`,
+/// `"115k2"`. Custom rates remain available as bare `u32` values wherever a
+/// baud rate is expected.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::Baudrate;
+///
+/// let baudrate: Baudrate = "19k2".parse().unwrap();
+/// assert_eq!(baudrate, Baudrate::Baud19200);
+/// assert_eq!(u32::from(baudrate), 19_200);
+/// assert_eq!("9600".parse(), Ok(Baudrate::Baud9600));
+/// assert_eq!(Baudrate::try_from(115_200), Ok(Baudrate::Baud115200));
+/// assert!("19k".parse::<Baudrate>().is_err());
+///
+/// for baudrate in Baudrate::ALL {
+///     assert_eq!(baudrate.to_string().parse(), Ok(baudrate));
+/// }
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum Baudrate {
+    /// 1200 baud.
+    Baud1200,
+
+    /// 2400 baud.
+    Baud2400,
+
+    /// 4800 baud.
+    Baud4800,
+
+    /// 9600 baud.
+    Baud9600,
+
+    /// 14400 baud.
+    Baud14400,
+
+    /// 19200 baud, the default rate of the Modbus RTU specification.
+    #[default]
+    Baud19200,
+
+    /// 38400 baud.
+    Baud38400,
+
+    /// 57600 baud.
+    Baud57600,
+
+    /// 115200 baud.
+    Baud115200,
+}
+
+impl Baudrate {
+    /// Every standard rate, slowest first.
+    pub const ALL: [Baudrate; 9] = [
+        Baudrate::Baud1200,
+        Baudrate::Baud2400,
+        Baudrate::Baud4800,
+        Baudrate::Baud9600,
+        Baudrate::Baud14400,
+        Baudrate::Baud19200,
+        Baudrate::Baud38400,
+        Baudrate::Baud57600,
+        Baudrate::Baud115200,
+    ];
+
+    /// Returns the rate in bits per second.
+    pub const fn as_u32(&self) -> u32 {
+        match self {
+            Baudrate::Baud1200 => 1_200,
+            Baudrate::Baud2400 => 2_400,
+            Baudrate::Baud4800 => 4_800,
+            Baudrate::Baud9600 => 9_600,
+            Baudrate::Baud14400 => 14_400,
+            Baudrate::Baud19200 => 19_200,
+            Baudrate::Baud38400 => 38_400,
+            Baudrate::Baud57600 => 57_600,
+            Baudrate::Baud115200 => 115_200,
+        }
+    }
+
+    /// Returns the standard rate of `bits_per_second` bits per second, if
+    /// any.
+    pub const fn from_u32(bits_per_second: u32) -> Option<Self> {
+        let mut i = 0;
+        while i < Self::ALL.len() {
+            if Self::ALL[i].as_u32() == bits_per_second {
+                return Some(Self::ALL[i]);
+            }
+            i += 1;
+        }
+        None
+    }
+}
+
+impl From<Baudrate> for u32 {
+    fn from(baudrate: Baudrate) -> Self {
+        baudrate.as_u32()
+    }
+}
+
+impl TryFrom<u32> for Baudrate {
+    type Error = BaudrateError;
+
+    fn try_from(bits_per_second: u32) -> Result<Self, Self::Error> {
+        Self::from_u32(bits_per_second).ok_or(BaudrateError::Unsupported(bits_per_second))
+    }
+}
+
+impl core::str::FromStr for Baudrate {
+    type Err = BaudrateError;
+
+    /// Parses plain bits per second (`"9600"`) or the kilo notation, where
+    /// `k` stands for the decimal point (`"9k6"`, `"19k2"`, `"115k2"`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || BaudrateError::Invalid(s.to_string());
+        // `u32::from_str` would also accept a leading `+`.
+        let digits = |part: &str| {
+            if part.bytes().all(|b| b.is_ascii_digit()) {
+                part.parse::<u32>().ok()
+            } else {
+                None
+            }
+        };
+        let text = s.trim();
+        let bits_per_second = match text.split_once(['k', 'K']) {
+            Some((kilo, fraction)) if fraction.len() <= 3 => {
+                let fraction = if fraction.is_empty() {
+                    0
+                } else {
+                    digits(fraction).ok_or_else(invalid)? * 10u32.pow(3 - fraction.len() as u32)
+                };
+                digits(kilo)
+                    .and_then(|kilo| kilo.checked_mul(1_000))
+                    .and_then(|kilo| kilo.checked_add(fraction))
+                    .ok_or_else(invalid)?
+            }
+            Some(_) => return Err(invalid()),
+            None => digits(text).ok_or_else(invalid)?,
+        };
+        Self::try_from(bits_per_second)
+    }
+}
+
+impl core::fmt::Display for Baudrate {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_u32())
+    }
+}
@@ -0,0 +1,41 @@
+/// ## BitOrder
+///
+/// Order in which coil and discrete input states are packed into the bytes
+/// of a frame.
+///
+/// The Modbus specification mandates [`BitOrder::LsbFirst`]: the first
+/// addressed coil occupies the least significant bit of the first byte. A few
+/// devices deviate and pack the first coil into the most significant bit;
+/// use [`Request::set_bit_order`](crate::Request::set_bit_order) to talk to
+/// them.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BitOrder {
+    /// The first coil is stored in bit 0 of each byte, as the specification requires.
+    #[default]
+    LsbFirst,
+
+    /// The first coil is stored in bit 7 of each byte.
+    MsbFirst,
+}
+
+impl BitOrder {
+    /// Returns the mask selecting the `index`-th coil (`0..8`) within a byte.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::BitOrder;
+    ///
+    /// assert_eq!(BitOrder::LsbFirst.mask(0), 0b0000_0001);
+    /// assert_eq!(BitOrder::MsbFirst.mask(0), 0b1000_0000);
+    /// assert_eq!(BitOrder::MsbFirst.mask(7), 0b0000_0001);
+    /// ```
+    ///
+    pub const fn mask(&self, index: usize) -> u8 {
+        match self {
+            BitOrder::LsbFirst => 0b0000_0001 << index,
+            BitOrder::MsbFirst => 0b1000_0000 >> index,
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use alloc::vec::Vec;
+
+
+/// ## DataBlock
+///
+/// Contiguous run of coils, discrete inputs or registers served by a
+/// [`Slave`](crate::Slave), starting at a fixed address.
+///
+/// A request is served only when every address it touches lies inside the
+/// block; requests that overlap it partially are rejected just like requests
+/// entirely outside of it.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::DataBlock;
+///
+/// let block = DataBlock::new(0x0010, vec![1u16, 2, 3]);
+/// assert_eq!(block.last(), Some(0x0012));
+///
+/// assert_eq!(block.range(0x0010, 3), Some(&[1, 2, 3][..]));
+/// assert_eq!(block.range(0x0012, 1), Some(&[3][..]));
+/// assert_eq!(block.range(0x000F, 2), None); // starts before the block
+/// assert_eq!(block.range(0x0012, 2), None); // runs past the block
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataBlock<T> {
+    start: u16,
+    values: Vec<T>,
+}
+
+impl<T> DataBlock<T> {
+    /// Creates a block holding `values` at consecutive addresses from `start`.
+    ///
+    /// ---
+    /// # Panics
+    /// Panics when the block would extend past address `0xFFFF`.
+    pub fn new(start: u16, values: Vec<T>) -> Self {
+        assert!(
+            start as usize + values.len() <= 0x1_0000,
+            "data block extends past address 0xFFFF"
+        );
+        Self { start, values }
+    }
+
+    /// Creates a block without any address.
+    pub const fn empty() -> Self {
+        Self {
+            start: 0,
+            values: Vec::new(),
+        }
+    }
+
+    /// Returns the first address of the block.
+    pub fn start(&self) -> u16 {
+        self.start
+    }
+
+    /// Returns the last address of the block, or [`None`] when it is empty.
+    pub fn last(&self) -> Option<u16> {
+        (!self.values.is_empty()).then(|| self.start + (self.values.len() - 1) as u16)
+    }
+
+    /// Returns the number of addresses in the block.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` when the block holds no address.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns `true` when `quantity` addresses from `start` all lie inside
+    /// the block.
+    pub fn contains(&self, start: u16, quantity: u16) -> bool {
+        self.range(start, quantity).is_some()
+    }
+
+    /// Returns the values of `quantity` addresses from `start`, or [`None`]
+    /// unless all of them lie inside the block.
+    pub fn range(&self, start: u16, quantity: u16) -> Option<&[T]> {
+        let offset = start.checked_sub(self.start)? as usize;
+        self.values.get(offset..offset + quantity as usize)
+    }
+
+    /// Mutable counterpart of [`range`](Self::range).
+    pub fn range_mut(&mut self, start: u16, quantity: u16) -> Option<&mut [T]> {
+        let offset = start.checked_sub(self.start)? as usize;
+        self.values.get_mut(offset..offset + quantity as usize)
+    }
+
+    /// Returns the value at `address`, if it lies inside the block.
+    pub fn get(&self, address: u16) -> Option<&T> {
+        self.range(address, 1)?.first()
+    }
+
+    /// Returns a mutable reference to the value at `address`, if it lies
+    /// inside the block.
+    pub fn get_mut(&mut self, address: u16) -> Option<&mut T> {
+        self.range_mut(address, 1)?.first_mut()
+    }
+}
+
+impl<T> Default for DataBlock<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
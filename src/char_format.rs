@@ -0,0 +1,157 @@
+/// Parity bit setting of a serial character frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    /// No parity bit.
+    None,
+
+    /// Even parity; the Modbus RTU default.
+    Even,
+
+    /// Odd parity.
+    Odd,
+}
+
+
+/// ## CharFormat
+///
+/// Describes how a single character is framed on the serial line (data bits,
+/// parity, and stop bits). Timing calculations such as the T3.5 silent
+/// interval depend on the resulting number of bits per character.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CharFormat {
+    data_bits: u8,
+    parity: Parity,
+    stop_bits: u8,
+}
+
+impl CharFormat {
+    /// 8 data bits, no parity, 1 stop bit.
+    pub const N81: Self = Self::new(8, Parity::None, 1);
+
+    /// 8 data bits, no parity, 2 stop bits.
+    pub const N82: Self = Self::new(8, Parity::None, 2);
+
+    /// 8 data bits, even parity, 1 stop bit. Default format of the Modbus RTU specification.
+    pub const E81: Self = Self::new(8, Parity::Even, 1);
+
+    /// 8 data bits, odd parity, 1 stop bit.
+    pub const O81: Self = Self::new(8, Parity::Odd, 1);
+
+    /// 7 data bits, even parity, 1 stop bit. Common on Modbus ASCII links.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::CharFormat;
+    ///
+    /// assert_eq!(CharFormat::E71.bits_per_char(), 10);
+    /// assert_eq!(CharFormat::E71.to_string(), "7E1");
+    /// ```
+    ///
+    pub const E71: Self = Self::new(7, Parity::Even, 1);
+
+    /// 7 data bits, odd parity, 1 stop bit.
+    pub const O71: Self = Self::new(7, Parity::Odd, 1);
+
+    /// Creates a character format from its data bits, parity, and stop bits.
+    ///
+    /// ---
+    /// # Panics
+    /// Panics when `data_bits` is not within `5..=8` or `stop_bits` is not `1` or `2`.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{CharFormat, Parity};
+    ///
+    /// let format = CharFormat::new(8, Parity::Even, 1);
+    /// assert_eq!(format, CharFormat::E81);
+    /// assert_eq!(format.bits_per_char(), 11);
+    /// ```
+    ///
+    pub const fn new(data_bits: u8, parity: Parity, stop_bits: u8) -> Self {
+        assert!(
+            data_bits >= 5 && data_bits <= 8,
+            "data bits must be within 5..=8"
+        );
+        assert!(stop_bits == 1 || stop_bits == 2, "stop bits must be 1 or 2");
+        Self {
+            data_bits,
+            parity,
+            stop_bits,
+        }
+    }
+
+    /// Returns the number of data bits per character.
+    pub const fn data_bits(&self) -> u8 {
+        self.data_bits
+    }
+
+    /// Returns the parity setting.
+    pub const fn parity(&self) -> Parity {
+        self.parity
+    }
+
+    /// Returns the number of stop bits per character.
+    pub const fn stop_bits(&self) -> u8 {
+        self.stop_bits
+    }
+
+    /// Returns the total number of bits on the wire for one character,
+    /// including the start bit.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::CharFormat;
+    ///
+    /// assert_eq!(CharFormat::N81.bits_per_char(), 10);
+    /// assert_eq!(CharFormat::N82.bits_per_char(), 11);
+    /// ```
+    ///
+    pub const fn bits_per_char(&self) -> u32 {
+        let parity_bits = match self.parity {
+            Parity::None => 0,
+            Parity::Even | Parity::Odd => 1,
+        };
+        1 + self.data_bits as u32 + parity_bits + self.stop_bits as u32
+    }
+
+    /// Returns the time needed to transmit one character at `baud_rate`.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::CharFormat;
+    ///
+    /// let char_time = CharFormat::N81.char_time(10_000);
+    /// assert_eq!(char_time, std::time::Duration::from_millis(1));
+    /// ```
+    ///
+    pub fn char_time(&self, baud_rate: u32) -> core::time::Duration {
+        core::time::Duration::from_secs_f64(self.bits_per_char() as f64 / baud_rate as f64)
+    }
+
+    /// Returns the Modbus RTU T3.5 silent interval at `baud_rate`.
+    pub fn idle_time(&self, baud_rate: u32) -> core::time::Duration {
+        self.char_time(baud_rate).mul_f64(3.5)
+    }
+}
+
+impl Default for CharFormat {
+    fn default() -> Self {
+        Self::E81
+    }
+}
+
+impl core::fmt::Display for CharFormat {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let parity = match self.parity {
+            Parity::None => 'N',
+            Parity::Even => 'E',
+            Parity::Odd => 'O',
+        };
+        write!(f, "{}{}{}", self.data_bits, parity, self.stop_bits)
+    }
+}
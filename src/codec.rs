@@ -0,0 +1,301 @@
+use crate::error::{RequestFrameError, RequestPacketError, ResponsePacketError};
+use crate::{BitOrder, Function, Request, Response};
+use alloc::vec::Vec;
+
+
+/// ## RtuCodec
+///
+/// Modbus RTU framing detached from any transport, in the spirit of
+/// tokio-util's `Encoder`/`Decoder`: requests and responses are encoded into
+/// a byte buffer, and complete frames are decoded out of whatever bytes were
+/// received so far.
+///
+/// Frame boundaries are found from the content alone, without the T3.5
+/// silence timing: the length follows from the function code and byte count
+/// of the frame, or, for the encapsulated interface transport, from the
+/// shortest prefix with a valid CRC. The codec therefore works over any
+/// stream that carries RTU frames, such as a TCP socket to a serial device
+/// server or a pipe in a test.
+///
+/// On the master side [`encode_request`](Self::encode_request) remembers the
+/// request, so that [`decode_response`](Self::decode_response) can validate
+/// the response against it. On the slave side
+/// [`decode_request`](Self::decode_request) and
+/// [`encode_response`](Self::encode_response) are used.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{test_vectors, Function, Request, Response, RtuCodec};
+///
+/// let mut codec = RtuCodec::new();
+/// let function = Function::ReadHoldingRegisters { starting_address: 0x006B, quantity: 3 };
+/// let request = Request::new(0x11, &function, std::time::Duration::from_millis(100));
+///
+/// let mut tx = Vec::new();
+/// codec.encode_request(&request, &mut tx).unwrap();
+/// assert_eq!(&tx[..], test_vectors::READ_HOLDING_REGISTERS.request);
+///
+/// // The response arrives in two chunks.
+/// let (head, tail) = test_vectors::READ_HOLDING_REGISTERS.response.split_at(4);
+/// let mut rx = head.to_vec();
+/// assert_eq!(codec.decode_response(&mut rx), Ok(None));
+/// rx.extend_from_slice(tail);
+/// assert_eq!(
+///     codec.decode_response(&mut rx),
+///     Ok(Some(Response::Value(Box::new([0x022B, 0x0000, 0x0064]))))
+/// );
+/// assert!(rx.is_empty());
+/// ```
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RtuCodec {
+    pending: Option<(u8, Function, BitOrder)>,
+}
+
+impl RtuCodec {
+    /// Creates a codec with no request outstanding.
+    pub const fn new() -> Self {
+        Self { pending: None }
+    }
+
+    /// Appends the frame of `request` to `dst` and, unless it is a
+    /// broadcast, waits for its response.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`RequestPacketError`] when the request cannot be encoded.
+    pub fn encode_request(
+        &mut self,
+        request: &Request,
+        dst: &mut Vec<u8>,
+    ) -> Result<(), RequestPacketError> {
+        dst.extend_from_slice(&request.to_bytes()?);
+        self.pending = (!request.is_broadcasting()).then(|| {
+            (
+                request.modbus_id(),
+                request.function().clone(),
+                request.bit_order(),
+            )
+        });
+        Ok(())
+    }
+
+    /// Returns `true` while a request sent through
+    /// [`encode_request`](Self::encode_request) awaits its response.
+    pub fn is_waiting(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Takes the response to the outstanding request off the front of `src`.
+    ///
+    /// Returns `Ok(None)` while `src` does not hold a whole frame yet. Once
+    /// it does, the frame is removed from `src` and the request is no longer
+    /// outstanding, whether the frame decodes or not.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`ResponsePacketError`] when the frame fails validation, or
+    /// [`UnexpectedResponder`](ResponsePacketError::UnexpectedResponder)
+    /// when bytes arrive with no request outstanding; those bytes are
+    /// discarded.
+    pub fn decode_response(
+        &mut self,
+        src: &mut Vec<u8>,
+    ) -> Result<Option<Response>, ResponsePacketError> {
+        let (consumed, result) = self.take_response(src);
+        src.drain(..consumed);
+        result
+    }
+
+    /// Takes a request off the front of `src`, as a slave receives it.
+    ///
+    /// Returns `Ok(None)` while `src` does not hold a whole frame yet. Once
+    /// it does, the frame is removed from `src`, whether it decodes or not.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`RequestFrameError`] when the frame fails validation.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{test_vectors, Function, RtuCodec};
+    ///
+    /// let mut codec = RtuCodec::new();
+    /// let mut rx = test_vectors::WRITE_MULTIPLE_REGISTERS.request.to_vec();
+    /// rx.extend_from_slice(test_vectors::READ_COILS.request);
+    ///
+    /// let (_, first) = codec.decode_request(&mut rx).unwrap().unwrap();
+    /// assert!(matches!(first, Function::WriteMultipleRegisters { .. }));
+    /// let (_, second) = codec.decode_request(&mut rx).unwrap().unwrap();
+    /// assert!(matches!(second, Function::ReadCoils { .. }));
+    /// assert_eq!(codec.decode_request(&mut rx), Ok(None));
+    /// ```
+    ///
+    pub fn decode_request(
+        &mut self,
+        src: &mut Vec<u8>,
+    ) -> Result<Option<(u8, Function)>, RequestFrameError> {
+        let Some(len) = request_len(src) else {
+            if src.len() >= MAX_FRAME_LEN {
+                src.clear();
+                return Err(RequestFrameError::InvalidFormat);
+            }
+            return Ok(None);
+        };
+        let result = crate::frame::parse_request(&src[..len]);
+        src.drain(..len);
+        result.map(Some)
+    }
+
+    /// Appends the frame answering a request with `function_code` by
+    /// `response` from slave `modbus_id` to `dst`.
+    ///
+    /// Nothing is appended for [`Response::Success`], which has no frame of
+    /// its own.
+    pub fn encode_response(
+        &mut self,
+        modbus_id: u8,
+        function_code: u8,
+        response: &Response,
+        dst: &mut Vec<u8>,
+    ) {
+        if let Some(frame) = crate::frame::encode_response(modbus_id, function_code, response) {
+            dst.extend_from_slice(&frame);
+        }
+    }
+
+    /// Decodes the response at the front of `src`, and returns how many
+    /// bytes it occupies along with the outcome.
+    fn take_response(
+        &mut self,
+        src: &[u8],
+    ) -> (usize, Result<Option<Response>, ResponsePacketError>) {
+        let Some((modbus_id, function, bit_order)) = &self.pending else {
+            return match src.first() {
+                Some(&responder) => (
+                    src.len(),
+                    Err(ResponsePacketError::UnexpectedResponder(responder)),
+                ),
+                None => (0, Ok(None)),
+            };
+        };
+        let Some(len) = response_len(function, src) else {
+            if src.len() >= MAX_FRAME_LEN {
+                self.pending = None;
+                return (src.len(), Err(ResponsePacketError::InvalidFormat));
+            }
+            return (0, Ok(None));
+        };
+        let mut request = Request::new(*modbus_id, function, core::time::Duration::ZERO);
+        request.set_bit_order(*bit_order);
+        let result = Response::from_bytes(&request, &src[..len]);
+        self.pending = None;
+        (len, result.map(Some))
+    }
+}
+
+
+/// Encodes requests into a `tokio_util` framed sink.
+///
+/// Errors are reported as [`std::io::ErrorKind::InvalidData`], wrapping the
+/// [`RequestPacketError`].
+#[cfg(feature = "async")]
+impl tokio_util::codec::Encoder<Request<'_>> for RtuCodec {
+    type Error = std::io::Error;
+
+    fn encode(&mut self, item: Request<'_>, dst: &mut bytes::BytesMut) -> std::io::Result<()> {
+        let mut frame = Vec::new();
+        self.encode_request(&item, &mut frame)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        dst.extend_from_slice(&frame);
+        Ok(())
+    }
+}
+
+
+/// Decodes responses out of a `tokio_util` framed stream.
+///
+/// Errors are reported as [`std::io::ErrorKind::InvalidData`], wrapping the
+/// [`ResponsePacketError`]; the offending frame is dropped, so the stream can
+/// be polled again.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use futures::{SinkExt, StreamExt};
+/// use modbus_rtu::{Function, Request, RtuCodec};
+/// use tokio_util::codec::Framed;
+///
+/// # async fn demo() -> std::io::Result<()> {
+/// let stream = tokio::net::TcpStream::connect("10.0.0.5:4001").await?;
+/// let mut framed = Framed::new(stream, RtuCodec::new());
+///
+/// let function = Function::ReadHoldingRegisters { starting_address: 0, quantity: 2 };
+/// framed.send(Request::new(0x01, &function, std::time::Duration::from_millis(200))).await?;
+/// let response = framed.next().await.transpose()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+#[cfg(feature = "async")]
+impl tokio_util::codec::Decoder for RtuCodec {
+    type Item = Response;
+    type Error = std::io::Error;
+
+    fn decode(&mut self, src: &mut bytes::BytesMut) -> std::io::Result<Option<Response>> {
+        let (consumed, result) = self.take_response(src);
+        bytes::Buf::advance(src, consumed);
+        result.map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+
+/// Longest Modbus RTU frame.
+pub(crate) const MAX_FRAME_LEN: usize = crate::limits::MAX_PDU + 3;
+
+
+/// Returns the length of the response to `function` starting `head`, or
+/// [`None`] while `head` is too short to tell or to hold it.
+fn response_len(function: &Function, head: &[u8]) -> Option<usize> {
+    let len = response_frame_len(function, head)?;
+    (head.len() >= len).then_some(len)
+}
+
+
+/// Returns the length of the response to `function` starting `head`, or
+/// [`None`] while `head` is too short to tell.
+pub(crate) fn response_frame_len(function: &Function, head: &[u8]) -> Option<usize> {
+    Some(match (head.get(1)?, function) {
+        (code, _) if code & 0x80 != 0 => 5,
+        (
+            _,
+            Function::ReadCoils { .. }
+            | Function::ReadDiscreteInputs { .. }
+            | Function::ReadHoldingRegisters { .. }
+            | Function::ReadInputRegisters { .. },
+        ) => 5 + *head.get(2)? as usize,
+        (_, Function::EncapsulatedInterface { .. }) => crc_len(head, 5)?,
+        _ => 8,
+    })
+}
+
+
+/// Returns the length of the request starting `head`, or [`None`] while
+/// `head` is too short to tell or to hold it.
+fn request_len(head: &[u8]) -> Option<usize> {
+    let len = match head.get(1)? {
+        0x01..=0x06 => 8,
+        0x0F | 0x10 => 9 + *head.get(6)? as usize,
+        _ => crc_len(head, crate::limits::MIN_FRAME_LEN)?,
+    };
+    (head.len() >= len).then_some(len)
+}
+
+
+/// Returns the length of the shortest prefix of `head`, at least `min` bytes
+/// long, that ends with its valid CRC.
+fn crc_len(head: &[u8], min: usize) -> Option<usize> {
+    (min..=head.len().min(MAX_FRAME_LEN)).find(|&len| crate::crc::validate(&head[..len]).is_ok())
+}
@@ -28,7 +28,7 @@
 /// assert_eq!(bytes, [0x01, 0x04, 0x00, 0x00, 0x00, 0x04, 0xF1, 0xC9]);
 /// ```
 ///
-pub(crate) fn generate(bytes: &[u8]) -> u16 {
+pub(crate) const fn generate(bytes: &[u8]) -> u16 {
     // CRC16-Modbus lookup table.
     const TABLE: [u16; 256] = [
         0x0000, 0xC0C1, 0xC181, 0x0140, 0xC301, 0x03C0, 0x0280, 0xC241, 0xC601, 0x06C0, 0x0780,
@@ -58,9 +58,11 @@ pub(crate) fn generate(bytes: &[u8]) -> u16 {
     ];
 
     let mut crc: u16 = 0xFFFF;
-    for &byte in bytes {
-        let index: u16 = (crc ^ byte as u16) & 0x00FF;
+    let mut i = 0;
+    while i < bytes.len() {
+        let index: u16 = (crc ^ bytes[i] as u16) & 0x00FF;
         crc = (crc >> 8) ^ TABLE[index as usize];
+        i += 1;
     }
 
     crc
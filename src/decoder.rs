@@ -0,0 +1,120 @@
+use crate::Response;
+use crate::error::ResponsePacketError;
+use alloc::boxed::Box;
+
+
+/// ## ResponseDecoder
+///
+/// Decodes the response to a function the crate does not model, typically a
+/// vendor-specific function code.
+///
+/// Decoders see the payload only, i.e. the frame without slave id, function
+/// code and CRC, which have been checked already. Any closure with a
+/// matching signature is also a decoder.
+///
+pub trait ResponseDecoder: Send + Sync {
+    /// Decodes `payload` into a [`Response`].
+    fn decode(&self, payload: &[u8]) -> Result<Response, ResponsePacketError>;
+}
+
+impl<F> ResponseDecoder for F
+where
+    F: Fn(&[u8]) -> Result<Response, ResponsePacketError> + Send + Sync,
+{
+    fn decode(&self, payload: &[u8]) -> Result<Response, ResponsePacketError> {
+        self(payload)
+    }
+}
+
+impl core::fmt::Debug for dyn ResponseDecoder {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ResponseDecoder")
+    }
+}
+
+
+/// ## DecoderRegistry
+///
+/// [`ResponseDecoder`]s keyed by function code.
+///
+/// [`Response::from_bytes_with`] delegates frames carrying a registered code
+/// the crate does not decode itself, instead of rejecting them as
+/// [`InvalidFormat`](ResponsePacketError::InvalidFormat).
+/// [`decode`](Self::decode) decodes raw frames, such as those returned by
+/// `Master::send_raw`.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::error::ResponsePacketError;
+/// use modbus_rtu::{raw, DecoderRegistry, Response};
+///
+/// // Vendor function 0x41 answers with a byte count followed by registers.
+/// let mut decoders = DecoderRegistry::new();
+/// decoders.register(0x41, |payload: &[u8]| {
+///     let (count, data) = payload.split_first().ok_or(ResponsePacketError::InvalidFormat)?;
+///     if data.len() != *count as usize || count % 2 != 0 {
+///         return Err(ResponsePacketError::InvalidFormat);
+///     }
+///     let values = data.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]]));
+///     Ok(Response::Value(values.collect()))
+/// });
+///
+/// let mut buf = Vec::new();
+/// let frame = raw::frame(0x01, 0x41, &[0x04, 0x00, 0x0A, 0x01, 0x02], &mut buf);
+/// assert_eq!(decoders.decode(frame), Ok(Response::Value(Box::new([0x000A, 0x0102]))));
+///
+/// let frame = raw::frame(0x01, 0x42, &[], &mut buf);
+/// assert_eq!(decoders.decode(frame), Err(ResponsePacketError::InvalidFormat));
+/// ```
+///
+#[derive(Debug, Default)]
+pub struct DecoderRegistry {
+    decoders: alloc::collections::BTreeMap<u8, Box<dyn ResponseDecoder>>,
+}
+
+impl DecoderRegistry {
+    /// Creates a registry without decoders.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Installs `decoder` for `function_code`, replacing any previous one.
+    pub fn register(&mut self, function_code: u8, decoder: impl ResponseDecoder + 'static) {
+        self.decoders.insert(function_code, Box::new(decoder));
+    }
+
+    /// Removes the decoder of `function_code`.
+    pub fn remove(&mut self, function_code: u8) {
+        self.decoders.remove(&function_code);
+    }
+
+    /// Returns `true` when a decoder is installed for `function_code`.
+    pub fn contains(&self, function_code: u8) -> bool {
+        self.decoders.contains_key(&function_code)
+    }
+
+    /// Checks the framing of a complete response frame and decodes it with
+    /// the decoder of its function code.
+    ///
+    /// Exception responses are decoded to [`Response::Exception`] whatever
+    /// the function code.
+    ///
+    /// ---
+    /// # Errors
+    /// Fails like [`raw::check`](crate::raw::check), with
+    /// [`InvalidFormat`](ResponsePacketError::InvalidFormat) when no decoder
+    /// is installed for the function code, and with whatever the decoder
+    /// returns.
+    pub fn decode(&self, frame: &[u8]) -> Result<Response, ResponsePacketError> {
+        let (_, function_code, payload) = crate::raw::check(frame)?;
+        if function_code & 0x80 != 0 {
+            let code = *payload.first().ok_or(ResponsePacketError::InvalidFormat)?;
+            return Ok(Response::Exception(crate::Exception::from_code(code)));
+        }
+        match self.decoders.get(&function_code) {
+            Some(decoder) => decoder.decode(payload),
+            None => Err(ResponsePacketError::InvalidFormat),
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use crate::limits::{MAX_READ_COILS, MAX_READ_REGISTERS};
+use crate::{DataBlock, Function, Response, Table};
+use alloc::vec::Vec;
+
+
+/// ## DeviceTemplate
+///
+/// Describes which address ranges of the four tables a device exposes, e.g.
+/// "coils `0..32`, holding registers `0..64` and `100..110`, input registers
+/// `0..16`".
+///
+/// [`functions`](Self::functions) plans the minimal set of read requests
+/// covering every range, and [`DeviceImage::decode`] assembles their
+/// responses into a single image of the device. No request spans the gap
+/// between two ranges of a table, so addresses the device does not expose
+/// are never read.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{DeviceTemplate, Function, Table};
+///
+/// let template = DeviceTemplate::new()
+///     .with(Table::Coils, 0..32)
+///     .with(Table::HoldingRegisters, 0..200)
+///     .also(Table::HoldingRegisters, 1000..1010)
+///     .with(Table::InputRegisters, 0..16);
+///
+/// // 200 holding registers need two requests of at most 125.
+/// assert_eq!(
+///     template.functions(),
+///     vec![
+///         Function::ReadCoils { starting_address: 0, quantity: 32 },
+///         Function::ReadHoldingRegisters { starting_address: 0, quantity: 125 },
+///         Function::ReadHoldingRegisters { starting_address: 125, quantity: 75 },
+///         Function::ReadHoldingRegisters { starting_address: 1000, quantity: 10 },
+///         Function::ReadInputRegisters { starting_address: 0, quantity: 16 },
+///     ]
+/// );
+/// ```
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceTemplate {
+    ranges: [Vec<core::ops::Range<u16>>; 4],
+}
+
+impl DeviceTemplate {
+    /// Creates a template exposing nothing.
+    pub const fn new() -> Self {
+        Self {
+            ranges: [Vec::new(), Vec::new(), Vec::new(), Vec::new()],
+        }
+    }
+
+    /// Declares that the device exposes `range` of `table`, replacing any
+    /// previous range of that table. Empty ranges remove the table.
+    pub fn with(mut self, table: Table, range: core::ops::Range<u16>) -> Self {
+        self.ranges[index(table)].clear();
+        self.also(table, range)
+    }
+
+    /// Declares that the device exposes `range` of `table` as well, next to
+    /// the ranges declared before. Overlapping and adjoining ranges are
+    /// merged; empty ranges are ignored.
+    pub fn also(mut self, table: Table, range: core::ops::Range<u16>) -> Self {
+        if range.is_empty() {
+            return self;
+        }
+        let ranges = &mut self.ranges[index(table)];
+        ranges.push(range);
+        ranges.sort_by_key(|range| range.start);
+        ranges.dedup_by(|next, run| {
+            let adjoins = next.start <= run.end;
+            if adjoins {
+                run.end = run.end.max(next.end);
+            }
+            adjoins
+        });
+        self
+    }
+
+    /// Returns the span of the exposed ranges of `table`, from the first
+    /// address of the first one to the end of the last one.
+    pub fn range(&self, table: Table) -> Option<core::ops::Range<u16>> {
+        let ranges = self.ranges(table);
+        Some(ranges.first()?.start..ranges.last()?.end)
+    }
+
+    /// Returns the exposed ranges of `table`, in ascending order.
+    pub fn ranges(&self, table: Table) -> &[core::ops::Range<u16>] {
+        &self.ranges[index(table)]
+    }
+
+    /// Returns the read requests covering every exposed range, table by
+    /// table, each as large as the protocol allows.
+    pub fn functions(&self) -> Vec<Function> {
+        TABLES
+            .iter()
+            .flat_map(|&table| {
+                self.ranges(table)
+                    .iter()
+                    .flat_map(move |range| reads(table, range.clone()))
+                    .map(move |(start, quantity)| table.read(start, quantity))
+            })
+            .collect()
+    }
+}
+
+
+/// ## DeviceImage
+///
+/// Values of every range of a [`DeviceTemplate`], read in one go.
+///
+/// Tables the template does not expose are empty.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{DeviceImage, DeviceTemplate, Response, Table};
+///
+/// let template = DeviceTemplate::new()
+///     .with(Table::Coils, 0..3)
+///     .with(Table::HoldingRegisters, 0x0010..0x0012)
+///     .also(Table::HoldingRegisters, 0x0100..0x0101);
+/// let responses = vec![
+///     Response::Status(Box::new([true, false, true])),
+///     Response::Value(Box::new([0x1234, 0x5678])),
+///     Response::Value(Box::new([0x9ABC])),
+/// ];
+///
+/// let image = DeviceImage::decode(&template, responses).unwrap();
+/// assert_eq!(image.coils().get(0x0002), Some(&true));
+/// assert_eq!(image.holding_registers().range(0x0010, 2), Some(&[0x1234, 0x5678][..]));
+/// assert_eq!(image.holding_registers().get(0x0100), Some(&0x9ABC));
+/// assert_eq!(image.holding_registers().get(0x0012), None);
+/// assert!(image.input_registers().is_empty());
+/// ```
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeviceImage {
+    coils: ImageTable<bool>,
+    discrete_inputs: ImageTable<bool>,
+    holding_registers: ImageTable<u16>,
+    input_registers: ImageTable<u16>,
+}
+
+impl DeviceImage {
+    /// Assembles the responses to [`DeviceTemplate::functions`], in the same
+    /// order, into an image.
+    ///
+    /// Returns [`None`] when the responses do not match the planned requests:
+    /// too few or too many, of the wrong kind or length, or exceptions.
+    pub fn decode(
+        template: &DeviceTemplate,
+        responses: impl IntoIterator<Item = Response>,
+    ) -> Option<Self> {
+        let mut image = Self::default();
+        let mut responses = responses.into_iter();
+        for table in TABLES {
+            for range in template.ranges(table) {
+                let mut bits = Vec::new();
+                let mut words = Vec::new();
+                for (_, quantity) in reads(table, range.clone()) {
+                    let quantity = quantity as usize;
+                    match responses.next()? {
+                        Response::Status(states) if table.is_bit() && states.len() >= quantity => {
+                            bits.extend_from_slice(&states[..quantity])
+                        }
+                        Response::Value(values) if !table.is_bit() && values.len() == quantity => {
+                            words.extend_from_slice(&values)
+                        }
+                        _ => return None,
+                    }
+                }
+                let start = range.start;
+                match table {
+                    Table::Coils => image.coils.push(start, bits),
+                    Table::DiscreteInputs => image.discrete_inputs.push(start, bits),
+                    Table::HoldingRegisters => image.holding_registers.push(start, words),
+                    Table::InputRegisters => image.input_registers.push(start, words),
+                }
+            }
+        }
+        if responses.next().is_some() {
+            return None;
+        }
+        Some(image)
+    }
+
+    /// Returns the coils read.
+    pub fn coils(&self) -> &ImageTable<bool> {
+        &self.coils
+    }
+
+    /// Returns the discrete inputs read.
+    pub fn discrete_inputs(&self) -> &ImageTable<bool> {
+        &self.discrete_inputs
+    }
+
+    /// Returns the holding registers read.
+    pub fn holding_registers(&self) -> &ImageTable<u16> {
+        &self.holding_registers
+    }
+
+    /// Returns the input registers read.
+    pub fn input_registers(&self) -> &ImageTable<u16> {
+        &self.input_registers
+    }
+}
+
+
+/// ## ImageTable
+///
+/// Values of one table of a [`DeviceImage`], a [`DataBlock`] per exposed
+/// range of the [`DeviceTemplate`].
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageTable<T> {
+    blocks: Vec<DataBlock<T>>,
+}
+
+impl<T> Default for ImageTable<T> {
+    fn default() -> Self {
+        Self { blocks: Vec::new() }
+    }
+}
+
+impl<T> ImageTable<T> {
+    /// Returns the blocks read, one per exposed range, in ascending order.
+    pub fn blocks(&self) -> &[DataBlock<T>] {
+        &self.blocks
+    }
+
+    /// Returns `true` when nothing of the table was read.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.iter().all(DataBlock::is_empty)
+    }
+
+    /// Returns the value at `address`, or [`None`] when it was not read.
+    pub fn get(&self, address: u16) -> Option<&T> {
+        self.blocks.iter().find_map(|block| block.get(address))
+    }
+
+    /// Returns the `quantity` values from `start`, or [`None`] unless all of
+    /// them lie in one exposed range.
+    pub fn range(&self, start: u16, quantity: u16) -> Option<&[T]> {
+        self.blocks
+            .iter()
+            .find_map(|block| block.range(start, quantity))
+    }
+
+    /// Appends the block of `values` read from `start`.
+    fn push(&mut self, start: u16, values: Vec<T>) {
+        self.blocks.push(DataBlock::new(start, values));
+    }
+}
+
+
+/// Splits `range` of `table` into the starts and quantities of requests as
+/// large as the protocol allows.
+fn reads(table: Table, range: core::ops::Range<u16>) -> impl Iterator<Item = (u16, u16)> {
+    let max = if table.is_bit() {
+        MAX_READ_COILS
+    } else {
+        MAX_READ_REGISTERS
+    };
+    (range.start..range.end)
+        .step_by(max as usize)
+        .map(move |start| (start, max.min(range.end - start)))
+}
+
+
+/// The four tables, in the order their requests are planned.
+const TABLES: [Table; 4] = [
+    Table::Coils,
+    Table::DiscreteInputs,
+    Table::HoldingRegisters,
+    Table::InputRegisters,
+];
+
+
+/// Returns the position of `table` in [`TABLES`].
+const fn index(table: Table) -> usize {
+    match table {
+        Table::Coils => 0,
+        Table::DiscreteInputs => 1,
+        Table::HoldingRegisters => 2,
+        Table::InputRegisters => 3,
+    }
+}
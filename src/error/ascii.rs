@@ -0,0 +1,40 @@
+/// Errors that can occur while converting a Modbus ASCII frame with
+/// [`ascii::to_rtu`](crate::ascii::to_rtu).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsciiError {
+    /// The frame is shorter than the minimum Modbus ASCII length.
+    TooShort(usize),
+
+    /// The frame does not start with `:` or does not end with CR LF.
+    MissingDelimiter,
+
+    /// The frame holds a character other than an upper-case hexadecimal
+    /// digit between its delimiters, or an odd number of them.
+    InvalidCharacter(u8),
+
+    /// Calculated LRC does not match the LRC present in the frame.
+    LRCMismatch { expected: u8, received: u8 },
+}
+
+impl core::fmt::Display for AsciiError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooShort(len) => write!(
+                f,
+                "ASCII frame too short; expected at least 9 characters but received {len}."
+            ),
+            Self::MissingDelimiter => {
+                f.write_str("ASCII frame does not start with ':' or end with CR LF.")
+            }
+            Self::InvalidCharacter(c) => {
+                write!(f, "ASCII frame holds invalid character 0x{c:02X}.")
+            }
+            Self::LRCMismatch { expected, received } => write!(
+                f,
+                "ASCII LRC mismatch: expected 0x{expected:02X}, received 0x{received:02X}."
+            ),
+        }
+    }
+}
+
+impl core::error::Error for AsciiError {}
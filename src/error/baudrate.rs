@@ -0,0 +1,24 @@
+use alloc::string::String;
+
+
+/// Errors that can occur while converting a value into a
+/// [`Baudrate`](crate::Baudrate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BaudrateError {
+    /// The rate is well-formed but not one of the standard rates.
+    Unsupported(u32),
+
+    /// The text is not a baud rate.
+    Invalid(String),
+}
+
+impl core::fmt::Display for BaudrateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Unsupported(rate) => write!(f, "{rate} baud is not a standard rate."),
+            Self::Invalid(text) => write!(f, "\"{text}\" is not a baud rate."),
+        }
+    }
+}
+
+impl core::error::Error for BaudrateError {}
@@ -0,0 +1,29 @@
+/// Errors that can occur while loading, saving or opening a
+/// [`Config`](crate::Config).
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The configuration file could not be read or written.
+    IO(std::io::Error),
+
+    /// The text is not valid TOML, or does not describe a configuration.
+    Syntax(toml::de::Error),
+
+    /// The configuration could not be formatted as TOML.
+    Serialize(toml::ser::Error),
+
+    /// The serial port of a bus could not be opened or configured.
+    Port(serialport::Error),
+}
+
+impl core::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::IO(error) => write!(f, "configuration file I/O error: {error}"),
+            Self::Syntax(error) => write!(f, "invalid configuration: {error}"),
+            Self::Serialize(error) => write!(f, "configuration cannot be saved: {error}"),
+            Self::Port(error) => write!(f, "cannot open the configured port: {error}"),
+        }
+    }
+}
+
+impl core::error::Error for ConfigError {}
@@ -1,11 +1,47 @@
 //! modbus-rtu crate error types
 
+use alloc::boxed::Box;
+
+#[cfg(feature = "ascii")]
+mod ascii;
+#[cfg(feature = "ascii")]
+pub use ascii::*;
+
+mod baudrate;
+pub use baudrate::*;
+
+#[cfg(feature = "config")]
+mod config;
+#[cfg(feature = "config")]
+pub use config::*;
+
+#[cfg(all(feature = "master", feature = "sync"))]
+mod ota;
+#[cfg(all(feature = "master", feature = "sync"))]
+pub use ota::*;
+
+mod request_frame;
+pub use request_frame::*;
+
 mod request_packet;
 pub use request_packet::*;
 
 mod response_packet;
 pub use response_packet::*;
 
+#[cfg(feature = "slave")]
+mod slave_model;
+#[cfg(feature = "slave")]
+pub use slave_model::*;
+
+#[cfg(feature = "template")]
+mod template;
+#[cfg(feature = "template")]
+pub use template::*;
+
+mod tunnel;
+pub use tunnel::*;
+
 use crate::Exception;
 
 
@@ -13,8 +49,9 @@ use crate::Exception;
 /// Modbus RTU device.
 #[derive(Debug)]
 pub enum Error {
-    /// The slave device replied with a Modbus exception response.
-    Exception(Exception),
+    /// The slave device replied with a Modbus exception response to the
+    /// summarized request.
+    Exception(Exception, crate::RequestSummary),
 
     /// The request packet could not be constructed; see [`RequestPacketError`]
     /// for details.
@@ -25,17 +62,185 @@ pub enum Error {
     Response(ResponsePacketError),
 
     /// Any I/O error surfaced by the underlying serial transport.
+    #[cfg(feature = "std")]
     IO(std::io::Error),
+
+    /// Repeated reads of the same block never returned a consistent
+    /// snapshot within the given number of attempts.
+    InconsistentRead { attempts: u32 },
+
+    /// Reading back written coils or registers returned something other than
+    /// what was written.
+    VerificationFailed {
+        expected: crate::Response,
+        actual: crate::Response,
+    },
+
+    /// The bytes read back from the line while transmitting differed from
+    /// the transmitted frame, i.e. another master was transmitting at the
+    /// same time, on every attempt.
+    BusCollision { sent: Box<[u8]>, echoed: Box<[u8]> },
+}
+
+
+/// Coarse classification of an [`Error`], so that retry policies and
+/// application code can branch without matching every nested variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// The exchange violated the protocol: a request that cannot be encoded,
+    /// or a response with an unexpected responder or malformed payload.
+    Protocol,
+
+    /// The transport failed to deliver an intact frame: I/O errors, timeouts,
+    /// CRC mismatches, truncated or echoed frames.
+    Transport,
+
+    /// The device answered, but with an exception or with data that did not
+    /// meet expectations.
+    Device(Option<Exception>),
+}
+
+
+impl Error {
+    /// Returns the [`ErrorClass`] of this error.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{Exception, Function, Request};
+    /// use modbus_rtu::error::{Error, ErrorClass, ResponsePacketError};
+    ///
+    /// let crc = Error::Response(ResponsePacketError::CRCMismatch { expected: 0x1234, received: 0x3412 });
+    /// assert_eq!(crc.class(), ErrorClass::Transport);
+    ///
+    /// let func = Function::ReadCoils { starting_address: 0, quantity: 8 };
+    /// let request = Request::new(0x01, &func, std::time::Duration::from_millis(100));
+    /// let busy = Error::Exception(Exception::DeviceBusy, request.summary());
+    /// assert_eq!(busy.class(), ErrorClass::Device(Some(Exception::DeviceBusy)));
+    /// ```
+    ///
+    pub fn class(&self) -> ErrorClass {
+        match self {
+            Error::Exception(exception, _) => ErrorClass::Device(Some(*exception)),
+            Error::Request(_) => ErrorClass::Protocol,
+            Error::Response(error) => match error {
+                ResponsePacketError::TooShort(_)
+                | ResponsePacketError::CRCMismatch { .. }
+                | ResponsePacketError::PrematureResponse(_)
+                | ResponsePacketError::ResponseOverflow { .. } => ErrorClass::Transport,
+                ResponsePacketError::UnexpectedResponder(_)
+                | ResponsePacketError::InvalidFormat => ErrorClass::Protocol,
+            },
+            #[cfg(feature = "std")]
+            Error::IO(_) => ErrorClass::Transport,
+            Error::BusCollision { .. } => ErrorClass::Transport,
+            Error::InconsistentRead { .. } | Error::VerificationFailed { .. } => {
+                ErrorClass::Device(None)
+            }
+        }
+    }
+
+    /// Returns `true` when repeating the same request may succeed.
+    ///
+    /// This covers timeouts and corrupted frames, devices reporting that they
+    /// are busy or that a gateway could not reach them, and reads that were
+    /// torn by concurrent updates. Requests that cannot be encoded and
+    /// exceptions such as illegal address or value are not retryable.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{Exception, Function, Request};
+    /// use modbus_rtu::error::Error;
+    ///
+    /// let func = Function::ReadCoils { starting_address: 0, quantity: 8 };
+    /// let summary = Request::new(0x01, &func, std::time::Duration::from_millis(100)).summary();
+    ///
+    /// # #[cfg(feature = "std")]
+    /// assert!(Error::IO(std::io::ErrorKind::TimedOut.into()).is_retryable());
+    /// assert!(Error::Exception(Exception::DeviceBusy, summary).is_retryable());
+    /// assert!(!Error::Exception(Exception::IllegalDataAddress, summary).is_retryable());
+    /// ```
+    ///
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Exception(exception, _) => matches!(
+                exception,
+                Exception::DeviceBusy
+                    | Exception::GatewayPathUnavailable
+                    | Exception::GatewayTargetDeviceFailedToRespond
+            ),
+            #[cfg(feature = "std")]
+            Error::IO(error) => matches!(
+                error.kind(),
+                std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::WouldBlock
+            ),
+            Error::InconsistentRead { .. } => true,
+            _ => self.class() == ErrorClass::Transport,
+        }
+    }
+
+    /// Returns `true` when the transport itself is unusable (the port
+    /// disappeared, access was revoked, the connection broke), so that
+    /// further requests will fail until it is reopened.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::error::Error;
+    ///
+    /// # #[cfg(feature = "std")]
+    /// assert!(Error::IO(std::io::ErrorKind::BrokenPipe.into()).is_fatal());
+    /// # #[cfg(feature = "std")]
+    /// assert!(!Error::IO(std::io::ErrorKind::TimedOut.into()).is_fatal());
+    /// ```
+    ///
+    pub fn is_fatal(&self) -> bool {
+        match self {
+            #[cfg(feature = "std")]
+            Error::IO(error) => matches!(
+                error.kind(),
+                std::io::ErrorKind::NotFound
+                    | std::io::ErrorKind::PermissionDenied
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::NotConnected
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::UnexpectedEof
+            ),
+            _ => false,
+        }
+    }
 }
 
 
 impl core::fmt::Display for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Error::Exception(exception) => write!(f, "device responsed {exception}"),
+            Error::Exception(exception, request) => {
+                write!(f, "device responsed {exception} to {request}")
+            }
             Error::Request(request_packet_error) => write!(f, "{request_packet_error}"),
             Error::Response(response_packet_error) => write!(f, "{response_packet_error}"),
+            #[cfg(feature = "std")]
             Error::IO(error) => write!(f, "{error}"),
+            Error::InconsistentRead { attempts } => {
+                write!(f, "no consistent read after {attempts} attempts")
+            }
+            Error::VerificationFailed { expected, actual } => {
+                write!(
+                    f,
+                    "write verification failed: expected {expected}, read back {actual}"
+                )
+            }
+            Error::BusCollision { sent, echoed } => {
+                write!(
+                    f,
+                    "bus collision: sent {sent:02X?}, read back {echoed:02X?}"
+                )
+            }
         }
     }
 }
@@ -0,0 +1,58 @@
+/// Errors that can end a firmware transfer started with
+/// [`Master::update_firmware`](crate::Master::update_firmware).
+#[derive(Debug)]
+pub enum OtaError {
+    /// The image needs more chunks than the transport can number.
+    ImageTooLarge { len: usize, max: usize },
+
+    /// The transfer was to start at file 0, which Write File Record does not
+    /// address; files are numbered from `0x0001` to `0xFFFF`.
+    InvalidFile { file: u16 },
+
+    /// The device refused a chunk with an exception other than
+    /// [`DeviceBusy`](crate::Exception::DeviceBusy).
+    Exception {
+        sequence: u32,
+        exception: crate::Exception,
+    },
+
+    /// The device acknowledged a chunk with something other than the
+    /// expected confirmation, on every attempt.
+    Verification { sequence: u32 },
+
+    /// A chunk could not be exchanged with the device, on every attempt.
+    Transfer { sequence: u32, error: super::Error },
+}
+
+impl core::fmt::Display for OtaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ImageTooLarge { len, max } => {
+                write!(
+                    f,
+                    "firmware image of {len} bytes exceeds the {max} bytes the transport can address."
+                )
+            }
+            Self::InvalidFile { file } => {
+                write!(f, "file {file} cannot be addressed by Write File Record.")
+            }
+            Self::Exception {
+                sequence,
+                exception,
+            } => {
+                write!(
+                    f,
+                    "device responded {exception} to firmware chunk {sequence}."
+                )
+            }
+            Self::Verification { sequence } => {
+                write!(f, "device did not confirm firmware chunk {sequence}.")
+            }
+            Self::Transfer { sequence, error } => {
+                write!(f, "firmware chunk {sequence} failed: {error}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for OtaError {}
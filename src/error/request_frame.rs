@@ -0,0 +1,37 @@
+/// Errors that can occur while parsing a received Modbus RTU request frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestFrameError {
+    /// The request frame is shorter than the minimum Modbus RTU length.
+    TooShort(usize),
+
+    /// Calculated CRC does not match the CRC bytes present in the frame.
+    CRCMismatch { expected: u16, received: u16 },
+
+    /// The function code is not supported by this crate.
+    UnsupportedFunction(u8),
+
+    /// The payload failed structural validation (wrong length, byte count
+    /// mismatch, invalid coil value, etc.).
+    InvalidFormat,
+}
+
+impl core::fmt::Display for RequestFrameError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooShort(len) => write!(
+                f,
+                "request frame too short; expected at least 4 bytes but received {len}."
+            ),
+            Self::CRCMismatch { expected, received } => write!(
+                f,
+                "request CRC mismatch: expected 0x{expected:04X}, received 0x{received:04X}."
+            ),
+            Self::UnsupportedFunction(code) => {
+                write!(f, "function code 0x{code:02X} is not supported.")
+            }
+            Self::InvalidFormat => f.write_str("request payload format is invalid."),
+        }
+    }
+}
+
+impl core::error::Error for RequestFrameError {}
@@ -19,7 +19,11 @@ pub enum RequestPacketError {
     /// modbus-rtu = { version = "1.0", features = ["unlimited_packet_size"] }
     /// ```
     ///
-    RequestTooBig,
+    /// `quantity` is the number of coils, registers or, for Encapsulated
+    /// Interface Transport, data bytes the request carries, and `max` the
+    /// largest number that fits.
+    ///
+    RequestTooBig { quantity: usize, max: usize },
 
     /// This error is raised when the expected response packet would exceed the
     /// Modbus RTU protocol's maximum packet length of 256 bytes.
@@ -36,7 +40,29 @@ pub enum RequestPacketError {
     /// modbus-rtu = { version = "1.0", features = ["unlimited_packet_size"] }
     /// ```
     ///
-    ResponseWillTooBig,
+    /// `quantity` is the number of coils or registers requested, and `max`
+    /// the largest number a response can hold.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::error::RequestPacketError;
+    /// use modbus_rtu::{Function, Request};
+    ///
+    /// let func = Function::ReadHoldingRegisters { starting_address: 0, quantity: 130 };
+    /// let request = Request::new(0x01, &func, std::time::Duration::from_millis(100));
+    /// # #[cfg(not(feature = "unlimited_packet_size"))]
+    /// # {
+    /// let error = request.to_bytes().unwrap_err();
+    /// assert_eq!(error, RequestPacketError::ResponseWillTooBig { quantity: 130, max: 125 });
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "request asks for 130 items but a response packet holds at most 125.",
+    /// );
+    /// # }
+    /// ```
+    ///
+    ResponseWillTooBig { quantity: u16, max: u16 },
 
     /// This error occurs when attempting to broadcast a function that does not
     /// support broadcasting (e.g., 0x01, 0x03).
@@ -54,16 +80,49 @@ pub enum RequestPacketError {
     /// ```
     ///
     CannotBroadcast,
+
+    /// This error occurs when the request timeout is shorter than the
+    /// T3.5 line turnaround plus one character at the active baud rate.
+    ///
+    /// No slave can answer within such a timeout, so the request is rejected
+    /// before transmission instead of producing a guaranteed timeout.
+    TimeoutTooShort {
+        timeout: core::time::Duration,
+        minimum: core::time::Duration,
+    },
+
+    /// This error occurs when a raw frame passed to
+    /// [`Master::send_raw`](crate::Master::send_raw) is shorter than the
+    /// smallest valid frame: slave id, function code and CRC.
+    ///
+    /// `len` is the length of the frame and `min` the smallest valid length,
+    /// [`limits::MIN_FRAME_LEN`](crate::limits::MIN_FRAME_LEN).
+    FrameTooShort { len: usize, min: usize },
 }
 
 impl core::fmt::Display for RequestPacketError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let message = match self {
-            Self::RequestTooBig => "request packet exceeds 256-byte.",
-            Self::ResponseWillTooBig => "expected response packet exceeds 256-byte.",
-            Self::CannotBroadcast => "this function does not support Modbus RTU broadcasting.",
-        };
-        f.write_str(message)
+        match self {
+            Self::RequestTooBig { quantity, max } => write!(
+                f,
+                "request carries {quantity} items but a request packet holds at most {max}."
+            ),
+            Self::ResponseWillTooBig { quantity, max } => write!(
+                f,
+                "request asks for {quantity} items but a response packet holds at most {max}."
+            ),
+            Self::CannotBroadcast => {
+                f.write_str("this function does not support Modbus RTU broadcasting.")
+            }
+            Self::TimeoutTooShort { timeout, minimum } => write!(
+                f,
+                "request timeout {timeout:?} is shorter than the minimum {minimum:?} for this baud rate."
+            ),
+            Self::FrameTooShort { len, min } => write!(
+                f,
+                "request frame is {len} bytes long but at least {min} are needed."
+            ),
+        }
     }
 }
 
@@ -1,3 +1,6 @@
+use alloc::{format, string::ToString};
+
+
 /// Errors that can occur while validating and decoding a Modbus RTU response packet.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ResponsePacketError {
@@ -10,9 +13,18 @@ pub enum ResponsePacketError {
     /// The response came from a different Modbus slave than the request targeted.
     UnexpectedResponder(u8),
 
+    /// Bytes were received before the request finished transmitting or
+    /// within the T3.5 turnaround after it, which a real slave cannot do. This usually indicates an adapter that echoes the
+    /// transmitted frame back to the receiver. Only reported in strict mode.
+    PrematureResponse(usize),
+
     /// The payload failed structural validation (unexpected function code,
     /// byte count mismatch, etc.).
     InvalidFormat,
+
+    /// The response filled the 256-byte receive buffer and `discarded`
+    /// further bytes had to be flushed before the line went idle.
+    ResponseOverflow { discarded: usize },
 }
 
 impl core::fmt::Display for ResponsePacketError {
@@ -29,7 +41,13 @@ impl core::fmt::Display for ResponsePacketError {
                 ),
                 Self::UnexpectedResponder(id) =>
                     format!("response came from unexpected Modbus slave id 0x{id:02X}."),
-                Self::InvalidFormat => format!("response payload format is invalid."),
+                Self::PrematureResponse(len) => format!(
+                    "received {len} bytes before the request finished transmitting; the adapter may be echoing."
+                ),
+                Self::InvalidFormat => "response payload format is invalid.".to_string(),
+                Self::ResponseOverflow { discarded } => format!(
+                    "response exceeded the 256-byte packet limit; discarded {discarded} extra bytes."
+                ),
             }
         )
     }
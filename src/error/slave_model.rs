@@ -0,0 +1,138 @@
+use alloc::{string::String, vec::Vec};
+
+
+/// A single inconsistency in the data model of a [`Slave`](crate::Slave).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelConflict {
+    /// Two blocks of the same table declare the same address.
+    Overlap {
+        table: crate::Table,
+        address: u16,
+        first: String,
+        second: String,
+    },
+
+    /// Several blocks of the same table share a name; `starts` holds the
+    /// first address of each, in declaration order.
+    DuplicateName {
+        table: crate::Table,
+        name: String,
+        starts: Vec<u16>,
+    },
+
+    /// A write callback is attached to an address no block defines.
+    UnbackedCallback { table: crate::Table, address: u16 },
+
+    /// A write callback is attached to a table a master cannot write to.
+    ReadOnlyCallback { table: crate::Table, address: u16 },
+
+    /// A constraint is attached to a holding register no block defines.
+    UnbackedConstraint { address: u16 },
+
+    /// [`FunctionPolicy::Acknowledge`](crate::FunctionPolicy::Acknowledge)
+    /// is set for a function code other than the four writes; reads are
+    /// quick and have no effect worth deferring.
+    AcknowledgedNonWrite { function_code: u8 },
+
+    /// The program complete register is an input register no block defines.
+    UnbackedProgramComplete { address: u16 },
+
+    /// The initial value of a holding register violates its constraint.
+    InitialValue {
+        address: u16,
+        value: u16,
+        constraint: crate::DataConstraint,
+    },
+}
+
+impl core::fmt::Display for ModelConflict {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Overlap {
+                table,
+                address,
+                first,
+                second,
+            } => write!(
+                f,
+                "{table} blocks \"{first}\" and \"{second}\" both declare address 0x{address:04X}."
+            ),
+            Self::DuplicateName {
+                table,
+                name,
+                starts,
+            } => {
+                write!(
+                    f,
+                    "{table} declare {} blocks named \"{name}\", starting at",
+                    starts.len()
+                )?;
+                for (i, start) in starts.iter().enumerate() {
+                    let separator = if i == 0 { " " } else { ", " };
+                    write!(f, "{separator}0x{start:04X}")?;
+                }
+                f.write_str(".")
+            }
+            Self::UnbackedCallback { table, address } => write!(
+                f,
+                "write callback on {table} address 0x{address:04X}, which no block defines."
+            ),
+            Self::ReadOnlyCallback { table, address } => write!(
+                f,
+                "write callback on read-only {table} address 0x{address:04X}."
+            ),
+            Self::UnbackedConstraint { address } => write!(
+                f,
+                "constraint on holding register 0x{address:04X}, which no block defines."
+            ),
+            Self::AcknowledgedNonWrite { function_code } => write!(
+                f,
+                "acknowledge policy for function 0x{function_code:02X}, which is not a write."
+            ),
+            Self::UnbackedProgramComplete { address } => write!(
+                f,
+                "program complete register at input register 0x{address:04X}, which no block defines."
+            ),
+            Self::InitialValue {
+                address,
+                value,
+                constraint,
+            } => write!(
+                f,
+                "holding register 0x{address:04X} starts at {value}, outside its constraint {constraint}."
+            ),
+        }
+    }
+}
+
+
+/// Error returned when a [`SlaveBuilder`](crate::SlaveBuilder) describes an
+/// inconsistent data model, listing every conflict found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelError {
+    pub(crate) conflicts: Vec<ModelConflict>,
+}
+
+impl ModelError {
+    /// Returns the conflicts: those between blocks, table by table, followed
+    /// by those of write callbacks and those of constraints.
+    pub fn conflicts(&self) -> &[ModelConflict] {
+        &self.conflicts
+    }
+}
+
+impl core::fmt::Display for ModelError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "slave data model has {} conflict(s):",
+            self.conflicts.len()
+        )?;
+        for conflict in &self.conflicts {
+            write!(f, "\n  - {conflict}")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for ModelError {}
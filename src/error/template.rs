@@ -0,0 +1,80 @@
+/// Errors that can occur while loading or saving a
+/// [`DeviceProfile`](crate::DeviceProfile).
+#[derive(Debug)]
+pub enum TemplateError {
+    /// The profile file could not be read.
+    IO(std::io::Error),
+
+    /// The text is not valid TOML, or does not describe a profile.
+    Syntax(toml::de::Error),
+
+    /// The profile could not be formatted as TOML.
+    Serialize(toml::ser::Error),
+
+    /// Two registers or derived values share a name or alias.
+    DuplicateRegister(String),
+
+    /// The data type of a register does not suit its table: `bool` needs a
+    /// bit table, the other types a register table.
+    TypeMismatch {
+        register: String,
+        table: crate::Table,
+    },
+
+    /// A register extends past the last address of its table.
+    AddressOverflow(String),
+
+    /// The lookup table of a register has fewer than two points, raw values
+    /// that do not ascend, or engineering values that neither ascend nor
+    /// descend.
+    InvalidLookup(String),
+
+    /// A derived value has no inputs.
+    NoInputs(String),
+
+    /// A derived value uses a register the profile does not declare, or a
+    /// value not derived before it.
+    UnknownInput { derived: String, input: String },
+
+    /// A polling group names a register the profile does not declare.
+    UnknownRegister { group: String, register: String },
+}
+
+impl core::fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::IO(error) => write!(f, "device profile I/O error: {error}"),
+            Self::Syntax(error) => write!(f, "invalid device profile: {error}"),
+            Self::Serialize(error) => write!(f, "device profile cannot be saved: {error}"),
+            Self::DuplicateRegister(name) => {
+                write!(f, "more than one register named or aliased \"{name}\".")
+            }
+            Self::TypeMismatch { register, table } => {
+                write!(
+                    f,
+                    "the type of register \"{register}\" does not suit {table}."
+                )
+            }
+            Self::AddressOverflow(name) => {
+                write!(f, "register \"{name}\" extends past address 0xFFFF.")
+            }
+            Self::InvalidLookup(name) => {
+                write!(
+                    f,
+                    "the lookup table of register \"{name}\" is not monotonic."
+                )
+            }
+            Self::NoInputs(name) => write!(f, "derived value \"{name}\" has no inputs."),
+            Self::UnknownInput { derived, input } => write!(
+                f,
+                "derived value \"{derived}\" uses \"{input}\", which is not declared before it."
+            ),
+            Self::UnknownRegister { group, register } => write!(
+                f,
+                "group \"{group}\" names register \"{register}\", which is not declared."
+            ),
+        }
+    }
+}
+
+impl core::error::Error for TemplateError {}
@@ -0,0 +1,50 @@
+use alloc::format;
+
+use super::ResponsePacketError;
+use crate::Exception;
+
+
+/// Errors that can occur while unwrapping a frame tunnelled through a
+/// gateway with [`raw::untunnel`](crate::raw::untunnel).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TunnelError {
+    /// The bypass frame of the gateway failed the framing checks.
+    Gateway(ResponsePacketError),
+
+    /// The gateway answered with a function code other than
+    /// [`raw::BYPASS`](crate::raw::BYPASS) or its exception form.
+    UnexpectedFunction(u8),
+
+    /// The gateway refused to forward the frame, e.g. with
+    /// [`Exception::GatewayPathUnavailable`] when the downstream bus is down.
+    GatewayException(Exception),
+
+    /// The tunnelled frame failed the framing checks.
+    Tunnelled(ResponsePacketError),
+
+    /// The slave on the downstream bus answered with an exception.
+    SlaveException { modbus_id: u8, exception: Exception },
+}
+
+impl core::fmt::Display for TunnelError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Gateway(error) => format!("gateway frame is invalid: {error}"),
+                Self::UnexpectedFunction(code) =>
+                    format!("gateway answered with unexpected function code 0x{code:02X}."),
+                Self::GatewayException(exception) =>
+                    format!("gateway refused to forward the frame: {exception}."),
+                Self::Tunnelled(error) => format!("tunnelled frame is invalid: {error}"),
+                Self::SlaveException {
+                    modbus_id,
+                    exception,
+                } => format!("downstream slave 0x{modbus_id:02X} answered with {exception}."),
+            }
+        )
+    }
+}
+
+impl core::error::Error for TunnelError {}
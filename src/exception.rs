@@ -1,3 +1,6 @@
+use alloc::{format, string::ToString};
+
+
 /// Enumerates the Modbus application exceptions returned by a slave device,
 /// including a catch-all for codes not defined by the specification.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
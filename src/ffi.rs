@@ -0,0 +1,168 @@
+//! C ABI for the Modbus RTU master.
+//!
+//! Enabled with the `capi` feature. Build a C library with
+//! `cargo rustc --release --features capi --crate-type cdylib` (or
+//! `staticlib`) and generate the header with
+//! `cbindgen --config cbindgen.toml --output modbus_rtu.h`.
+//!
+//! All functions return [`MODBUS_RTU_OK`] on success, a positive Modbus
+//! exception code when the device answered with an exception, or one of the
+//! negative `MODBUS_RTU_ERR_*` codes.
+
+use core::ffi::{c_char, c_int};
+
+use crate::error::{Error, ResponsePacketError};
+use crate::{BitOrder, Function, Master, Request, Response};
+
+
+/// The call succeeded.
+pub const MODBUS_RTU_OK: c_int = 0;
+
+/// A pointer argument was null, or the path was not valid UTF-8.
+pub const MODBUS_RTU_ERR_INVALID_ARGUMENT: c_int = -1;
+
+/// The serial port could not be opened or configured.
+pub const MODBUS_RTU_ERR_OPEN: c_int = -2;
+
+/// The request PDU could not be parsed or encoded.
+pub const MODBUS_RTU_ERR_REQUEST: c_int = -3;
+
+/// The response failed CRC validation.
+pub const MODBUS_RTU_ERR_CRC: c_int = -4;
+
+/// The response was truncated, malformed, or came from the wrong device.
+pub const MODBUS_RTU_ERR_RESPONSE: c_int = -5;
+
+/// No complete response arrived before the timeout.
+pub const MODBUS_RTU_ERR_TIMEOUT: c_int = -6;
+
+/// The serial transport reported an I/O error.
+pub const MODBUS_RTU_ERR_IO: c_int = -7;
+
+/// The output buffer is too small for the response values.
+pub const MODBUS_RTU_ERR_BUFFER_TOO_SMALL: c_int = -8;
+
+
+/// Opaque handle to a [`Master`], created by [`modbus_rtu_master_new`] and
+/// released by [`modbus_rtu_master_free`].
+pub struct ModbusRtuMaster {
+    inner: Master,
+}
+
+
+/// Opens `path` as an RS-485 master at `baud_rate` and stores the new handle
+/// in `*master`.
+///
+/// # Safety
+/// `path` must point to a NUL-terminated string and `master` must be valid
+/// for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn modbus_rtu_master_new(
+    path: *const c_char,
+    baud_rate: u32,
+    master: *mut *mut ModbusRtuMaster,
+) -> c_int {
+    if path.is_null() || master.is_null() {
+        return MODBUS_RTU_ERR_INVALID_ARGUMENT;
+    }
+    let Ok(path) = unsafe { core::ffi::CStr::from_ptr(path) }.to_str() else {
+        return MODBUS_RTU_ERR_INVALID_ARGUMENT;
+    };
+    match Master::new_rs485(path, baud_rate) {
+        Ok(inner) => {
+            unsafe { *master = Box::into_raw(Box::new(ModbusRtuMaster { inner })) };
+            MODBUS_RTU_OK
+        }
+        Err(_) => MODBUS_RTU_ERR_OPEN,
+    }
+}
+
+
+/// Sends the request PDU (function code followed by its data, without slave
+/// id or CRC) to `modbus_id` and waits up to `timeout_ms` for the response.
+///
+/// Response values are written to `out`, one `uint16_t` per item: coil and
+/// input states as `0`/`1`, registers as-is, the address and value (or
+/// quantity) echoed by write functions, or the data bytes of an encapsulated
+/// interface response. The number of items is stored in `*out_len`.
+///
+/// # Safety
+/// `master` must be a handle returned by [`modbus_rtu_master_new`], `pdu` must
+/// be valid for `pdu_len` bytes reads, `out` must be valid for
+/// `out_capacity` writes (or null when `out_capacity` is zero), and `out_len`
+/// must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn modbus_rtu_master_send(
+    master: *mut ModbusRtuMaster,
+    modbus_id: u8,
+    pdu: *const u8,
+    pdu_len: usize,
+    timeout_ms: u32,
+    out: *mut u16,
+    out_capacity: usize,
+    out_len: *mut usize,
+) -> c_int {
+    if master.is_null() || pdu.is_null() || out_len.is_null() || (out.is_null() && out_capacity > 0)
+    {
+        return MODBUS_RTU_ERR_INVALID_ARGUMENT;
+    }
+    let master = unsafe { &mut (*master).inner };
+    let pdu = unsafe { core::slice::from_raw_parts(pdu, pdu_len) };
+    unsafe { *out_len = 0 };
+
+    let Ok(function) = Function::from_bytes(pdu, BitOrder::LsbFirst) else {
+        return MODBUS_RTU_ERR_REQUEST;
+    };
+    let timeout = core::time::Duration::from_millis(timeout_ms as u64);
+    let request = Request::new(modbus_id, &function, timeout);
+    let values: Vec<u16> = match master.send(&request) {
+        Ok(Response::Status(items)) => items.iter().map(|&state| state as u16).collect(),
+        Ok(Response::Value(items)) => items.into_vec(),
+        Ok(Response::Success) => Vec::new(),
+        Ok(Response::WriteEcho {
+            address,
+            value_or_quantity,
+        }) => vec![address, value_or_quantity],
+        Ok(Response::Encapsulated { data, .. }) => data.iter().map(|&byte| byte as u16).collect(),
+        Ok(Response::Exception(exception)) => return exception.as_code() as c_int,
+        Err(error) => return error_code(&error),
+    };
+    if values.len() > out_capacity {
+        return MODBUS_RTU_ERR_BUFFER_TOO_SMALL;
+    }
+    if !values.is_empty() {
+        unsafe { core::ptr::copy_nonoverlapping(values.as_ptr(), out, values.len()) };
+    }
+    unsafe { *out_len = values.len() };
+    MODBUS_RTU_OK
+}
+
+
+/// Closes the port and releases a handle created by
+/// [`modbus_rtu_master_new`]. Passing null is a no-op.
+///
+/// # Safety
+/// `master` must be null or a handle returned by [`modbus_rtu_master_new`]
+/// that has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn modbus_rtu_master_free(master: *mut ModbusRtuMaster) {
+    if !master.is_null() {
+        drop(unsafe { Box::from_raw(master) });
+    }
+}
+
+
+/// Maps an [`Error`] to its C error code.
+fn error_code(error: &Error) -> c_int {
+    match error {
+        Error::Exception(exception, _) => exception.as_code() as c_int,
+        Error::Request(_) => MODBUS_RTU_ERR_REQUEST,
+        Error::Response(ResponsePacketError::CRCMismatch { .. }) => MODBUS_RTU_ERR_CRC,
+        Error::Response(_) => MODBUS_RTU_ERR_RESPONSE,
+        Error::IO(error) if error.kind() == std::io::ErrorKind::TimedOut => MODBUS_RTU_ERR_TIMEOUT,
+        Error::IO(_) | Error::BusCollision { .. } => MODBUS_RTU_ERR_IO,
+        Error::InconsistentRead { .. } | Error::VerificationFailed { .. } => {
+            MODBUS_RTU_ERR_RESPONSE
+        }
+    }
+}
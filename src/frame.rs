@@ -0,0 +1,496 @@
+//! Conversion between [`Request`](crate::Request)s and raw Modbus RTU request frames.
+
+use alloc::{boxed::Box, vec};
+
+
+/// Encodes `request` into a complete Modbus RTU frame (slave id, function
+/// payload, and CRC).
+///
+/// This is equivalent to [`Request::to_bytes`](crate::Request::to_bytes) and
+/// is the inverse of [`parse_request`].
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{frame, Function, Request};
+///
+/// let func = Function::ReadHoldingRegisters { starting_address: 0x006B, quantity: 3 };
+/// let request = Request::new(0x11, &func, std::time::Duration::from_millis(100));
+///
+/// let bytes = frame::encode(&request).unwrap();
+/// assert_eq!(&bytes[..], &[0x11, 0x03, 0x00, 0x6B, 0x00, 0x03, 0x76, 0x87]);
+/// ```
+///
+pub fn encode(request: &crate::Request) -> Result<Box<[u8]>, crate::error::RequestPacketError> {
+    request.to_bytes()
+}
+
+/// Parses a complete Modbus RTU request frame into the addressed slave id and
+/// its [`Function`](crate::Function).
+///
+/// The CRC and the payload structure are validated; coil values are unpacked
+/// least significant bit first, as mandated by the specification.
+///
+/// ---
+/// # Errors
+/// Returns [`RequestFrameError`](crate::error::RequestFrameError) when the
+/// frame is too short, fails the CRC check, uses an unsupported function
+/// code, or has a malformed payload.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{frame, Function, Request};
+///
+/// // Write Multiple Coils example from the Modbus application protocol specification.
+/// let bytes = [0x11, 0x0F, 0x00, 0x13, 0x00, 0x0A, 0x02, 0xCD, 0x01, 0xBF, 0x0B];
+/// let (modbus_id, function) = frame::parse_request(&bytes).unwrap();
+///
+/// let coils = [true, false, true, true, false, false, true, true, true, false];
+/// assert_eq!(modbus_id, 0x11);
+/// assert_eq!(function, Function::WriteMultipleCoils { starting_address: 0x0013, value: Box::new(coils) });
+///
+/// // Encoding the parsed request reproduces the original frame.
+/// let request = Request::new(modbus_id, &function, std::time::Duration::from_millis(100));
+/// assert_eq!(&frame::encode(&request).unwrap()[..], &bytes);
+/// ```
+///
+/// Every supported function survives a round trip:
+/// ```rust
+/// use modbus_rtu::{frame, Function, Request};
+///
+/// let functions = [
+///     Function::ReadCoils { starting_address: 0x0013, quantity: 19 },
+///     Function::ReadDiscreteInputs { starting_address: 0x00C4, quantity: 22 },
+///     Function::ReadHoldingRegisters { starting_address: 0x006B, quantity: 3 },
+///     Function::ReadInputRegisters { starting_address: 0x0008, quantity: 1 },
+///     Function::WriteSingleCoil { address: 0x00AC, value: true },
+///     Function::WriteSingleRegister { address: 0x0001, value: 0x0003 },
+///     Function::WriteMultipleCoils { starting_address: 0x0013, value: Box::new([true; 17]) },
+///     Function::WriteMultipleRegisters { starting_address: 0x0001, value: Box::new([0x000A, 0x0102]) },
+///     Function::EncapsulatedInterface { mei_type: 0x0E, data: Box::new([0x01, 0x00]) },
+/// ];
+/// for function in functions {
+///     let request = Request::new(0x11, &function, std::time::Duration::from_millis(100));
+///     let bytes = frame::encode(&request).unwrap();
+///     assert_eq!(frame::parse_request(&bytes).unwrap(), (0x11, function));
+/// }
+/// ```
+///
+pub fn parse_request(
+    bytes: &[u8],
+) -> Result<(u8, crate::Function), crate::error::RequestFrameError> {
+    let len = bytes.len();
+    if len < 4 {
+        return Err(crate::error::RequestFrameError::TooShort(len));
+    }
+    let expected = crate::crc::generate(&bytes[0..(len - 2)]);
+    let received = u16::from_le_bytes([bytes[len - 2], bytes[len - 1]]);
+    if received != expected {
+        return Err(crate::error::RequestFrameError::CRCMismatch { expected, received });
+    }
+    let function = crate::Function::from_bytes(&bytes[1..(len - 2)], crate::BitOrder::LsbFirst)?;
+    Ok((bytes[0], function))
+}
+
+
+
+/// A frame decoded without knowing which direction it travelled in.
+///
+/// Returned by [`decode_any`] and [`decode_after`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodedFrame {
+    /// A request sent by a master to slave `modbus_id`.
+    Request {
+        modbus_id: u8,
+        function: crate::Function,
+    },
+
+    /// A normal response sent by slave `modbus_id`.
+    Response {
+        modbus_id: u8,
+        kind: crate::FunctionKind,
+        response: crate::Response,
+    },
+
+    /// An exception response sent by slave `modbus_id` to a request with
+    /// function code `function_code`.
+    Exception {
+        modbus_id: u8,
+        function_code: u8,
+        exception: crate::Exception,
+    },
+}
+
+
+/// Decodes a complete Modbus RTU frame that may be either a request or a
+/// response, e.g. a line of a bus capture.
+///
+/// The direction is inferred from the function code and the length of the
+/// frame: an exception code or a byte count matching the length makes a
+/// response, a layout valid for the function makes a request. Some frames
+/// fit both; they are decoded as requests:
+///
+/// - write single coil and write single register responses echo their
+///   request byte for byte,
+/// - a coil or discrete input read response carrying 3 bytes of states has
+///   the length of a read request,
+/// - encapsulated interface transport frames have the same layout both ways.
+///
+/// Without the request, coil and discrete input states are decoded for
+/// every bit of the data bytes, padding included. [`decode_after`] resolves
+/// both limitations when the preceding request is known.
+///
+/// ---
+/// # Errors
+/// Returns [`RequestFrameError`](crate::error::RequestFrameError) when the
+/// frame is too short, fails the CRC check, uses an unsupported function
+/// code, or fits neither direction.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::frame::{self, DecodedFrame};
+/// use modbus_rtu::{test_vectors, Exception, Function, FunctionKind, Response};
+///
+/// let request = frame::decode_any(test_vectors::READ_HOLDING_REGISTERS.request).unwrap();
+/// assert_eq!(
+///     request,
+///     DecodedFrame::Request {
+///         modbus_id: 0x11,
+///         function: Function::ReadHoldingRegisters { starting_address: 0x006B, quantity: 3 },
+///     }
+/// );
+///
+/// let response = frame::decode_any(test_vectors::READ_HOLDING_REGISTERS.response).unwrap();
+/// assert_eq!(
+///     response,
+///     DecodedFrame::Response {
+///         modbus_id: 0x11,
+///         kind: FunctionKind::ReadHoldingRegisters,
+///         response: Response::Value(Box::new([0x022B, 0x0000, 0x0064])),
+///     }
+/// );
+///
+/// let exception = frame::decode_any(test_vectors::EXCEPTION_ILLEGAL_DATA_ADDRESS.response).unwrap();
+/// assert_eq!(
+///     exception,
+///     DecodedFrame::Exception {
+///         modbus_id: 0x11,
+///         function_code: 0x01,
+///         exception: Exception::IllegalDataAddress,
+///     }
+/// );
+/// ```
+///
+pub fn decode_any(bytes: &[u8]) -> Result<DecodedFrame, crate::error::RequestFrameError> {
+    decode(bytes, None)
+}
+
+
+/// Decodes a complete Modbus RTU frame seen right after `request`, taking it
+/// as the response whenever it is one.
+///
+/// Frames that do not answer `request` are decoded as by [`decode_any`].
+///
+/// ---
+/// # Errors
+/// Returns [`RequestFrameError`](crate::error::RequestFrameError) under the
+/// same conditions as [`decode_any`].
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::frame::{self, DecodedFrame};
+/// use modbus_rtu::{test_vectors, Function, FunctionKind, Request, Response};
+///
+/// // On its own, a write echo cannot be told apart from the request.
+/// let echo = test_vectors::WRITE_SINGLE_REGISTER.response;
+/// assert!(matches!(frame::decode_any(echo), Ok(DecodedFrame::Request { .. })));
+///
+/// let function = Function::WriteSingleRegister { address: 0x0001, value: 0x0003 };
+/// let request = Request::new(0x11, &function, std::time::Duration::from_millis(100));
+/// assert_eq!(
+///     frame::decode_after(echo, &request).unwrap(),
+///     DecodedFrame::Response {
+///         modbus_id: 0x11,
+///         kind: FunctionKind::WriteSingleRegister,
+///         response: Response::WriteEcho { address: 0x0001, value_or_quantity: 0x0003 },
+///     }
+/// );
+/// ```
+///
+pub fn decode_after(
+    bytes: &[u8],
+    request: &crate::Request,
+) -> Result<DecodedFrame, crate::error::RequestFrameError> {
+    decode(bytes, Some(request))
+}
+
+
+/// Decodes `bytes` in whichever direction fits, preferring the response to
+/// `context` when given.
+fn decode(
+    bytes: &[u8],
+    context: Option<&crate::Request>,
+) -> Result<DecodedFrame, crate::error::RequestFrameError> {
+    use crate::error::RequestFrameError;
+    use crate::{FunctionKind, Response};
+
+    let len = bytes.len();
+    if len < 4 {
+        return Err(RequestFrameError::TooShort(len));
+    }
+    let expected = crate::crc::generate(&bytes[0..(len - 2)]);
+    let received = u16::from_le_bytes([bytes[len - 2], bytes[len - 1]]);
+    if received != expected {
+        return Err(RequestFrameError::CRCMismatch { expected, received });
+    }
+    let (modbus_id, function_code) = (bytes[0], bytes[1]);
+    if function_code & 0x80 != 0 {
+        if len != 5 {
+            return Err(RequestFrameError::InvalidFormat);
+        }
+        return Ok(DecodedFrame::Exception {
+            modbus_id,
+            function_code: function_code & 0x7F,
+            exception: crate::Exception::from_code(bytes[2]),
+        });
+    }
+    if let Some(request) = context
+        && let Ok(response) = Response::from_bytes(request, bytes)
+    {
+        return Ok(DecodedFrame::Response {
+            modbus_id,
+            kind: request.function().kind(),
+            response,
+        });
+    }
+    let error = match parse_request(bytes) {
+        Ok((modbus_id, function)) => {
+            return Ok(DecodedFrame::Request {
+                modbus_id,
+                function,
+            });
+        }
+        Err(error) => error,
+    };
+    let kind = FunctionKind::from_code(function_code)
+        .ok_or(RequestFrameError::UnsupportedFunction(function_code))?;
+    let payload = &bytes[2..(len - 2)];
+    let counted = payload
+        .first()
+        .is_some_and(|&byte_count| payload.len() == byte_count as usize + 1);
+    let response = match kind {
+        FunctionKind::ReadCoils | FunctionKind::ReadDiscreteInputs if counted => Response::Status(
+            payload[1..]
+                .iter()
+                .flat_map(|byte| (0..8).map(move |i| byte & crate::BitOrder::LsbFirst.mask(i) != 0))
+                .collect(),
+        ),
+        FunctionKind::ReadHoldingRegisters | FunctionKind::ReadInputRegisters
+            if counted && payload[0].is_multiple_of(2) =>
+        {
+            Response::Value(
+                payload[1..]
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect(),
+            )
+        }
+        FunctionKind::WriteSingleCoil
+        | FunctionKind::WriteSingleRegister
+        | FunctionKind::WriteMultipleCoils
+        | FunctionKind::WriteMultipleRegisters
+            if payload.len() == 4 =>
+        {
+            Response::WriteEcho {
+                address: u16::from_be_bytes([payload[0], payload[1]]),
+                value_or_quantity: u16::from_be_bytes([payload[2], payload[3]]),
+            }
+        }
+        _ => return Err(error),
+    };
+    Ok(DecodedFrame::Response {
+        modbus_id,
+        kind,
+        response,
+    })
+}
+
+/// Encodes `response` to a request with function code `function_code` into a
+/// complete Modbus RTU response frame from slave `modbus_id`, as a slave would
+/// send it.
+///
+/// Coil and discrete input states are packed least significant bit first.
+/// Returns [`None`] for [`Response::Success`](crate::Response::Success),
+/// which has no frame of its own.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{frame, test_vectors, Exception, Response};
+///
+/// let response = Response::Value(Box::new([0x022B, 0x0000, 0x0064]));
+/// let bytes = frame::encode_response(0x11, 0x03, &response).unwrap();
+/// assert_eq!(&bytes[..], test_vectors::READ_HOLDING_REGISTERS.response);
+///
+/// let exception = Response::Exception(Exception::IllegalDataAddress);
+/// let bytes = frame::encode_response(0x11, 0x01, &exception).unwrap();
+/// assert_eq!(&bytes[..], test_vectors::EXCEPTION_ILLEGAL_DATA_ADDRESS.response);
+/// ```
+///
+pub fn encode_response(
+    modbus_id: u8,
+    function_code: u8,
+    response: &crate::Response,
+) -> Option<Box<[u8]>> {
+    let mut buf = vec![modbus_id, function_code];
+    match response {
+        crate::Response::Status(states) => {
+            buf.push(states.len().div_ceil(8) as u8);
+            for chunk in states.chunks(8) {
+                let mut byte: u8 = 0x00;
+                for (i, state) in chunk.iter().enumerate() {
+                    if *state {
+                        byte |= crate::BitOrder::LsbFirst.mask(i);
+                    }
+                }
+                buf.push(byte);
+            }
+        }
+        crate::Response::Value(values) => {
+            buf.push((values.len() * 2) as u8);
+            for value in values {
+                buf.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+        crate::Response::Success => return None,
+        crate::Response::WriteEcho {
+            address,
+            value_or_quantity,
+        } => {
+            buf.extend_from_slice(&address.to_be_bytes());
+            buf.extend_from_slice(&value_or_quantity.to_be_bytes());
+        }
+        crate::Response::Encapsulated { mei_type, data } => {
+            buf.push(*mei_type);
+            buf.extend_from_slice(data);
+        }
+        crate::Response::Exception(exception) => {
+            buf[1] |= 0x80;
+            buf.push(exception.as_code());
+        }
+    }
+    let crc = crate::crc::generate(&buf);
+    buf.extend_from_slice(&crc.to_le_bytes());
+    Some(buf.into_boxed_slice())
+}
+
+
+/// Builds the 8-byte frame of a request carrying two 16-bit fields, the
+/// layout shared by the read and single-write functions.
+const fn fixed(modbus_id: u8, function_code: u8, first: u16, second: u16) -> [u8; 8] {
+    let [a_hi, a_lo] = first.to_be_bytes();
+    let [b_hi, b_lo] = second.to_be_bytes();
+    let body = [modbus_id, function_code, a_hi, a_lo, b_hi, b_lo];
+    let [crc_lo, crc_hi] = crate::crc::generate(&body).to_le_bytes();
+    [
+        modbus_id,
+        function_code,
+        a_hi,
+        a_lo,
+        b_hi,
+        b_lo,
+        crc_lo,
+        crc_hi,
+    ]
+}
+
+
+/// Builds a Read Coils `(0x01)` frame at compile time.
+///
+/// Like the other `encode_*` functions, this is a `const fn` returning a fixed
+/// array, so firmware can keep frequently used frames in flash. The quantity
+/// is not checked against the protocol limits.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{frame, test_vectors};
+///
+/// const READ_COILS: [u8; 8] = frame::encode_read_coils(0x11, 0x0013, 19);
+/// assert_eq!(&READ_COILS[..], test_vectors::READ_COILS.request);
+/// ```
+///
+pub const fn encode_read_coils(modbus_id: u8, starting_address: u16, quantity: u16) -> [u8; 8] {
+    fixed(modbus_id, 0x01, starting_address, quantity)
+}
+
+
+/// Builds a Read Discrete Inputs `(0x02)` frame at compile time.
+pub const fn encode_read_discrete(modbus_id: u8, starting_address: u16, quantity: u16) -> [u8; 8] {
+    fixed(modbus_id, 0x02, starting_address, quantity)
+}
+
+
+/// Builds a Read Holding Registers `(0x03)` frame at compile time.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{frame, Function, Request};
+///
+/// static POLL: [u8; 8] = frame::encode_read_holding(0x01, 0x0000, 2);
+///
+/// let func = Function::ReadHoldingRegisters { starting_address: 0x0000, quantity: 2 };
+/// let request = Request::new(0x01, &func, std::time::Duration::from_millis(100));
+/// assert_eq!(&POLL[..], &request.to_bytes().unwrap()[..]);
+/// ```
+///
+pub const fn encode_read_holding(modbus_id: u8, starting_address: u16, quantity: u16) -> [u8; 8] {
+    fixed(modbus_id, 0x03, starting_address, quantity)
+}
+
+
+/// Builds a Read Input Registers `(0x04)` frame at compile time.
+pub const fn encode_read_input(modbus_id: u8, starting_address: u16, quantity: u16) -> [u8; 8] {
+    fixed(modbus_id, 0x04, starting_address, quantity)
+}
+
+
+/// Builds a Write Single Coil `(0x05)` frame at compile time.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{frame, test_vectors};
+///
+/// const COIL_ON: [u8; 8] = frame::encode_write_coil(0x11, 0x00AC, true);
+/// assert_eq!(&COIL_ON[..], test_vectors::WRITE_SINGLE_COIL.request);
+/// ```
+///
+pub const fn encode_write_coil(modbus_id: u8, address: u16, value: bool) -> [u8; 8] {
+    fixed(
+        modbus_id,
+        0x05,
+        address,
+        if value { 0xFF00 } else { 0x0000 },
+    )
+}
+
+
+/// Builds a Write Single Register `(0x06)` frame at compile time.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{frame, test_vectors};
+///
+/// const WRITE: [u8; 8] = frame::encode_write_register(0x11, 0x0001, 0x0003);
+/// assert_eq!(&WRITE[..], test_vectors::WRITE_SINGLE_REGISTER.request);
+/// ```
+///
+pub const fn encode_write_register(modbus_id: u8, address: u16, value: u16) -> [u8; 8] {
+    fixed(modbus_id, 0x06, address, value)
+}
@@ -1,3 +1,6 @@
+use alloc::{boxed::Box, vec::Vec};
+
+
 /// ## Function
 ///
 /// Represents a Modbus RTU function request along with the data required to
@@ -46,6 +49,27 @@ pub enum Function {
         starting_address: u16,
         value: Box<[u16]>,
     },
+
+    /// Encapsulated Interface Transport `(0x2B)`
+    ///
+    /// Carries `data` for the MEI type `mei_type` unchanged, e.g. `0x0D` for
+    /// CANopen General Reference or `0x0E` for Read Device Identification.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{Function, Request, Response};
+    ///
+    /// let func = Function::EncapsulatedInterface { mei_type: 0x0D, data: Box::new([0x01, 0x02]) };
+    /// let request = Request::new(0x01, &func, std::time::Duration::from_millis(100));
+    /// assert_eq!(&request.to_bytes().unwrap()[..], &[0x01, 0x2B, 0x0D, 0x01, 0x02, 0x01, 0xB6]);
+    ///
+    /// let frame = [0x01, 0x2B, 0x0D, 0xAA, 0xBB, 0xBF, 0x34];
+    /// let response = Response::from_bytes(&request, &frame).unwrap();
+    /// assert_eq!(response, Response::Encapsulated { mei_type: 0x0D, data: Box::new([0xAA, 0xBB]) });
+    /// ```
+    ///
+    EncapsulatedInterface { mei_type: u8, data: Box<[u8]> },
 }
 
 impl Function {
@@ -71,6 +95,7 @@ impl Function {
             Function::WriteSingleRegister { .. } => FunctionKind::WriteSingleRegister,
             Function::WriteMultipleCoils { .. } => FunctionKind::WriteMultipleCoils,
             Function::WriteMultipleRegisters { .. } => FunctionKind::WriteMultipleRegisters,
+            Function::EncapsulatedInterface { .. } => FunctionKind::EncapsulatedInterfaceTransport,
         }
     }
 
@@ -91,20 +116,25 @@ impl Function {
 
     /// Serializes this function into a Modbus RTU payload (function code + data).
     ///
-    /// Returns [`FunctionError`](crate::error::FunctionError) when the generated
+    /// Coil values are packed according to `bit_order`.
+    ///
+    /// Returns [`RequestPacketError`](crate::error::RequestPacketError) when the generated
     /// payload would exceed the 256-byte packet limit imposed by the Modbus RTU
     /// specification.
     ///
     /// # Examples
     /// ```ignore
-    /// use modbus_rtu::Function;
+    /// use modbus_rtu::{BitOrder, Function};
     ///
     /// let function = Function::WriteSingleCoil { address: 0x0025, value: true };
-    /// let bytes = function.to_bytes().unwrap();
+    /// let bytes = function.to_bytes(BitOrder::LsbFirst).unwrap();
     /// assert_eq!(&bytes[..], &[0x05, 0x00, 0x25, 0xFF, 0x00]);
     /// ```
     ///
-    pub(crate) fn to_bytes(&self) -> Result<Box<[u8]>, crate::error::RequestPacketError> {
+    pub(crate) fn to_bytes(
+        &self,
+        bit_order: crate::BitOrder,
+    ) -> Result<Box<[u8]>, crate::error::RequestPacketError> {
         let mut buf: Vec<u8> = Vec::with_capacity(5);
         buf.push(self.kind().as_code());
         match self {
@@ -118,8 +148,12 @@ impl Function {
             } => {
                 #[cfg(not(feature = "unlimited_packet_size"))]
                 {
-                    if *quantity > 2008 {
-                        return Err(crate::error::RequestPacketError::ResponseWillTooBig);
+                    let max = crate::limits::MAX_READ_COILS;
+                    if *quantity > max {
+                        return Err(crate::error::RequestPacketError::ResponseWillTooBig {
+                            quantity: *quantity,
+                            max,
+                        });
                     }
                 }
                 buf.extend_from_slice(&starting_address.to_be_bytes());
@@ -135,8 +169,12 @@ impl Function {
             } => {
                 #[cfg(not(feature = "unlimited_packet_size"))]
                 {
-                    if *quantity > 125 {
-                        return Err(crate::error::RequestPacketError::ResponseWillTooBig);
+                    let max = crate::limits::MAX_READ_REGISTERS;
+                    if *quantity > max {
+                        return Err(crate::error::RequestPacketError::ResponseWillTooBig {
+                            quantity: *quantity,
+                            max,
+                        });
                     }
                 }
                 buf.extend_from_slice(&starting_address.to_be_bytes());
@@ -144,7 +182,7 @@ impl Function {
             }
             Function::WriteSingleCoil { address, value } => {
                 buf.extend_from_slice(&address.to_be_bytes());
-                buf.push(if *value == true { 0xFF } else { 0x00 });
+                buf.push(if *value { 0xFF } else { 0x00 });
                 buf.push(0x00);
             }
             Function::WriteSingleRegister { address, value } => {
@@ -158,22 +196,20 @@ impl Function {
                 let quantity = value.len() as u16;
                 #[cfg(not(feature = "unlimited_packet_size"))]
                 {
-                    if quantity > 1976 {
-                        return Err(crate::error::RequestPacketError::RequestTooBig);
-                    }
+                    check_size(value.len(), crate::limits::MAX_WRITE_COILS as usize)?;
                 }
-                let byte_count = ((quantity + 7) / 8) as u8;
+                // The byte count must describe the packed values even when
+                // the size limit is lifted.
+                check_size(value.len(), u8::MAX as usize * 8)?;
+                let byte_count = value.len().div_ceil(8) as u8;
                 buf.extend_from_slice(&starting_address.to_be_bytes());
                 buf.extend_from_slice(&quantity.to_be_bytes());
                 buf.push(byte_count);
-                let mut chunks = value.chunks(8);
-                while let Some(chunk) = chunks.next() {
+                for chunk in value.chunks(8) {
                     let mut byte: u8 = 0x00;
                     for (i, value) in chunk.iter().enumerate() {
-                        if *value == true {
-                            byte |= 0b1 << i;
-                        } else {
-                            byte &= !(0b1 << i);
+                        if *value {
+                            byte |= bit_order.mask(i);
                         }
                     }
                     buf.push(byte);
@@ -186,11 +222,10 @@ impl Function {
                 let quantity = value.len() as u16;
                 #[cfg(not(feature = "unlimited_packet_size"))]
                 {
-                    if quantity > 123 {
-                        return Err(crate::error::RequestPacketError::RequestTooBig);
-                    }
+                    check_size(value.len(), crate::limits::MAX_WRITE_REGISTERS as usize)?;
                 }
-                let byte_count = (quantity * 2) as u8;
+                check_size(value.len(), u8::MAX as usize / 2)?;
+                let byte_count = (value.len() * 2) as u8;
                 buf.extend_from_slice(&starting_address.to_be_bytes());
                 buf.extend_from_slice(&quantity.to_be_bytes());
                 buf.push(byte_count);
@@ -198,10 +233,126 @@ impl Function {
                     buf.extend_from_slice(&each.to_be_bytes());
                 }
             }
+            Function::EncapsulatedInterface { mei_type, data } => {
+                #[cfg(not(feature = "unlimited_packet_size"))]
+                {
+                    check_size(data.len(), crate::limits::MAX_PDU - 2)?;
+                }
+                buf.push(*mei_type);
+                buf.extend_from_slice(data);
+            }
         }
         Ok(buf.into_boxed_slice())
     }
 
+    /// Parses a Modbus RTU request payload (function code + data) into a
+    /// [`Function`], unpacking coil values according to `bit_order`. This is
+    /// the inverse of [`to_bytes`](Self::to_bytes).
+    ///
+    /// Only the structure of the payload is validated; quantities are not
+    /// checked against protocol limits.
+    pub(crate) fn from_bytes(
+        bytes: &[u8],
+        bit_order: crate::BitOrder,
+    ) -> Result<Self, crate::error::RequestFrameError> {
+        use crate::error::RequestFrameError;
+        let (&code, data) = bytes.split_first().ok_or(RequestFrameError::TooShort(0))?;
+        let kind = crate::FunctionKind::from_code(code)
+            .ok_or(RequestFrameError::UnsupportedFunction(code))?;
+        let word = |i: usize| u16::from_be_bytes([data[i], data[i + 1]]);
+        match kind {
+            crate::FunctionKind::EncapsulatedInterfaceTransport => {
+                let (&mei_type, data) =
+                    data.split_first().ok_or(RequestFrameError::InvalidFormat)?;
+                return Ok(Function::EncapsulatedInterface {
+                    mei_type,
+                    data: data.into(),
+                });
+            }
+            crate::FunctionKind::WriteMultipleCoils
+            | crate::FunctionKind::WriteMultipleRegisters => {
+                if data.len() < 5 || data.len() != 5 + data[4] as usize {
+                    return Err(RequestFrameError::InvalidFormat);
+                }
+            }
+            _ => {
+                if data.len() != 4 {
+                    return Err(RequestFrameError::InvalidFormat);
+                }
+            }
+        }
+        let (address, quantity) = (word(0), word(2));
+        Ok(match kind {
+            crate::FunctionKind::ReadCoils => Function::ReadCoils {
+                starting_address: address,
+                quantity,
+            },
+            crate::FunctionKind::ReadDiscreteInputs => Function::ReadDiscreteInputs {
+                starting_address: address,
+                quantity,
+            },
+            crate::FunctionKind::ReadHoldingRegisters => Function::ReadHoldingRegisters {
+                starting_address: address,
+                quantity,
+            },
+            crate::FunctionKind::ReadInputRegisters => Function::ReadInputRegisters {
+                starting_address: address,
+                quantity,
+            },
+            crate::FunctionKind::WriteSingleCoil => Function::WriteSingleCoil {
+                address,
+                value: match quantity {
+                    0xFF00 => true,
+                    0x0000 => false,
+                    _ => return Err(RequestFrameError::InvalidFormat),
+                },
+            },
+            crate::FunctionKind::WriteSingleRegister => Function::WriteSingleRegister {
+                address,
+                value: quantity,
+            },
+            crate::FunctionKind::WriteMultipleCoils => {
+                if data[4] as usize != (quantity as usize).div_ceil(8) {
+                    return Err(RequestFrameError::InvalidFormat);
+                }
+                let value = (0..quantity as usize)
+                    .map(|i| data[5 + i / 8] & bit_order.mask(i % 8) != 0)
+                    .collect();
+                Function::WriteMultipleCoils {
+                    starting_address: address,
+                    value,
+                }
+            }
+            crate::FunctionKind::WriteMultipleRegisters => {
+                if data[4] as usize != quantity as usize * 2 {
+                    return Err(RequestFrameError::InvalidFormat);
+                }
+                let value = (0..quantity as usize).map(|i| word(5 + i * 2)).collect();
+                Function::WriteMultipleRegisters {
+                    starting_address: address,
+                    value,
+                }
+            }
+            crate::FunctionKind::EncapsulatedInterfaceTransport => unreachable!(),
+        })
+    }
+
+    /// Returns the length of the request frame (slave id, payload, and CRC)
+    /// that encodes this function.
+    pub(crate) const fn request_len(&self) -> usize {
+        match self {
+            Function::ReadCoils { .. }
+            | Function::ReadDiscreteInputs { .. }
+            | Function::ReadHoldingRegisters { .. }
+            | Function::ReadInputRegisters { .. }
+            | Function::WriteSingleCoil { .. }
+            | Function::WriteSingleRegister { .. } => 8,
+            Function::WriteMultipleCoils { value, .. } => 9 + value.len().div_ceil(8),
+            Function::WriteMultipleRegisters { value, .. } => 9 + (value.len() * 2),
+            Function::EncapsulatedInterface { data, .. } => 5 + data.len(),
+        }
+    }
+
     /// Returns the minimum expected response length for this function.
     ///
     /// This helps callers pre-allocate receive buffers before the Modbus frame
@@ -219,13 +370,24 @@ impl Function {
     pub const fn expected_len(&self) -> usize {
         match self {
             Function::ReadCoils { quantity, .. } |
-            Function::ReadDiscreteInputs { quantity, .. } => 5 + ((*quantity as usize + 7) / 8),
+            Function::ReadDiscreteInputs { quantity, .. } => 5 + (*quantity as usize).div_ceil(8),
             Function::ReadHoldingRegisters { quantity, .. } |
             Function::ReadInputRegisters { quantity, .. } => 5 + (*quantity as usize * 2),
             Function::WriteSingleCoil { .. } |
             Function::WriteSingleRegister { .. } |
             Function::WriteMultipleCoils { .. } |
             Function::WriteMultipleRegisters { .. } => 8,
+            Function::EncapsulatedInterface { .. } => 5,
         }
     }
 }
+
+
+/// Fails with [`RequestPacketError::RequestTooBig`](crate::error::RequestPacketError::RequestTooBig)
+/// when a request carries more than `max` items.
+fn check_size(quantity: usize, max: usize) -> Result<(), crate::error::RequestPacketError> {
+    if quantity > max {
+        return Err(crate::error::RequestPacketError::RequestTooBig { quantity, max });
+    }
+    Ok(())
+}
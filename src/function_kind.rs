@@ -29,6 +29,9 @@ pub enum FunctionKind {
 
     /// Write Multiple Registers `(0x10)`
     WriteMultipleRegisters = 0x10,
+
+    /// Encapsulated Interface Transport `(0x2B)`
+    EncapsulatedInterfaceTransport = 0x2B,
 }
 
 impl FunctionKind {
@@ -74,9 +77,48 @@ impl FunctionKind {
             0x06 => Some(Self::WriteSingleRegister),
             0x0F => Some(Self::WriteMultipleCoils),
             0x10 => Some(Self::WriteMultipleRegisters),
+            0x2B => Some(Self::EncapsulatedInterfaceTransport),
             _ => None,
         }
     }
+
+    /// Returns the data model [`Table`](crate::Table) this function reads or
+    /// writes, or [`None`] for functions outside the data model.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{FunctionKind, Table};
+    ///
+    /// assert_eq!(FunctionKind::WriteMultipleCoils.table(), Some(Table::Coils));
+    /// assert_eq!(FunctionKind::EncapsulatedInterfaceTransport.table(), None);
+    /// ```
+    ///
+    pub const fn table(&self) -> Option<crate::Table> {
+        use crate::Table;
+        match self {
+            Self::ReadCoils | Self::WriteSingleCoil | Self::WriteMultipleCoils => {
+                Some(Table::Coils)
+            }
+            Self::ReadDiscreteInputs => Some(Table::DiscreteInputs),
+            Self::ReadHoldingRegisters
+            | Self::WriteSingleRegister
+            | Self::WriteMultipleRegisters => Some(Table::HoldingRegisters),
+            Self::ReadInputRegisters => Some(Table::InputRegisters),
+            Self::EncapsulatedInterfaceTransport => None,
+        }
+    }
+
+    /// Returns `true` for the four functions that read a data model table.
+    pub const fn is_read(&self) -> bool {
+        matches!(
+            self,
+            Self::ReadCoils
+                | Self::ReadDiscreteInputs
+                | Self::ReadHoldingRegisters
+                | Self::ReadInputRegisters
+        )
+    }
 }
 
 impl core::fmt::Display for FunctionKind {
@@ -93,6 +135,7 @@ impl core::fmt::Display for FunctionKind {
                 Self::WriteSingleRegister => "Write Single Register",
                 Self::WriteMultipleCoils => "Write Multiple Coils",
                 Self::WriteMultipleRegisters => "Write Multiple Registers",
+                Self::EncapsulatedInterfaceTransport => "Encapsulated Interface Transport",
             }
         )
     }
@@ -1,7 +1,44 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub(crate) mod crc;
 
 pub mod error;
 
+#[cfg(feature = "ascii")]
+pub mod ascii;
+
+#[cfg(feature = "master")]
+mod assembler;
+#[cfg(feature = "master")]
+pub use assembler::{Assembly, ResponseAssembler};
+
+mod baudrate;
+pub use baudrate::Baudrate;
+
+mod block;
+pub use block::DataBlock;
+
+mod bit_order;
+pub use bit_order::BitOrder;
+
+mod char_format;
+pub use char_format::*;
+
+#[cfg(feature = "master")]
+mod codec;
+#[cfg(feature = "master")]
+pub use codec::RtuCodec;
+
+pub mod frame;
+
+mod decoder;
+pub use decoder::{DecoderRegistry, ResponseDecoder};
+
+mod device;
+pub use device::{DeviceImage, DeviceTemplate, ImageTable};
+
 mod exception;
 pub use exception::*;
 
@@ -11,12 +48,58 @@ pub use function::Function;
 mod function_kind;
 pub use function_kind::FunctionKind;
 
+pub mod limits;
+
+mod quirk;
+pub use quirk::QuirkHook;
+
+pub mod raw;
+
 mod request;
 pub use request::*;
 
 mod response;
 pub use response::*;
 
-mod master;
+mod response_view;
+pub use response_view::ResponseView;
+
+#[cfg(feature = "slave")]
+mod slave;
+#[cfg(feature = "slave")]
+pub use slave::*;
+
+#[cfg(feature = "master")]
+mod static_poller;
 #[cfg(feature = "master")]
+pub use static_poller::StaticPoller;
+
+mod table;
+pub use table::Table;
+
+#[cfg(feature = "template")]
+mod template;
+#[cfg(feature = "template")]
+pub use template::*;
+
+pub mod test_vectors;
+
+mod warning;
+pub use warning::Warning;
+
+mod word_order;
+pub use word_order::WordOrder;
+
+#[cfg(all(feature = "master", feature = "sync"))]
+mod master;
+#[cfg(all(feature = "master", feature = "sync"))]
 pub use master::*;
+
+#[cfg(all(feature = "master", feature = "sync"))]
+pub mod ops;
+
+#[cfg(feature = "capi")]
+pub mod ffi;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
@@ -0,0 +1,59 @@
+//! Protocol limits enforced by the crate.
+//!
+//! Requests beyond these limits are rejected when encoded, unless the
+//! `unlimited_packet_size` feature is enabled, and answered with
+//! `IllegalDataValue` by a [`Slave`](crate::Slave). Applications can use the
+//! same constants to validate user input up front.
+//!
+//! ---
+//! # Examples
+//! ```rust
+//! use modbus_rtu::limits::{MAX_READ_COILS, MAX_WRITE_COILS};
+//! use modbus_rtu::{Function, Request};
+//!
+//! let timeout = std::time::Duration::from_millis(100);
+//! let read = |quantity| Function::ReadCoils { starting_address: 0, quantity };
+//! let write = |quantity| Function::WriteMultipleCoils { starting_address: 0, value: vec![true; quantity].into() };
+//!
+//! assert!(Request::new(0x01, &read(MAX_READ_COILS), timeout).to_bytes().is_ok());
+//! assert!(Request::new(0x01, &write(MAX_WRITE_COILS as usize), timeout).to_bytes().is_ok());
+//! # #[cfg(not(feature = "unlimited_packet_size"))]
+//! # {
+//! assert!(Request::new(0x01, &read(MAX_READ_COILS + 1), timeout).to_bytes().is_err());
+//! assert!(Request::new(0x01, &write(MAX_WRITE_COILS as usize + 1), timeout).to_bytes().is_err());
+//! # }
+//! ```
+//!
+//! The byte count of Write Multiple Coils must match the number of coils,
+//! both in encoded and in parsed requests:
+//! ```rust
+//! use modbus_rtu::{frame, raw};
+//!
+//! let mut buf = Vec::new();
+//! // 9 coils need 2 bytes.
+//! let valid = raw::frame(0x01, 0x0F, &[0x00, 0x00, 0x00, 0x09, 0x02, 0xFF, 0x01], &mut buf);
+//! assert!(frame::parse_request(valid).is_ok());
+//! let short = raw::frame(0x01, 0x0F, &[0x00, 0x00, 0x00, 0x09, 0x01, 0xFF], &mut buf);
+//! assert!(frame::parse_request(short).is_err());
+//! ```
+
+
+/// Largest protocol data unit (function code and data) a frame may carry:
+/// 256 bytes minus the slave id and the CRC.
+pub const MAX_PDU: usize = 253;
+
+/// Smallest complete frame: slave id, function code and CRC.
+pub const MIN_FRAME_LEN: usize = 4;
+
+/// Largest number of registers a single read may request.
+pub const MAX_READ_REGISTERS: u16 = 125;
+
+/// Largest number of registers a single Write Multiple Registers request may
+/// carry.
+pub const MAX_WRITE_REGISTERS: u16 = 123;
+
+/// Largest number of coils or discrete inputs a single read may request.
+pub const MAX_READ_COILS: u16 = 2000;
+
+/// Largest number of coils a single Write Multiple Coils request may carry.
+pub const MAX_WRITE_COILS: u16 = 1968;
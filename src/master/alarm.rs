@@ -0,0 +1,123 @@
+//! Alarms on the rate of exception responses per slave.
+
+use crate::Exception;
+
+
+/// ## ExceptionAlarm
+///
+/// Raised when a slave answered with the same exception more often within a
+/// sliding window than configured with
+/// [`Master::set_exception_alarm`](crate::Master::set_exception_alarm).
+///
+/// A steady rate of a given exception usually points at a configuration
+/// problem rather than a transient one, e.g. repeated `IllegalDataAddress`
+/// suggests that the register map used by the master does not match the
+/// device firmware.
+///
+/// The alarm is delivered once, to
+/// [`Observer::on_exception_alarm`](crate::Observer::on_exception_alarm),
+/// when the rate first exceeds the limit; it is raised again only after the
+/// rate fell back within the limit.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExceptionAlarm {
+    modbus_id: u8,
+    exception: Exception,
+    count: u32,
+    limit: u32,
+    window: core::time::Duration,
+}
+
+impl ExceptionAlarm {
+    /// Returns the id of the slave returning the exceptions.
+    pub fn modbus_id(&self) -> u8 {
+        self.modbus_id
+    }
+
+    /// Returns the exception returned.
+    pub fn exception(&self) -> Exception {
+        self.exception
+    }
+
+    /// Returns how many times the exception was returned within the window.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Returns the largest count tolerated within the window.
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    /// Returns the sliding window the exceptions are counted over.
+    pub fn window(&self) -> core::time::Duration {
+        self.window
+    }
+}
+
+impl core::fmt::Display for ExceptionAlarm {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "slave {} returned {} {} exceptions within {:?}, more than the {} tolerated.",
+            self.modbus_id, self.count, self.exception, self.window, self.limit
+        )
+    }
+}
+
+
+/// Exception counts per slave, checked against the configured limits.
+#[derive(Debug, Default)]
+pub(crate) struct ExceptionAlarms {
+    /// Largest tolerated count and window per exception code.
+    limits: std::collections::HashMap<u8, (u32, core::time::Duration)>,
+
+    /// Moments each slave returned each exception code, oldest first.
+    history: std::collections::HashMap<(u8, u8), std::collections::VecDeque<std::time::Instant>>,
+
+    /// Slave and exception code pairs whose alarm is currently raised.
+    raised: std::collections::HashSet<(u8, u8)>,
+}
+
+impl ExceptionAlarms {
+    /// Tolerates at most `limit` responses with `exception` per slave within
+    /// `window`.
+    pub(crate) fn set(&mut self, exception: Exception, limit: u32, window: core::time::Duration) {
+        self.limits.insert(exception.as_code(), (limit, window));
+    }
+
+    /// Stops counting `exception`.
+    pub(crate) fn remove(&mut self, exception: Exception) {
+        let code = exception.as_code();
+        self.limits.remove(&code);
+        self.history.retain(|(_, c), _| *c != code);
+        self.raised.retain(|(_, c)| *c != code);
+    }
+
+    /// Counts a response with `exception` from `modbus_id`, and returns the
+    /// alarm when it makes the count exceed the limit.
+    pub(crate) fn record(&mut self, modbus_id: u8, exception: Exception) -> Option<ExceptionAlarm> {
+        let key = (modbus_id, exception.as_code());
+        let &(limit, window) = self.limits.get(&key.1)?;
+        let now = std::time::Instant::now();
+        let history = self.history.entry(key).or_default();
+        history.push_back(now);
+        while let Some(oldest) = history.front()
+            && now.duration_since(*oldest) > window
+        {
+            history.pop_front();
+        }
+        let count = history.len() as u32;
+        if count <= limit {
+            self.raised.remove(&key);
+            return None;
+        }
+        self.raised.insert(key).then_some(ExceptionAlarm {
+            modbus_id,
+            exception,
+            count,
+            limit,
+            window,
+        })
+    }
+}
@@ -0,0 +1,258 @@
+//! Bus configurations declared in TOML files.
+
+use crate::error::ConfigError;
+use crate::{CharFormat, Master, PollMode, Poller, Table};
+
+
+/// ## Config
+///
+/// Every bus of an installation: its port, character format, slaves and
+/// the blocks polled from each of them.
+///
+/// A configuration is usually written by hand and loaded at start-up;
+/// [`MasterConfig::open`] then turns each bus into a ready [`Master`] and
+/// [`Poller`] in one call.
+///
+/// ```toml
+/// [[bus]]
+/// port = "/dev/ttyUSB0"
+/// baud_rate = 19200
+/// char_format = "8E1"
+/// poll_mode = "fixed_phase"
+///
+/// [[bus.slave]]
+/// name = "boiler"
+/// modbus_id = 1
+/// timeout_ms = 200
+///
+/// [[bus.slave.poll]]
+/// table = "holding_registers"
+/// starting_address = 0
+/// quantity = 16
+/// interval_ms = 1000
+/// ```
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{CharFormat, Config, Function};
+///
+/// let config = Config::from_toml(r#"
+///     [[bus]]
+///     port = "/dev/ttyUSB0"
+///     baud_rate = 19200
+///
+///     [[bus.slave]]
+///     name = "boiler"
+///     modbus_id = 1
+///
+///     [[bus.slave.poll]]
+///     table = "input_registers"
+///     starting_address = 8
+///     quantity = 4
+///     interval_ms = 500
+/// "#).unwrap();
+///
+/// let bus = &config.buses[0];
+/// assert_eq!(bus.char_format, CharFormat::E81);
+/// assert_eq!(bus.slaves[0].timeout_ms, 100);
+/// assert_eq!(
+///     bus.slaves[0].polls[0].function(),
+///     Function::ReadInputRegisters { starting_address: 8, quantity: 4 }
+/// );
+///
+/// // Saved configurations load back unchanged.
+/// assert_eq!(Config::from_toml(&config.to_toml().unwrap()).unwrap(), config);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct Config {
+    /// The buses, one per serial port.
+    #[serde(default, rename = "bus")]
+    pub buses: Vec<MasterConfig>,
+}
+
+impl Config {
+    /// Reads and parses the TOML file at `path`.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`ConfigError`] when the file cannot be read or is not a
+    /// valid configuration.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, ConfigError> {
+        Self::from_toml(&std::fs::read_to_string(path).map_err(ConfigError::IO)?)
+    }
+
+    /// Writes the configuration as TOML to the file at `path`, replacing it.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`ConfigError`] when the file cannot be written.
+    pub fn save(&self, path: impl AsRef<std::path::Path>) -> Result<(), ConfigError> {
+        std::fs::write(path, self.to_toml()?).map_err(ConfigError::IO)
+    }
+
+    /// Parses a configuration from TOML text.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`ConfigError::Syntax`] when `text` is not a valid
+    /// configuration.
+    pub fn from_toml(text: &str) -> Result<Self, ConfigError> {
+        toml::from_str(text).map_err(ConfigError::Syntax)
+    }
+
+    /// Formats the configuration as TOML text.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`ConfigError::Serialize`] when the configuration cannot be
+    /// represented in TOML.
+    pub fn to_toml(&self) -> Result<String, ConfigError> {
+        toml::to_string(self).map_err(ConfigError::Serialize)
+    }
+}
+
+
+/// One bus: the serial port a [`Master`] opens and the slaves it polls.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MasterConfig {
+    /// Path of the serial port, e.g. `/dev/ttyUSB0` or `COM3`.
+    pub port: String,
+
+    /// Baud rate of the bus.
+    pub baud_rate: u32,
+
+    /// Character format of the bus, written like `"8E1"`; the Modbus RTU
+    /// default 8E1 when omitted.
+    #[serde(default, with = "char_format_text")]
+    pub char_format: CharFormat,
+
+    /// How polling intervals are scheduled.
+    #[serde(default)]
+    pub poll_mode: PollMode,
+
+    /// The slaves on the bus.
+    #[serde(default, rename = "slave")]
+    pub slaves: Vec<SlaveProfile>,
+}
+
+impl MasterConfig {
+    /// Opens the port of this bus and plans the polling of all its slaves.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`ConfigError::Port`] when the port cannot be opened or
+    /// configured.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::Config;
+    ///
+    /// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Config::load("bus.toml")?;
+    /// let (mut master, mut poller) = config.buses[0].open()?;
+    /// loop {
+    ///     let (id, result) = poller.poll(&mut master).unwrap();
+    ///     println!("#{id}: {result:?}");
+    /// }
+    /// # }
+    /// ```
+    ///
+    pub fn open(&self) -> Result<(Master, Poller), ConfigError> {
+        let master = Master::open(&self.port, self.baud_rate, self.char_format)
+            .map_err(ConfigError::Port)?;
+        let mut poller = Poller::new(self.poll_mode);
+        for slave in &self.slaves {
+            for poll in &slave.polls {
+                poller.add(
+                    slave.modbus_id,
+                    poll.function(),
+                    core::time::Duration::from_millis(slave.timeout_ms),
+                    core::time::Duration::from_millis(poll.interval_ms),
+                );
+            }
+        }
+        Ok((master, poller))
+    }
+}
+
+
+/// One slave on a bus and the blocks polled from it.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SlaveProfile {
+    /// Name identifying the slave in the installation.
+    pub name: String,
+
+    /// Modbus id of the slave.
+    pub modbus_id: u8,
+
+    /// Response timeout in milliseconds, `100` when omitted.
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+
+    /// Blocks polled cyclically.
+    #[serde(default, rename = "poll")]
+    pub polls: Vec<PollProfile>,
+}
+
+
+/// A block of a slave polled at a fixed interval.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PollProfile {
+    /// Table the block is read from.
+    pub table: Table,
+
+    /// First address of the block.
+    pub starting_address: u16,
+
+    /// Number of coils or registers in the block.
+    pub quantity: u16,
+
+    /// Polling interval in milliseconds.
+    pub interval_ms: u64,
+}
+
+impl PollProfile {
+    /// Returns the read request polling the block.
+    pub fn function(&self) -> crate::Function {
+        self.table.read(self.starting_address, self.quantity)
+    }
+}
+
+
+/// Default response timeout of a [`SlaveProfile`].
+fn default_timeout_ms() -> u64 {
+    100
+}
+
+
+/// (De)serializes a [`CharFormat`] as its short notation, e.g. `"8E1"`.
+mod char_format_text {
+    use crate::{CharFormat, Parity};
+
+    pub(super) fn serialize<S: serde::Serializer>(
+        char_format: &CharFormat,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(char_format)
+    }
+
+    pub(super) fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CharFormat, D::Error> {
+        let text = <String as serde::Deserialize>::deserialize(deserializer)?;
+        let invalid = || serde::de::Error::custom(format!("\"{text}\" is not a character format"));
+        let &[data_bits @ b'5'..=b'8', parity, stop_bits @ b'1'..=b'2'] = text.as_bytes() else {
+            return Err(invalid());
+        };
+        let parity = match parity.to_ascii_uppercase() {
+            b'N' => Parity::None,
+            b'E' => Parity::Even,
+            b'O' => Parity::Odd,
+            _ => return Err(invalid()),
+        };
+        Ok(CharFormat::new(data_bits - b'0', parity, stop_bits - b'0'))
+    }
+}
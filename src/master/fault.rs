@@ -0,0 +1,315 @@
+//! Deterministic line-fault injection for testing masters without a noisy bus.
+
+use std::io::{Read, Write};
+
+
+/// Probabilities of the faults injected by a [`FaultyPort`].
+///
+/// Every probability is applied per received frame (except
+/// [`bit_flip`](Self::bit_flip), which is applied per byte) and must lie
+/// within `0.0..=1.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FaultConfig {
+    /// Probability that a single received byte has one random bit flipped.
+    pub bit_flip: f64,
+
+    /// Probability that a response is dropped entirely.
+    pub drop: f64,
+
+    /// Probability that a response is held back by [`delay_by`](Self::delay_by).
+    pub delay: f64,
+
+    /// How long a delayed response is held back, typically longer than the
+    /// request timeout.
+    pub delay_by: core::time::Duration,
+
+    /// Probability that a response is cut short at a random position.
+    pub truncate: f64,
+
+    /// Probability that a response is delivered twice back to back.
+    pub duplicate: f64,
+}
+
+
+/// Small xorshift generator so that a seed fully determines the faults.
+#[derive(Debug, Clone)]
+pub(crate) struct XorShift(u64);
+
+impl XorShift {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns `true` with probability `p`.
+    fn chance(&mut self, p: f64) -> bool {
+        p > 0.0 && (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= p
+    }
+
+    /// Returns a value in `0..upper`; `upper` must not be zero.
+    pub(crate) fn below(&mut self, upper: usize) -> usize {
+        (self.next_u64() % upper as u64) as usize
+    }
+}
+
+
+/// ## FaultyPort
+///
+/// Wraps a [`serialport::SerialPort`] and corrupts the responses read from it
+/// according to a [`FaultConfig`]: bit flips, dropped, delayed, truncated, and
+/// duplicated frames.
+///
+/// A response is the burst of bytes received after a write until the line
+/// goes idle. The wrapper collects each burst from the inner port, decides
+/// its faults with a seeded generator, and then serves the result through
+/// regular reads, honoring the port timeout just like a real device would. The
+/// same seed and traffic always produce the same faults, which makes retry
+/// and resynchronization logic testable.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use modbus_rtu::{FaultConfig, FaultyPort, Master};
+///
+/// # fn demo() -> serialport::Result<()> {
+/// let port = serialport::new("/dev/ttyUSB0", 9_600).open()?;
+/// let faults = FaultConfig { bit_flip: 0.001, drop: 0.05, ..FaultConfig::default() };
+/// let master = Master::from_port(Box::new(FaultyPort::new(port, faults, 42)), 9_600)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Debug)]
+pub struct FaultyPort {
+    inner: Box<dyn serialport::SerialPort>,
+    config: FaultConfig,
+    rng: XorShift,
+
+    /// Response prepared for delivery, with the instant it may be read.
+    pending: Option<(std::collections::VecDeque<u8>, std::time::Instant)>,
+}
+
+impl FaultyPort {
+    /// Wraps `inner`, injecting the faults described by `config` with a
+    /// generator seeded by `seed`.
+    pub fn new(inner: Box<dyn serialport::SerialPort>, config: FaultConfig, seed: u64) -> Self {
+        Self {
+            inner,
+            config,
+            rng: XorShift::new(seed),
+            pending: None,
+        }
+    }
+
+    /// Returns the fault configuration.
+    pub fn config(&self) -> &FaultConfig {
+        &self.config
+    }
+
+    /// Replaces the fault configuration; the generator state is kept.
+    pub fn set_config(&mut self, config: FaultConfig) {
+        self.config = config;
+    }
+
+    /// Unwraps the inner port.
+    pub fn into_inner(self) -> Box<dyn serialport::SerialPort> {
+        self.inner
+    }
+
+    /// Reads one burst from the inner port, stopping at the first idle timeout.
+    fn capture(&mut self) -> std::io::Result<Vec<u8>> {
+        let mut frame = Vec::new();
+        let mut chunk = [0u8; 256];
+        loop {
+            match self.inner.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(n) => frame.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut && !frame.is_empty() => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(frame)
+    }
+
+    /// Applies the configured faults to a captured burst.
+    fn corrupt(&mut self, mut frame: Vec<u8>) -> (Vec<u8>, std::time::Instant) {
+        let mut deliver_at = std::time::Instant::now();
+        if self.rng.chance(self.config.drop) {
+            frame.clear();
+        }
+        for byte in frame.iter_mut() {
+            if self.rng.chance(self.config.bit_flip) {
+                *byte ^= 1 << self.rng.below(8);
+            }
+        }
+        if frame.len() > 1 && self.rng.chance(self.config.truncate) {
+            let len = 1 + self.rng.below(frame.len() - 1);
+            frame.truncate(len);
+        }
+        if self.rng.chance(self.config.duplicate) {
+            frame.extend_from_within(..);
+        }
+        if self.rng.chance(self.config.delay) {
+            deliver_at += self.config.delay_by;
+        }
+        (frame, deliver_at)
+    }
+}
+
+impl Read for FaultyPort {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let (mut frame, deliver_at) = match self.pending.take() {
+            Some(pending) => pending,
+            None => {
+                let frame = self.capture()?;
+                let (frame, deliver_at) = self.corrupt(frame);
+                (frame.into(), deliver_at)
+            }
+        };
+        let timeout = self.inner.timeout();
+        let now = std::time::Instant::now();
+        if deliver_at > now {
+            let wait = deliver_at - now;
+            std::thread::sleep(wait.min(timeout));
+            if wait > timeout {
+                self.pending = Some((frame, deliver_at));
+                return Err(std::io::ErrorKind::TimedOut.into());
+            }
+        }
+        if frame.is_empty() {
+            std::thread::sleep(timeout);
+            return Err(std::io::ErrorKind::TimedOut.into());
+        }
+        let n = frame.read(buf)?;
+        if !frame.is_empty() {
+            self.pending = Some((frame, deliver_at));
+        }
+        Ok(n)
+    }
+}
+
+impl Write for FaultyPort {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.pending = None;
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl serialport::SerialPort for FaultyPort {
+    fn name(&self) -> Option<String> {
+        self.inner.name()
+    }
+
+    fn baud_rate(&self) -> serialport::Result<u32> {
+        self.inner.baud_rate()
+    }
+
+    fn data_bits(&self) -> serialport::Result<serialport::DataBits> {
+        self.inner.data_bits()
+    }
+
+    fn flow_control(&self) -> serialport::Result<serialport::FlowControl> {
+        self.inner.flow_control()
+    }
+
+    fn parity(&self) -> serialport::Result<serialport::Parity> {
+        self.inner.parity()
+    }
+
+    fn stop_bits(&self) -> serialport::Result<serialport::StopBits> {
+        self.inner.stop_bits()
+    }
+
+    fn timeout(&self) -> core::time::Duration {
+        self.inner.timeout()
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> serialport::Result<()> {
+        self.inner.set_baud_rate(baud_rate)
+    }
+
+    fn set_data_bits(&mut self, data_bits: serialport::DataBits) -> serialport::Result<()> {
+        self.inner.set_data_bits(data_bits)
+    }
+
+    fn set_flow_control(
+        &mut self,
+        flow_control: serialport::FlowControl,
+    ) -> serialport::Result<()> {
+        self.inner.set_flow_control(flow_control)
+    }
+
+    fn set_parity(&mut self, parity: serialport::Parity) -> serialport::Result<()> {
+        self.inner.set_parity(parity)
+    }
+
+    fn set_stop_bits(&mut self, stop_bits: serialport::StopBits) -> serialport::Result<()> {
+        self.inner.set_stop_bits(stop_bits)
+    }
+
+    fn set_timeout(&mut self, timeout: core::time::Duration) -> serialport::Result<()> {
+        self.inner.set_timeout(timeout)
+    }
+
+    fn write_request_to_send(&mut self, level: bool) -> serialport::Result<()> {
+        self.inner.write_request_to_send(level)
+    }
+
+    fn write_data_terminal_ready(&mut self, level: bool) -> serialport::Result<()> {
+        self.inner.write_data_terminal_ready(level)
+    }
+
+    fn read_clear_to_send(&mut self) -> serialport::Result<bool> {
+        self.inner.read_clear_to_send()
+    }
+
+    fn read_data_set_ready(&mut self) -> serialport::Result<bool> {
+        self.inner.read_data_set_ready()
+    }
+
+    fn read_ring_indicator(&mut self) -> serialport::Result<bool> {
+        self.inner.read_ring_indicator()
+    }
+
+    fn read_carrier_detect(&mut self) -> serialport::Result<bool> {
+        self.inner.read_carrier_detect()
+    }
+
+    fn bytes_to_read(&self) -> serialport::Result<u32> {
+        let pending = self
+            .pending
+            .as_ref()
+            .map_or(0, |(frame, _)| frame.len() as u32);
+        Ok(pending + self.inner.bytes_to_read()?)
+    }
+
+    fn bytes_to_write(&self) -> serialport::Result<u32> {
+        self.inner.bytes_to_write()
+    }
+
+    fn clear(&self, buffer_to_clear: serialport::ClearBuffer) -> serialport::Result<()> {
+        self.inner.clear(buffer_to_clear)
+    }
+
+    fn try_clone(&self) -> serialport::Result<Box<dyn serialport::SerialPort>> {
+        self.inner.try_clone()
+    }
+
+    fn set_break(&self) -> serialport::Result<()> {
+        self.inner.set_break()
+    }
+
+    fn clear_break(&self) -> serialport::Result<()> {
+        self.inner.clear_break()
+    }
+}
@@ -0,0 +1,200 @@
+//! Bus health summary reported by [`Master::health`](crate::Master::health).
+
+use crate::Response;
+
+
+/// Default length of the sliding window over which error rates are computed.
+pub(crate) const DEFAULT_WINDOW: core::time::Duration = core::time::Duration::from_secs(60);
+
+
+/// ## BusHealth
+///
+/// Snapshot of the state of a bus, returned by
+/// [`Master::health`](crate::Master::health).
+///
+/// With the `serde` feature enabled the snapshot implements `Serialize`, so
+/// it can be handed directly to a monitoring endpoint.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BusHealth {
+    pub(crate) baud_rate: u32,
+    pub(crate) port_ok: bool,
+    pub(crate) consecutive_failures: u32,
+    pub(crate) window: core::time::Duration,
+    pub(crate) slaves: Vec<SlaveStatus>,
+}
+
+impl BusHealth {
+    /// Returns the baud rate configured on the port.
+    pub fn baud_rate(&self) -> u32 {
+        self.baud_rate
+    }
+
+    /// Returns `true` when the port answered a status query.
+    pub fn port_ok(&self) -> bool {
+        self.port_ok
+    }
+
+    /// Returns the number of transactions that failed in a row, across all
+    /// slaves.
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    /// Returns the length of the sliding window the per-slave counts cover.
+    pub fn window(&self) -> core::time::Duration {
+        self.window
+    }
+
+    /// Returns the status of every slave addressed so far, ordered by id.
+    pub fn slaves(&self) -> &[SlaveStatus] {
+        &self.slaves
+    }
+}
+
+
+/// ## SlaveStatus
+///
+/// Per-slave part of a [`BusHealth`] snapshot.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SlaveStatus {
+    pub(crate) modbus_id: u8,
+    pub(crate) last_success: Option<std::time::SystemTime>,
+    pub(crate) transactions: u32,
+    pub(crate) failures: u32,
+    pub(crate) exceptions: u32,
+}
+
+impl SlaveStatus {
+    /// Returns the slave id.
+    pub fn modbus_id(&self) -> u8 {
+        self.modbus_id
+    }
+
+    /// Returns the wall-clock time of the last transaction that received a
+    /// valid, non-exception response.
+    pub fn last_success(&self) -> Option<std::time::SystemTime> {
+        self.last_success
+    }
+
+    /// Returns the number of transactions within the window.
+    pub fn transactions(&self) -> u32 {
+        self.transactions
+    }
+
+    /// Returns the number of transactions within the window that received
+    /// no valid response.
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// Returns the number of exception responses within the window.
+    pub fn exceptions(&self) -> u32 {
+        self.exceptions
+    }
+
+    /// Returns the share of failed transactions within the window, or `0.0`
+    /// when there were none.
+    pub fn error_rate(&self) -> f64 {
+        match self.transactions {
+            0 => 0.0,
+            n => self.failures as f64 / n as f64,
+        }
+    }
+}
+
+
+/// Outcome of one transaction as remembered for the health window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Success,
+    Exception,
+    Failure,
+}
+
+
+/// Recent outcomes of one slave.
+#[derive(Debug, Clone, Default)]
+struct SlaveHistory {
+    last_success: Option<std::time::SystemTime>,
+    recent: std::collections::VecDeque<(std::time::Instant, Outcome)>,
+}
+
+
+/// Collects per-slave outcomes for [`BusHealth`] snapshots.
+#[derive(Debug, Clone)]
+pub(crate) struct HealthTracker {
+    pub(crate) window: core::time::Duration,
+    slaves: std::collections::BTreeMap<u8, SlaveHistory>,
+}
+
+impl HealthTracker {
+    pub(crate) fn new() -> Self {
+        Self {
+            window: DEFAULT_WINDOW,
+            slaves: std::collections::BTreeMap::new(),
+        }
+    }
+
+    /// Records the outcome of a transaction with `modbus_id`. Requests that
+    /// were rejected before transmission and broadcasts are not recorded.
+    pub(crate) fn record(&mut self, modbus_id: u8, result: &Result<Response, crate::error::Error>) {
+        let outcome = match result {
+            _ if modbus_id == 0 => return,
+            Err(crate::error::Error::Request(_)) => return,
+            Ok(Response::Exception(_)) | Err(crate::error::Error::Exception(..)) => {
+                Outcome::Exception
+            }
+            Ok(_) => Outcome::Success,
+            Err(_) => Outcome::Failure,
+        };
+        let now = std::time::Instant::now();
+        let history = self.slaves.entry(modbus_id).or_default();
+        if outcome == Outcome::Success {
+            history.last_success = Some(std::time::SystemTime::now());
+        }
+        history.recent.push_back((now, outcome));
+        Self::expire(&mut history.recent, now, self.window);
+    }
+
+    /// Summarizes the outcomes within the window.
+    pub(crate) fn slaves(&self) -> Vec<SlaveStatus> {
+        let now = std::time::Instant::now();
+        self.slaves
+            .iter()
+            .map(|(&modbus_id, history)| {
+                let recent: Vec<Outcome> = history
+                    .recent
+                    .iter()
+                    .filter(|(at, _)| now.duration_since(*at) <= self.window)
+                    .map(|(_, outcome)| *outcome)
+                    .collect();
+                let count = |outcome| recent.iter().filter(|o| **o == outcome).count() as u32;
+                SlaveStatus {
+                    modbus_id,
+                    last_success: history.last_success,
+                    transactions: recent.len() as u32,
+                    failures: count(Outcome::Failure),
+                    exceptions: count(Outcome::Exception),
+                }
+            })
+            .collect()
+    }
+
+    /// Drops outcomes older than `window`.
+    fn expire(
+        recent: &mut std::collections::VecDeque<(std::time::Instant, Outcome)>,
+        now: std::time::Instant,
+        window: core::time::Duration,
+    ) {
+        while recent
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > window)
+        {
+            recent.pop_front();
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Higher-level transaction patterns built on [`Master::send`].
+
+use crate::{DeviceImage, DeviceTemplate, Function, Master, Request, Response};
+
+
+impl Master {
+    /// Reads a block repeatedly until two consecutive reads return identical
+    /// data, so that multi-register values updated asynchronously by the
+    /// device (totals, counters) are not torn.
+    ///
+    /// At most `max_attempts` reads are performed (at least two). Exception
+    /// responses are returned immediately. Requests other than the four read
+    /// functions are sent once and returned as is, since repeating a write is
+    /// not a read.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`Error::InconsistentRead`](crate::error::Error::InconsistentRead)
+    /// when no two consecutive reads matched within `max_attempts`, in
+    /// addition to the errors of [`send`](Self::send).
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{Function, Master, Request};
+    ///
+    /// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 19_200)?;
+    /// let func = Function::ReadHoldingRegisters { starting_address: 0x0100, quantity: 4 };
+    /// let request = Request::new(0x01, &func, std::time::Duration::from_millis(200));
+    /// let totals = master.read_consistent(&request, 5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn read_consistent(
+        &mut self,
+        req: &Request,
+        max_attempts: u32,
+    ) -> Result<Response, crate::error::Error> {
+        let mut previous = self.send(req)?;
+        if !req.function().kind().is_read() || matches!(previous, Response::Exception(_)) {
+            return Ok(previous);
+        }
+        for _ in 1..max_attempts.max(2) {
+            let current = self.send(req)?;
+            if matches!(current, Response::Exception(_)) || current == previous {
+                return Ok(current);
+            }
+            previous = current;
+        }
+        Err(crate::error::Error::InconsistentRead {
+            attempts: max_attempts.max(2),
+        })
+    }
+
+    /// Reads a block bracketed by two reads of a sequence counter and retries
+    /// until the counter did not change during the block read.
+    ///
+    /// This suits devices that expose an update counter next to their data:
+    /// the block is consistent when the counter read before and after it is
+    /// equal. At most `max_attempts` block reads are performed.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`Error::InconsistentRead`](crate::error::Error::InconsistentRead)
+    /// when the counter changed during every attempt, in addition to the
+    /// errors of [`send`](Self::send). Exception responses to either request
+    /// are returned as responses.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{Function, Master, Request};
+    /// use std::time::Duration;
+    ///
+    /// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 19_200)?;
+    /// let counter = Function::ReadInputRegisters { starting_address: 0x0000, quantity: 1 };
+    /// let block = Function::ReadInputRegisters { starting_address: 0x0010, quantity: 8 };
+    /// let snapshot = master.read_with_sequence(
+    ///     &Request::new(0x01, &block, Duration::from_millis(200)),
+    ///     &Request::new(0x01, &counter, Duration::from_millis(200)),
+    ///     3,
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn read_with_sequence(
+        &mut self,
+        req: &Request,
+        sequence: &Request,
+        max_attempts: u32,
+    ) -> Result<Response, crate::error::Error> {
+        let attempts = max_attempts.max(1);
+        for _ in 0..attempts {
+            let before = self.send(sequence)?;
+            if matches!(before, Response::Exception(_)) {
+                return Ok(before);
+            }
+            let block = self.send(req)?;
+            if matches!(block, Response::Exception(_)) {
+                return Ok(block);
+            }
+            let after = self.send(sequence)?;
+            if matches!(after, Response::Exception(_)) {
+                return Ok(after);
+            }
+            if before == after {
+                return Ok(block);
+            }
+        }
+        Err(crate::error::Error::InconsistentRead { attempts })
+    }
+
+    /// Sends a write request and reads the written coils or registers back to
+    /// verify that the device stored exactly what was sent.
+    ///
+    /// Some devices silently clamp or ignore values while still echoing a
+    /// successful write. The read-back uses Read Coils for coil writes and
+    /// Read Holding Registers for register writes, with the timeout and bit
+    /// order of `req`. Broadcasts cannot be read back and are returned
+    /// unverified, as are exception responses and non-write requests.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`Error::VerificationFailed`](crate::error::Error::VerificationFailed)
+    /// with the expected and the read-back state when they differ (including
+    /// when the read-back is answered with an exception), in addition to the
+    /// errors of [`send`](Self::send).
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{Function, Master, Request};
+    ///
+    /// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 19_200)?;
+    /// let func = Function::WriteSingleRegister { address: 0x0010, value: 1500 };
+    /// let request = Request::new(0x01, &func, std::time::Duration::from_millis(200));
+    /// master.write_verified(&request)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn write_verified(&mut self, req: &Request) -> Result<Response, crate::error::Error> {
+        let response = self.send(req)?;
+        if req.is_broadcasting() || matches!(response, Response::Exception(_)) {
+            return Ok(response);
+        }
+        let (read_back, expected) = match req.function() {
+            Function::WriteSingleCoil { address, value } => (
+                Function::ReadCoils {
+                    starting_address: *address,
+                    quantity: 1,
+                },
+                Response::Status(Box::new([*value])),
+            ),
+            Function::WriteMultipleCoils {
+                starting_address,
+                value,
+            } => (
+                Function::ReadCoils {
+                    starting_address: *starting_address,
+                    quantity: value.len() as u16,
+                },
+                Response::Status(value.clone()),
+            ),
+            Function::WriteSingleRegister { address, value } => (
+                Function::ReadHoldingRegisters {
+                    starting_address: *address,
+                    quantity: 1,
+                },
+                Response::Value(Box::new([*value])),
+            ),
+            Function::WriteMultipleRegisters {
+                starting_address,
+                value,
+            } => (
+                Function::ReadHoldingRegisters {
+                    starting_address: *starting_address,
+                    quantity: value.len() as u16,
+                },
+                Response::Value(value.clone()),
+            ),
+            _ => return Ok(response),
+        };
+        let mut read_req = Request::new(req.modbus_id(), &read_back, req.timeout());
+        read_req.set_bit_order(req.bit_order());
+        let actual = self.send(&read_req)?;
+        if actual != expected {
+            return Err(crate::error::Error::VerificationFailed { expected, actual });
+        }
+        Ok(response)
+    }
+
+    /// Reads every range of `template` from slave `modbus_id` with the
+    /// minimal set of requests and returns the assembled [`DeviceImage`].
+    ///
+    /// Each request is sent with `timeout`; the first failure or exception
+    /// aborts the poll.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns the errors of [`send`](Self::send), with exception responses
+    /// reported as [`Error::Exception`](crate::error::Error::Exception).
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{DeviceTemplate, Master, Table};
+    /// use std::time::Duration;
+    ///
+    /// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 19_200)?;
+    /// let template = DeviceTemplate::new()
+    ///     .with(Table::Coils, 0..32)
+    ///     .with(Table::HoldingRegisters, 0..64)
+    ///     .with(Table::InputRegisters, 0..16);
+    ///
+    /// let image = master.poll_device(0x01, &template, Duration::from_millis(200))?;
+    /// println!("{:?}", image.holding_registers().range(0, 4));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn poll_device(
+        &mut self,
+        modbus_id: u8,
+        template: &DeviceTemplate,
+        timeout: core::time::Duration,
+    ) -> Result<DeviceImage, crate::error::Error> {
+        let mut responses = Vec::new();
+        for function in template.functions() {
+            let request = Request::new(modbus_id, &function, timeout);
+            responses.push(self.send(&request)?.into_result(&request)?);
+        }
+        DeviceImage::decode(template, responses).ok_or(crate::error::Error::Response(
+            crate::error::ResponsePacketError::InvalidFormat,
+        ))
+    }
+}
@@ -1,2 +1,74 @@
 mod sync;
 pub use sync::*;
+
+mod helpers;
+
+mod poller;
+pub use poller::*;
+
+#[cfg(feature = "config")]
+mod config;
+#[cfg(feature = "config")]
+pub use config::*;
+
+mod alarm;
+pub use alarm::ExceptionAlarm;
+
+mod fault;
+pub use fault::*;
+
+mod transaction;
+pub use transaction::*;
+
+mod watchdog;
+pub use watchdog::*;
+
+mod reset;
+pub use reset::*;
+
+mod health;
+pub use health::{BusHealth, SlaveStatus};
+
+mod ota;
+pub use ota::{OtaOptions, OtaTransport};
+
+mod retry;
+pub use retry::RetryPolicy;
+
+mod statistics;
+pub use statistics::{LatencyHistogram, Statistics};
+
+mod self_test;
+pub use self_test::*;
+
+mod redundant;
+pub use redundant::*;
+
+mod recorder;
+pub use recorder::*;
+
+mod monitor;
+pub use monitor::*;
+
+#[cfg(feature = "tcp")]
+mod rfc2217;
+#[cfg(feature = "tcp")]
+pub use rfc2217::Rfc2217Port;
+
+#[cfg(feature = "tcp")]
+mod tcp;
+#[cfg(feature = "tcp")]
+pub use tcp::TcpPort;
+
+mod url;
+
+#[cfg(all(unix, feature = "slave"))]
+mod virtual_bus;
+#[cfg(all(unix, feature = "slave"))]
+pub use virtual_bus::VirtualBus;
+
+mod soak;
+pub use soak::*;
+
+mod stale;
+pub use stale::{LateResponse, StaleInput};
@@ -0,0 +1,431 @@
+//! Passive decoding of traffic exchanged by other devices on the bus.
+
+use crate::frame::DecodedFrame;
+use crate::{CharFormat, Function, Request, Response, Timestamp};
+
+
+/// ## MonitoredTransaction
+///
+/// A request seen on the bus and the response that followed it.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitoredTransaction {
+    modbus_id: u8,
+    function: Function,
+    request_frame: Box<[u8]>,
+    response_frame: Box<[u8]>,
+    response: Response,
+    requested: Timestamp,
+    responded: Timestamp,
+}
+
+impl MonitoredTransaction {
+    /// Returns the addressed slave id.
+    pub fn modbus_id(&self) -> u8 {
+        self.modbus_id
+    }
+
+    /// Returns the decoded request.
+    pub fn function(&self) -> &Function {
+        &self.function
+    }
+
+    /// Returns the request frame as seen on the bus, CRC included.
+    pub fn request_frame(&self) -> &[u8] {
+        &self.request_frame
+    }
+
+    /// Returns the response frame as seen on the bus, CRC included.
+    pub fn response_frame(&self) -> &[u8] {
+        &self.response_frame
+    }
+
+    /// Returns the decoded response.
+    pub fn response(&self) -> &Response {
+        &self.response
+    }
+
+    /// Returns when the first byte of the request was received.
+    pub fn requested(&self) -> Timestamp {
+        self.requested
+    }
+
+    /// Returns when the first byte of the response was received.
+    pub fn responded(&self) -> Timestamp {
+        self.responded
+    }
+
+    /// Returns the time from the start of the request to the start of the
+    /// response.
+    pub fn latency(&self) -> core::time::Duration {
+        self.responded
+            .instant()
+            .saturating_duration_since(self.requested.instant())
+    }
+}
+
+
+/// What a [`Monitor`] made of the traffic it observed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MonitorEvent {
+    /// A request followed by its response.
+    Transaction(MonitoredTransaction),
+
+    /// A request followed by another request instead of a response.
+    /// Broadcasts, which are never answered, are reported this way as soon as
+    /// they are seen.
+    Unanswered {
+        modbus_id: u8,
+        function: Function,
+        frame: Box<[u8]>,
+        at: Timestamp,
+    },
+
+    /// A frame that fails the CRC check, that fits neither direction, or
+    /// that is a response to a request the monitor did not see.
+    Unrecognized { frame: Box<[u8]>, at: Timestamp },
+}
+
+
+/// ## Monitor
+///
+/// Listens to a bus driven by other devices, e.g. a third-party master
+/// polling third-party slaves, and decodes what they exchange.
+///
+/// The monitor never transmits. It splits the incoming bytes into frames on
+/// silences of at least the Modbus RTU silent interval (T3.5, see
+/// [`set_silence`](Self::set_silence)), checks their CRC, and pairs each
+/// request with the response that follows it, telling both apart with
+/// [`frame::decode_after`](crate::frame::decode_after).
+///
+/// A monitor created with [`replay`](Self::replay) has no port; it segments
+/// bytes [`fed`](Self::feed) with the timestamps of an external capture
+/// instead.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use modbus_rtu::{Baudrate, CharFormat, Monitor, MonitorEvent};
+///
+/// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut monitor = Monitor::open("/dev/ttyUSB0", Baudrate::Baud19200, CharFormat::E81)?;
+/// loop {
+///     match monitor.next_event()? {
+///         MonitorEvent::Transaction(transaction) => println!(
+///             "0x{:02X} {:?} -> {:?} in {:?}",
+///             transaction.modbus_id(),
+///             transaction.function(),
+///             transaction.response(),
+///             transaction.latency()
+///         ),
+///         MonitorEvent::Unanswered { modbus_id, function, .. } => {
+///             println!("0x{modbus_id:02X} {function:?} unanswered")
+///         }
+///         MonitorEvent::Unrecognized { frame, .. } => println!("garbage {frame:02X?}"),
+///     }
+/// }
+/// # }
+/// ```
+///
+#[derive(Debug)]
+pub struct Monitor {
+    port: Option<Box<dyn serialport::SerialPort>>,
+    baud_rate: u32,
+    char_format: CharFormat,
+    silence: core::time::Duration,
+    capture: Vec<u8>,
+    capture_start: core::time::Duration,
+    capture_last: core::time::Duration,
+    capture_epoch: Option<Timestamp>,
+    pending: Option<(u8, Function, Box<[u8]>, Timestamp)>,
+    events: std::collections::VecDeque<MonitorEvent>,
+}
+
+impl Monitor {
+    /// Opens the serial port at `path` with `baud_rate` and `char_format`,
+    /// for listening only.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`serialport::Error`] when the port cannot be opened or
+    /// configured.
+    pub fn open(
+        path: &str,
+        baud_rate: impl Into<u32>,
+        char_format: CharFormat,
+    ) -> serialport::Result<Self> {
+        let baud_rate = baud_rate.into();
+        let (data_bits, parity, stop_bits) = super::sync::serial_settings(char_format);
+        let port = serialport::new(path, baud_rate)
+            .data_bits(data_bits)
+            .parity(parity)
+            .stop_bits(stop_bits)
+            .open()?;
+        Self::from_port(port, baud_rate, char_format)
+    }
+
+    /// Listens on an already opened serial port, whose character format is
+    /// `char_format`.
+    ///
+    /// Only the timeout of the port is changed, to the silent interval
+    /// (T3.5) for `baud_rate` and `char_format`.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`serialport::Error`] when the timeout cannot be set.
+    pub fn from_port(
+        mut port: Box<dyn serialport::SerialPort>,
+        baud_rate: impl Into<u32>,
+        char_format: CharFormat,
+    ) -> serialport::Result<Self> {
+        let mut monitor = Self::replay(baud_rate, char_format);
+        port.set_timeout(monitor.silence)?;
+        monitor.port = Some(port);
+        Ok(monitor)
+    }
+
+    /// Creates a monitor without a port, decoding a capture recorded at
+    /// `baud_rate` with `char_format`, e.g. by a logic analyzer.
+    ///
+    /// Bytes are passed with [`feed`](Self::feed) and the resulting events
+    /// taken with [`poll_event`](Self::poll_event).
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{test_vectors, Baudrate, CharFormat, Monitor, MonitorEvent, Response};
+    /// use std::time::Duration;
+    ///
+    /// let mut monitor = Monitor::replay(Baudrate::Baud9600, CharFormat::N81);
+    /// let char_time = CharFormat::N81.char_time(9_600);
+    ///
+    /// let vector = test_vectors::READ_HOLDING_REGISTERS;
+    /// for (i, byte) in vector.request.iter().enumerate() {
+    ///     monitor.feed(*byte, char_time * i as u32);
+    /// }
+    /// let reply_start = Duration::from_millis(40);
+    /// for (i, byte) in vector.response.iter().enumerate() {
+    ///     monitor.feed(*byte, reply_start + char_time * i as u32);
+    /// }
+    /// monitor.finish();
+    ///
+    /// let Some(MonitorEvent::Transaction(transaction)) = monitor.poll_event() else {
+    ///     panic!("expected a transaction");
+    /// };
+    /// assert_eq!(transaction.modbus_id(), 0x11);
+    /// assert_eq!(transaction.response(), &Response::Value(Box::new([0x022B, 0x0000, 0x0064])));
+    /// assert_eq!(transaction.latency(), reply_start);
+    /// assert_eq!(monitor.poll_event(), None);
+    /// ```
+    ///
+    pub fn replay(baud_rate: impl Into<u32>, char_format: CharFormat) -> Self {
+        let baud_rate = baud_rate.into();
+        Self {
+            port: None,
+            baud_rate,
+            char_format,
+            silence: char_format.idle_time(baud_rate),
+            capture: Vec::new(),
+            capture_start: core::time::Duration::ZERO,
+            capture_last: core::time::Duration::ZERO,
+            capture_epoch: None,
+            pending: None,
+            events: std::collections::VecDeque::new(),
+        }
+    }
+
+    /// Returns the baud rate the monitor listens at.
+    pub fn baud_rate(&self) -> u32 {
+        self.baud_rate
+    }
+
+    /// Returns the character format the monitor listens with.
+    pub fn char_format(&self) -> CharFormat {
+        self.char_format
+    }
+
+    /// Returns the shortest silence that ends a frame.
+    pub fn silence(&self) -> core::time::Duration {
+        self.silence
+    }
+
+    /// Sets the shortest silence that ends a frame, T3.5 for the baud rate
+    /// and character format by default.
+    ///
+    /// A longer silence keeps frames whole when a USB adapter or a capture
+    /// tool delivers bytes in bursts, or when devices on the bus pause
+    /// within their frames; a shorter one separates frames on busy buses at
+    /// baud rates where T3.5 is an odd number.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`serialport::Error`] when the timeout of the port cannot be
+    /// changed.
+    pub fn set_silence(&mut self, silence: core::time::Duration) -> serialport::Result<()> {
+        if let Some(port) = &mut self.port {
+            port.set_timeout(silence)?;
+        }
+        self.silence = silence;
+        Ok(())
+    }
+
+    /// Passes one captured byte received `offset` after the start of the
+    /// capture.
+    ///
+    /// Offsets must not decrease. The bytes fed so far form a frame once the
+    /// next byte comes at least [`silence`](Self::silence) after the last
+    /// one, or [`finish`](Self::finish) is called. Events are timestamped
+    /// relative to the moment the first byte was fed.
+    pub fn feed(&mut self, byte: u8, offset: core::time::Duration) {
+        if !self.capture.is_empty() && offset.saturating_sub(self.capture_last) >= self.silence {
+            self.end_capture_frame();
+        }
+        if self.capture.is_empty() {
+            self.capture_start = offset;
+        }
+        self.capture.push(byte);
+        self.capture_last = offset;
+    }
+
+    /// Ends the capture: the bytes fed last form a frame, and a request
+    /// still waiting for a response is reported as unanswered.
+    pub fn finish(&mut self) {
+        if !self.capture.is_empty() {
+            self.end_capture_frame();
+        }
+        if let Some((modbus_id, function, frame, at)) = self.pending.take() {
+            self.events.push_back(MonitorEvent::Unanswered {
+                modbus_id,
+                function,
+                frame,
+                at,
+            });
+        }
+    }
+
+    /// Takes the oldest event decoded so far, without blocking.
+    pub fn poll_event(&mut self) -> Option<MonitorEvent> {
+        self.events.pop_front()
+    }
+
+    /// Blocks until the traffic yields an event.
+    ///
+    /// A request is held back until the next frame shows whether it was
+    /// answered, so the event describing it is returned only then.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`std::io::Error`] when reading the port fails for any reason
+    /// other than the bus being silent, or with
+    /// [`NotConnected`](std::io::ErrorKind::NotConnected) when a monitor
+    /// created with [`replay`](Self::replay) has no event left.
+    pub fn next_event(&mut self) -> std::io::Result<MonitorEvent> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Ok(event);
+            }
+            let (frame, at) = self.read_frame()?;
+            self.decode(frame, at);
+        }
+    }
+
+    /// Decodes the captured bytes fed since the last silence as a frame.
+    fn end_capture_frame(&mut self) {
+        let epoch = *self.capture_epoch.get_or_insert_with(Timestamp::now);
+        let frame = core::mem::take(&mut self.capture).into_boxed_slice();
+        self.decode(frame, epoch.after(self.capture_start));
+    }
+
+    /// Reads bytes until the bus stays silent after at least one of them,
+    /// and returns them with the moment the first one arrived.
+    fn read_frame(&mut self) -> std::io::Result<(Box<[u8]>, Timestamp)> {
+        let Some(port) = &mut self.port else {
+            return Err(std::io::ErrorKind::NotConnected.into());
+        };
+        let mut frame = Vec::new();
+        let mut started = None;
+        let mut buf = [0u8; 256];
+        loop {
+            match port.read(&mut buf) {
+                Ok(len) if len > 0 => {
+                    started.get_or_insert_with(Timestamp::now);
+                    frame.extend_from_slice(&buf[..len]);
+                }
+                Ok(_) => {}
+                Err(error) if error.kind() == std::io::ErrorKind::TimedOut => {
+                    if let Some(started) = started {
+                        return Ok((frame.into_boxed_slice(), started));
+                    }
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Pairs `frame` with the pending request, or takes it as a new request,
+    /// and queues the resulting events.
+    fn decode(&mut self, frame: Box<[u8]>, at: Timestamp) {
+        let pending = self.pending.take();
+        let decoded = match &pending {
+            Some((modbus_id, function, ..)) => {
+                let request = Request::new(*modbus_id, function, core::time::Duration::ZERO);
+                crate::frame::decode_after(&frame, &request)
+            }
+            None => crate::frame::decode_any(&frame),
+        };
+        if let Some((modbus_id, function, request_frame, requested)) = pending {
+            let response = match &decoded {
+                Ok(DecodedFrame::Response {
+                    modbus_id: responder,
+                    kind,
+                    response,
+                }) if *responder == modbus_id && *kind == function.kind() => Some(response.clone()),
+                Ok(DecodedFrame::Exception {
+                    modbus_id: responder,
+                    function_code,
+                    exception,
+                }) if *responder == modbus_id && *function_code == function.as_code() => {
+                    Some(Response::Exception(*exception))
+                }
+                _ => None,
+            };
+            if let Some(response) = response {
+                self.events
+                    .push_back(MonitorEvent::Transaction(MonitoredTransaction {
+                        modbus_id,
+                        function,
+                        request_frame,
+                        response_frame: frame,
+                        response,
+                        requested,
+                        responded: at,
+                    }));
+                return;
+            }
+            self.events.push_back(MonitorEvent::Unanswered {
+                modbus_id,
+                function,
+                frame: request_frame,
+                at: requested,
+            });
+        }
+        match decoded {
+            Ok(DecodedFrame::Request {
+                modbus_id: 0,
+                function,
+            }) => self.events.push_back(MonitorEvent::Unanswered {
+                modbus_id: 0,
+                function,
+                frame,
+                at,
+            }),
+            Ok(DecodedFrame::Request {
+                modbus_id,
+                function,
+            }) => self.pending = Some((modbus_id, function, frame, at)),
+            _ => self
+                .events
+                .push_back(MonitorEvent::Unrecognized { frame, at }),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+//! Firmware transfer in numbered, verified chunks.
+
+use crate::error::OtaError;
+use crate::{Exception, Master};
+
+
+/// Records addressable in one file by Write File Record.
+const RECORDS_PER_FILE: usize = 10_000;
+
+/// Largest Write File Record payload that fits one request, in words.
+const MAX_RECORD_WORDS: usize = (crate::limits::MAX_PDU - 9) / 2;
+
+/// Largest vendor function payload that fits one request, in bytes.
+const MAX_CUSTOM_CHUNK: usize = crate::limits::MAX_PDU - 3;
+
+
+/// ## OtaTransport
+///
+/// How [`Master::update_firmware`] carries the chunks of an image.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtaTransport {
+    /// Write File Record (`0x15`), with the image laid out as consecutive
+    /// records starting at record 0 of `file`, which must not be 0.
+    ///
+    /// A file holds 10 000 records of one word each; the image continues in
+    /// the following files. An odd image is padded with one `0xFF` byte. The
+    /// device confirms each chunk by echoing the request, as the function
+    /// specifies.
+    FileRecord { file: u16 },
+
+    /// A vendor-specific `function_code`, for devices with their own update
+    /// function.
+    ///
+    /// The request carries the sequence number of the chunk (big-endian
+    /// `u16`, from 0) followed by the chunk. The device confirms with the
+    /// sequence number followed by the CRC of the chunk, computed like the
+    /// frame CRC and sent low byte first.
+    Custom { function_code: u8 },
+}
+
+
+/// ## OtaOptions
+///
+/// Settings of a firmware transfer, see [`Master::update_firmware`].
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OtaOptions {
+    /// How the chunks are carried.
+    pub transport: OtaTransport,
+
+    /// Bytes per chunk, limited to what one request can carry.
+    pub chunk_size: usize,
+
+    /// Retransmissions of a chunk that timed out, failed verification, or
+    /// was answered with [`Exception::DeviceBusy`], before giving up.
+    pub retries: u32,
+
+    /// Response timeout of each chunk; flash writes can take a while.
+    pub timeout: core::time::Duration,
+
+    /// Pause before a chunk answered with [`Exception::DeviceBusy`] is sent
+    /// again, giving the device time to finish what it is busy with.
+    pub busy_delay: core::time::Duration,
+}
+
+impl OtaOptions {
+    /// Creates options for `transport` with 128-byte chunks, 3 retries, a
+    /// one second timeout and a one second pause after a busy device.
+    pub const fn new(transport: OtaTransport) -> Self {
+        Self {
+            transport,
+            chunk_size: 128,
+            retries: 3,
+            timeout: core::time::Duration::from_secs(1),
+            busy_delay: core::time::Duration::from_secs(1),
+        }
+    }
+
+    /// Splits an image of `len` bytes into the offsets and lengths of the
+    /// chunks [`Master::update_firmware`] sends, e.g. to show the size of a
+    /// transfer up front.
+    ///
+    /// File record chunks have an even length, the last one of an odd image
+    /// covering the padding byte, and never cross into the next file.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`ImageTooLarge`](OtaError::ImageTooLarge) when the chunks
+    /// cannot all be addressed, and [`InvalidFile`](OtaError::InvalidFile)
+    /// for file record transfers starting at file 0.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{OtaOptions, OtaTransport, error::OtaError};
+    ///
+    /// let options = OtaOptions::new(OtaTransport::FileRecord { file: 1 });
+    /// assert_eq!(options.chunks(129).unwrap(), [(0, 128), (128, 2)]);
+    ///
+    /// // A file ends after 10 000 records, i.e. 20 000 bytes.
+    /// let chunks = options.chunks(20_001).unwrap();
+    /// assert_eq!(chunks[chunks.len() - 2..], [(19_968, 32), (20_000, 2)]);
+    ///
+    /// let last = OtaOptions::new(OtaTransport::FileRecord { file: 0xFFFF });
+    /// assert!(matches!(
+    ///     last.chunks(20_001),
+    ///     Err(OtaError::ImageTooLarge { len: 20_001, max: 20_000 }),
+    /// ));
+    ///
+    /// let zero = OtaOptions::new(OtaTransport::FileRecord { file: 0 });
+    /// assert!(matches!(zero.chunks(2), Err(OtaError::InvalidFile { file: 0 })));
+    /// ```
+    ///
+    pub fn chunks(&self, len: usize) -> Result<Vec<(usize, usize)>, OtaError> {
+        let mut chunks = Vec::new();
+        match self.transport {
+            OtaTransport::FileRecord { file: 0 } => {
+                return Err(OtaError::InvalidFile { file: 0 });
+            }
+            OtaTransport::FileRecord { file } => {
+                let max = (0x1_0000 - file as usize) * RECORDS_PER_FILE * 2;
+                if len > max {
+                    return Err(OtaError::ImageTooLarge { len, max });
+                }
+                let words = (self.chunk_size / 2).clamp(1, MAX_RECORD_WORDS);
+                let mut record = 0;
+                while record * 2 < len {
+                    let n = words
+                        .min(RECORDS_PER_FILE - record % RECORDS_PER_FILE)
+                        .min(len.div_ceil(2) - record);
+                    chunks.push((record * 2, n * 2));
+                    record += n;
+                }
+            }
+            OtaTransport::Custom { .. } => {
+                let size = self.chunk_size.clamp(1, MAX_CUSTOM_CHUNK);
+                let max = size * 0x1_0000;
+                if len > max {
+                    return Err(OtaError::ImageTooLarge { len, max });
+                }
+                chunks.extend(
+                    (0..len)
+                        .step_by(size)
+                        .map(|offset| (offset, size.min(len - offset))),
+                );
+            }
+        }
+        Ok(chunks)
+    }
+}
+
+
+impl Master {
+    /// Transfers the firmware `image` to slave `modbus_id` in chunks as
+    /// described by `options`, calling `progress` with the bytes confirmed
+    /// so far and the image size after every chunk.
+    ///
+    /// Chunks are sent in order and each must be confirmed by the device
+    /// before the next one goes out. What the device does with the image
+    /// once complete, e.g. checking a signature and rebooting, is up to its
+    /// vendor protocol. The transfer cannot be broadcast, as broadcasts are
+    /// never confirmed.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`OtaError`] naming the sequence number of the chunk that
+    /// failed, or the error of [`OtaOptions::chunks`] before anything is
+    /// sent.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{Master, OtaOptions, OtaTransport};
+    ///
+    /// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 115_200)?;
+    /// let image = std::fs::read("firmware.bin")?;
+    /// let options = OtaOptions::new(OtaTransport::FileRecord { file: 1 });
+    /// master.update_firmware(0x01, &image, &options, |done, total| {
+    ///     println!("{:.0}%", done as f64 * 100.0 / total as f64);
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn update_firmware(
+        &mut self,
+        modbus_id: u8,
+        image: &[u8],
+        options: &OtaOptions,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<(), OtaError> {
+        if modbus_id == 0 {
+            return Err(OtaError::Transfer {
+                sequence: 0,
+                error: crate::error::Error::Request(
+                    crate::error::RequestPacketError::CannotBroadcast,
+                ),
+            });
+        }
+        let chunks = options.chunks(image.len())?;
+        let mut buf = Vec::new();
+        let mut payload = Vec::new();
+        for (sequence, (offset, len)) in chunks.into_iter().enumerate() {
+            let sequence = sequence as u32;
+            let chunk = &image[offset..(offset + len).min(image.len())];
+            let (function_code, confirmation) = match options.transport {
+                OtaTransport::FileRecord { file } => {
+                    let words = len / 2;
+                    let record = offset / 2;
+                    let file = file as usize + record / RECORDS_PER_FILE;
+                    payload.clear();
+                    payload.push((7 + len) as u8);
+                    payload.push(0x06);
+                    payload.extend_from_slice(&(file as u16).to_be_bytes());
+                    payload.extend_from_slice(&((record % RECORDS_PER_FILE) as u16).to_be_bytes());
+                    payload.extend_from_slice(&(words as u16).to_be_bytes());
+                    payload.extend_from_slice(chunk);
+                    payload.resize(payload.len() + len - chunk.len(), 0xFF);
+                    (0x15, payload.clone())
+                }
+                OtaTransport::Custom { function_code } => {
+                    payload.clear();
+                    payload.extend_from_slice(&(sequence as u16).to_be_bytes());
+                    payload.extend_from_slice(chunk);
+                    let mut confirmation = (sequence as u16).to_be_bytes().to_vec();
+                    confirmation.extend_from_slice(&crate::crc::generate(chunk).to_le_bytes());
+                    (function_code, confirmation)
+                }
+            };
+            let frame = crate::raw::frame(modbus_id, function_code, &payload, &mut buf);
+            self.send_chunk(frame, sequence, &confirmation, options)?;
+            progress(offset + chunk.len(), image.len());
+        }
+        Ok(())
+    }
+
+    /// Sends one chunk until the device confirms it with `confirmation` or
+    /// the retries of `options` are used up.
+    fn send_chunk(
+        &mut self,
+        frame: &[u8],
+        sequence: u32,
+        confirmation: &[u8],
+        options: &OtaOptions,
+    ) -> Result<(), OtaError> {
+        let expected_len = confirmation.len() + 4;
+        let mut attempt = 0;
+        loop {
+            let error = match self.send_raw(frame, expected_len, options.timeout) {
+                Ok(response) => match crate::raw::check(&response) {
+                    Ok((_, code, payload)) if code & 0x80 != 0 => {
+                        let exception = Exception::from_code(payload.first().copied().unwrap_or(0));
+                        let error = OtaError::Exception {
+                            sequence,
+                            exception,
+                        };
+                        if exception != Exception::DeviceBusy {
+                            return Err(error);
+                        }
+                        error
+                    }
+                    Ok((_, code, payload)) if code == frame[1] && payload == confirmation => {
+                        return Ok(());
+                    }
+                    _ => OtaError::Verification { sequence },
+                },
+                Err(error @ crate::error::Error::Request(_)) => {
+                    return Err(OtaError::Transfer { sequence, error });
+                }
+                Err(error) => OtaError::Transfer { sequence, error },
+            };
+            if attempt >= options.retries {
+                return Err(error);
+            }
+            attempt += 1;
+            #[cfg(feature = "log")]
+            log::debug!("retrying firmware chunk {sequence}: {error}");
+            if let OtaError::Exception { .. } = error {
+                std::thread::sleep(options.busy_delay);
+            }
+        }
+    }
+}
@@ -0,0 +1,380 @@
+//! Cyclic polling of Modbus RTU requests on top of a blocking [`Master`].
+
+use crate::{Function, HealthEvent, Master, Request, Response, Watchdog};
+
+
+/// Determines how the next execution of a polled request is scheduled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PollMode {
+    /// The next execution is due one interval after the previous one
+    /// finished, so the effective period grows with the transaction time.
+    #[default]
+    FixedDelay,
+
+    /// Executions are locked to a fixed phase: the next one is due exactly one
+    /// interval after the previous one was *scheduled*, regardless of how long
+    /// it took. Missed cycles are skipped and counted as overruns.
+    FixedPhase,
+}
+
+
+/// Start-time jitter measured for a polled request.
+///
+/// Jitter is the delay between the instant an execution was scheduled and
+/// the instant it actually started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct JitterStats {
+    samples: u64,
+    min: core::time::Duration,
+    max: core::time::Duration,
+    total: core::time::Duration,
+    overruns: u64,
+}
+
+impl JitterStats {
+    /// Returns the number of executions measured.
+    pub const fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Returns the smallest jitter observed.
+    pub const fn min(&self) -> core::time::Duration {
+        self.min
+    }
+
+    /// Returns the largest jitter observed.
+    pub const fn max(&self) -> core::time::Duration {
+        self.max
+    }
+
+    /// Returns the mean jitter, or zero when nothing was measured yet.
+    pub fn mean(&self) -> core::time::Duration {
+        match self.samples {
+            0 => core::time::Duration::ZERO,
+            n => self.total / n as u32,
+        }
+    }
+
+    /// Returns how many scheduled cycles were skipped because the previous
+    /// execution ran past them. Only [`PollMode::FixedPhase`] records overruns.
+    pub const fn overruns(&self) -> u64 {
+        self.overruns
+    }
+
+    /// Records the jitter of one execution.
+    fn record(&mut self, jitter: core::time::Duration) {
+        if self.samples == 0 || jitter < self.min {
+            self.min = jitter;
+        }
+        if jitter > self.max {
+            self.max = jitter;
+        }
+        self.total += jitter;
+        self.samples += 1;
+    }
+}
+
+
+/// A request polled cyclically by a [`Poller`].
+#[derive(Debug)]
+struct PollEntry {
+    modbus_id: u8,
+    function: Function,
+    timeout: core::time::Duration,
+    interval: core::time::Duration,
+    next_due: std::time::Instant,
+    jitter: JitterStats,
+}
+
+
+/// ## Poller
+///
+/// Executes a set of requests cyclically, each at its own interval, and
+/// measures how accurately the schedule is met.
+///
+/// The poller does not own the [`Master`]; each call to [`poll`](Self::poll)
+/// borrows it for one transaction, so the master stays available for
+/// ad-hoc requests between cycles.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use modbus_rtu::{Function, Master, PollMode, Poller};
+/// use std::time::Duration;
+///
+/// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut master = Master::new_rs485("/dev/ttyUSB0", 115_200)?;
+/// let mut poller = Poller::new(PollMode::FixedPhase);
+/// let id = poller.add(
+///     0x01,
+///     Function::ReadInputRegisters { starting_address: 0, quantity: 4 },
+///     Duration::from_millis(50),
+///     Duration::from_millis(100),
+/// );
+///
+/// loop {
+///     let (polled, result) = poller.poll(&mut master).unwrap();
+///     println!("#{polled}: {result:?}, jitter {:?}", poller.jitter(id).unwrap().max());
+/// }
+/// # }
+/// ```
+///
+#[derive(Debug, Default)]
+pub struct Poller {
+    mode: PollMode,
+    entries: Vec<PollEntry>,
+    watchdog: Option<Watchdog>,
+    events: Vec<HealthEvent>,
+    backoff_limit: Option<core::time::Duration>,
+    backoff: std::collections::HashMap<u8, u32>,
+}
+
+impl Poller {
+    /// Creates an empty poller using the given scheduling mode.
+    pub fn new(mode: PollMode) -> Self {
+        Self {
+            mode,
+            entries: Vec::new(),
+            watchdog: None,
+            events: Vec::new(),
+            backoff_limit: None,
+            backoff: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Enables liveness tracking of the polled slaves.
+    ///
+    /// Every polled result is fed to `watchdog`; the resulting
+    /// [`HealthEvent`]s are collected and can be taken with
+    /// [`take_events`](Self::take_events).
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{HealthEvent, PollMode, Poller, Watchdog};
+    /// use std::time::Duration;
+    ///
+    /// let mut poller = Poller::new(PollMode::FixedPhase);
+    /// poller.set_watchdog(Watchdog::new(3, Duration::from_secs(2)));
+    ///
+    /// loop {
+    ///     poller.poll(&mut master);
+    ///     for event in poller.take_events() {
+    ///         match event {
+    ///             HealthEvent::SlaveDown { modbus_id } => eprintln!("slave {modbus_id} down"),
+    ///             HealthEvent::SlaveRecovered { modbus_id, downtime } => {
+    ///                 eprintln!("slave {modbus_id} back after {downtime:?}")
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    pub fn set_watchdog(&mut self, watchdog: Watchdog) {
+        self.watchdog = Some(watchdog);
+    }
+
+    /// Returns the liveness tracker, if enabled.
+    pub fn watchdog(&self) -> Option<&Watchdog> {
+        self.watchdog.as_ref()
+    }
+
+    /// Takes the [`HealthEvent`]s collected since the previous call.
+    pub fn take_events(&mut self) -> Vec<HealthEvent> {
+        core::mem::take(&mut self.events)
+    }
+
+    /// Enables adaptive backoff of failing slaves, or disables it with
+    /// [`None`].
+    ///
+    /// Every transport failure (timeout, I/O error, corrupted frame) doubles
+    /// the polling interval of all requests to that slave, up to `limit`,
+    /// and defers every one of them by its stretched interval, so the slave
+    /// is tried once per interval rather than once per request; every
+    /// transaction the slave answers halves it again, until the configured
+    /// interval is restored. A slave that stopped responding thus
+    /// no longer occupies the bus with back-to-back timeouts, while exception
+    /// responses, which prove the slave is alive, do not slow it down.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{PollMode, Poller};
+    /// use std::time::Duration;
+    ///
+    /// let mut poller = Poller::new(PollMode::FixedDelay);
+    /// poller.set_backoff(Some(Duration::from_secs(30)));
+    ///
+    /// loop {
+    ///     poller.poll(&mut master);
+    ///     if poller.backoff(0x01) > 1 {
+    ///         eprintln!("slave 1 polled {}x less often", poller.backoff(0x01));
+    ///     }
+    /// }
+    /// ```
+    ///
+    pub fn set_backoff(&mut self, limit: Option<core::time::Duration>) {
+        self.backoff_limit = limit;
+        if limit.is_none() {
+            self.backoff.clear();
+        }
+    }
+
+    /// Returns the factor by which the polling intervals of `modbus_id` are
+    /// currently stretched; `1` when the slave is not backed off.
+    pub fn backoff(&self, modbus_id: u8) -> u32 {
+        1 << self.backoff.get(&modbus_id).copied().unwrap_or(0)
+    }
+
+    /// Returns the scheduling mode of this poller.
+    pub fn mode(&self) -> PollMode {
+        self.mode
+    }
+
+    /// Adds a request polled every `interval` and returns its identifier.
+    ///
+    /// The first execution is due immediately.
+    pub fn add(
+        &mut self,
+        modbus_id: u8,
+        function: Function,
+        timeout: core::time::Duration,
+        interval: core::time::Duration,
+    ) -> usize {
+        self.entries.push(PollEntry {
+            modbus_id,
+            function,
+            timeout,
+            interval,
+            next_due: std::time::Instant::now(),
+            jitter: JitterStats::default(),
+        });
+        self.entries.len() - 1
+    }
+
+    /// Returns the number of polled requests.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` when no request has been added.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the jitter statistics of the request with identifier `id`.
+    pub fn jitter(&self, id: usize) -> Option<&JitterStats> {
+        self.entries.get(id).map(|entry| &entry.jitter)
+    }
+
+    /// Returns the instant at which the next request becomes due.
+    pub fn next_due(&self) -> Option<std::time::Instant> {
+        self.entries.iter().map(|entry| entry.next_due).min()
+    }
+
+    /// Blocks until the next request is due, sends it through `master`, and
+    /// returns its identifier together with the outcome.
+    ///
+    /// Returns [`None`] when the poller is empty. When several requests are
+    /// due at once, the one added first is executed first.
+    pub fn poll(
+        &mut self,
+        master: &mut Master,
+    ) -> Option<(usize, Result<Response, crate::error::Error>)> {
+        let (id, due) = self
+            .entries
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, entry)| entry.next_due)
+            .map(|(id, entry)| (id, entry.next_due))?;
+        let now = std::time::Instant::now();
+        if due > now {
+            std::thread::sleep(due - now);
+        }
+
+        let started = std::time::Instant::now();
+        let entry = &mut self.entries[id];
+        let jitter = started.saturating_duration_since(due);
+        entry.jitter.record(jitter);
+        #[cfg(feature = "metrics")]
+        metrics::histogram!("modbus_rtu_poll_jitter_seconds", "slave" => entry.modbus_id.to_string())
+            .record(jitter.as_secs_f64());
+        let request = Request::new(entry.modbus_id, &entry.function, entry.timeout);
+        let result = master.send(&request);
+
+        let finished = std::time::Instant::now();
+        let interval = stretch(
+            &mut self.backoff,
+            self.backoff_limit,
+            entry.modbus_id,
+            entry.interval,
+            &result,
+        );
+        entry.next_due = match self.mode {
+            PollMode::FixedDelay => finished + interval,
+            PollMode::FixedPhase => {
+                let mut next = due + interval;
+                while next <= finished && !interval.is_zero() {
+                    next += interval;
+                    entry.jitter.overruns += 1;
+                    #[cfg(feature = "metrics")]
+                    metrics::counter!("modbus_rtu_poll_overruns_total", "slave" => entry.modbus_id.to_string())
+                        .increment(1);
+                }
+                next
+            }
+        };
+        let modbus_id = entry.modbus_id;
+        if let Some(limit) = self.backoff_limit
+            && let Err(error) = &result
+            && error.class() == crate::error::ErrorClass::Transport
+        {
+            // The backoff holds for the slave, not only the request that failed.
+            let factor = self.backoff(modbus_id);
+            for other in self
+                .entries
+                .iter_mut()
+                .filter(|other| other.modbus_id == modbus_id)
+            {
+                let interval = (other.interval * factor).min(limit.max(other.interval));
+                other.next_due = other.next_due.max(finished + interval);
+            }
+        }
+        if let Some(watchdog) = self.watchdog.as_mut()
+            && let Some(event) = watchdog.record(modbus_id, &result)
+        {
+            self.events.push(event);
+        }
+        Some((id, result))
+    }
+}
+
+
+/// Updates the backoff level of `modbus_id` with `result` and returns
+/// `interval` stretched accordingly, never beyond `limit` unless `interval`
+/// itself is longer.
+fn stretch(
+    backoff: &mut std::collections::HashMap<u8, u32>,
+    limit: Option<core::time::Duration>,
+    modbus_id: u8,
+    interval: core::time::Duration,
+    result: &Result<Response, crate::error::Error>,
+) -> core::time::Duration {
+    let Some(limit) = limit else {
+        return interval;
+    };
+    let level = backoff.entry(modbus_id).or_insert(0);
+    match result {
+        Err(error) if error.class() == crate::error::ErrorClass::Transport => {
+            // Stop doubling once the limit is reached, so recovery starts at once.
+            if *level < 16 && interval * (1 << *level) < limit {
+                *level += 1;
+            }
+        }
+        Err(crate::error::Error::Request(_)) => {}
+        _ => *level = level.saturating_sub(1),
+    }
+    (interval * (1 << *level)).min(limit.max(interval))
+}
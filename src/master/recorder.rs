@@ -0,0 +1,156 @@
+//! Transaction recording as JSON Lines.
+
+use crate::{Observer, Response, Transaction};
+
+
+/// ## JsonLinesRecorder
+///
+/// [`Observer`] that writes every completed transaction as one JSON object
+/// per line to a writer, for offline analysis with `jq`, pandas or a log
+/// pipeline.
+///
+/// Each line carries the transaction id, slave id, function code and name,
+/// the transmit and receive wall-clock times in microseconds since the Unix
+/// epoch, the duration in microseconds, both frames as hex strings, and a
+/// `result` object holding either the decoded response or the `error`
+/// description (see [`Transaction::to_json_line`]):
+///
+/// ```text
+/// {"id":1,"slave":1,"function":3,"function_name":"Read Holding Registers","tx_unix_us":1700000000000000,"rx_unix_us":1700000000012000,"duration_us":12400,"request":"010300000002C40B","response":"01030400010002...","result":{"values":[1,2]}}
+/// ```
+///
+/// Write errors do not interrupt the master; the most recent one is kept and
+/// can be taken with [`take_error`](Self::take_error).
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use modbus_rtu::{JsonLinesRecorder, Master};
+///
+/// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+/// let file = std::io::BufWriter::new(std::fs::File::create("bus.jsonl")?);
+/// master.add_observer(JsonLinesRecorder::new(file));
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Debug)]
+pub struct JsonLinesRecorder<W> {
+    writer: W,
+    error: Option<std::io::Error>,
+}
+
+impl<W: std::io::Write + Send> JsonLinesRecorder<W> {
+    /// Creates a recorder writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    /// Takes the most recent write error, if any.
+    pub fn take_error(&mut self) -> Option<std::io::Error> {
+        self.error.take()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: std::io::Write + Send> Observer for JsonLinesRecorder<W> {
+    fn on_transaction(&mut self, transaction: &Transaction) {
+        let line = transaction.to_json_line();
+        if let Err(error) = writeln!(self.writer, "{line}") {
+            self.error = Some(error);
+        }
+    }
+}
+
+
+impl Transaction {
+    /// Formats this transaction as the single JSON line written by
+    /// [`JsonLinesRecorder`], without the trailing newline.
+    pub fn to_json_line(&self) -> String {
+        let unix_us = |timestamp: Option<crate::Timestamp>| {
+            timestamp
+                .and_then(|t| t.system().duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or("null".to_string(), |d| d.as_micros().to_string())
+        };
+        let result = match self.outcome() {
+            Ok(response) => format!("{{{}}}", response_fields(response)),
+            Err(error) => format!("{{\"error\":{}}}", quote(error)),
+        };
+        format!(
+            "{{\"id\":{},\"slave\":{},\"function\":{},\"function_name\":{},\"tx_unix_us\":{},\"rx_unix_us\":{},\"duration_us\":{},\"request\":\"{}\",\"response\":\"{}\",\"result\":{}}}",
+            self.id(),
+            self.modbus_id(),
+            self.function().as_code(),
+            quote(&self.function().to_string()),
+            unix_us(self.transmitted()),
+            unix_us(self.received()),
+            self.duration().as_micros(),
+            hex(self.request_frame()),
+            hex(self.response_frame()),
+            result,
+        )
+    }
+}
+
+
+/// Formats the members of the JSON object describing `response`.
+fn response_fields(response: &Response) -> String {
+    let list = |items: Vec<String>| items.join(",");
+    match response {
+        Response::Status(states) => format!(
+            "\"states\":[{}]",
+            list(states.iter().map(bool::to_string).collect())
+        ),
+        Response::Value(values) => format!(
+            "\"values\":[{}]",
+            list(values.iter().map(u16::to_string).collect())
+        ),
+        Response::Success => "\"success\":true".to_string(),
+        Response::WriteEcho {
+            address,
+            value_or_quantity,
+        } => format!("\"address\":{address},\"value_or_quantity\":{value_or_quantity}"),
+        Response::Encapsulated { mei_type, data } => {
+            format!("\"mei_type\":{mei_type},\"data\":\"{}\"", hex(data))
+        }
+        Response::Exception(exception) => format!(
+            "\"exception\":{},\"exception_name\":{}",
+            exception.as_code(),
+            quote(&exception.to_string())
+        ),
+    }
+}
+
+
+/// Formats `bytes` as upper-case hex without separators.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02X}")).collect()
+}
+
+
+/// Formats `text` as a JSON string literal.
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
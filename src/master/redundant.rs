@@ -0,0 +1,165 @@
+//! Failover between two serial ports connected to the same bus.
+
+use crate::{Master, Request, Response};
+
+
+/// Port of a [`RedundantMaster`] that carried a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BusPath {
+    /// The preferred port.
+    Primary,
+
+    /// The standby port.
+    Secondary,
+}
+
+impl BusPath {
+    /// Returns the other path.
+    pub const fn other(&self) -> Self {
+        match self {
+            BusPath::Primary => BusPath::Secondary,
+            BusPath::Secondary => BusPath::Primary,
+        }
+    }
+}
+
+impl core::fmt::Display for BusPath {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                BusPath::Primary => "primary",
+                BusPath::Secondary => "secondary",
+            }
+        )
+    }
+}
+
+
+/// ## RedundantMaster
+///
+/// Drives a bus reachable through two serial ports, such as a redundant
+/// RS-485 ring, and fails over from the active port once it produced
+/// `threshold` transport failures (timeouts, I/O errors, corrupted frames)
+/// in a row.
+///
+/// The transaction that reaches the threshold is repeated on the other port,
+/// so failover is transparent to the caller; every result reports the
+/// [`BusPath`] that served it. Exception responses prove the path works and
+/// reset the count. When the standby port fails as well, traffic moves back
+/// to the primary one, and [`fail_back`](Self::fail_back) returns to it
+/// explicitly once it has been repaired.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use modbus_rtu::{BusPath, Function, Master, RedundantMaster, Request};
+///
+/// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+/// let primary = Master::new_rs485("/dev/ttyUSB0", 19_200)?;
+/// let secondary = Master::new_rs485("/dev/ttyUSB1", 19_200)?;
+/// let mut master = RedundantMaster::new(primary, secondary, 3);
+///
+/// let func = Function::ReadHoldingRegisters { starting_address: 0, quantity: 2 };
+/// let request = Request::new(0x01, &func, std::time::Duration::from_millis(100));
+/// let (path, result) = master.send(&request);
+/// if path == BusPath::Secondary {
+///     eprintln!("primary ring down, served by the secondary");
+/// }
+/// println!("{:?}", result?);
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Debug)]
+pub struct RedundantMaster {
+    primary: Master,
+    secondary: Master,
+    active: BusPath,
+    threshold: u32,
+    failures: u32,
+    failovers: u64,
+}
+
+impl RedundantMaster {
+    /// Creates a redundant master sending through `primary` until it fails
+    /// `threshold` transactions in a row.
+    ///
+    /// ---
+    /// # Panics
+    /// Panics when `threshold` is zero.
+    pub fn new(primary: Master, secondary: Master, threshold: u32) -> Self {
+        assert!(threshold > 0, "failover threshold must be at least 1");
+        Self {
+            primary,
+            secondary,
+            active: BusPath::Primary,
+            threshold,
+            failures: 0,
+            failovers: 0,
+        }
+    }
+
+    /// Returns the path currently used for new transactions.
+    pub fn active(&self) -> BusPath {
+        self.active
+    }
+
+    /// Returns the number of times traffic moved to the other path.
+    pub fn failovers(&self) -> u64 {
+        self.failovers
+    }
+
+    /// Returns the master driving `path`, e.g. to configure it or to inspect
+    /// its journal.
+    pub fn master(&mut self, path: BusPath) -> &mut Master {
+        match path {
+            BusPath::Primary => &mut self.primary,
+            BusPath::Secondary => &mut self.secondary,
+        }
+    }
+
+    /// Moves traffic back to the primary path and clears the failure count.
+    pub fn fail_back(&mut self) {
+        self.active = BusPath::Primary;
+        self.failures = 0;
+    }
+
+    /// Sends `req` through the active path, failing over when the threshold
+    /// is reached, and returns the path that produced the result.
+    pub fn send(&mut self, req: &Request) -> (BusPath, Result<Response, crate::error::Error>) {
+        let path = self.active;
+        let result = self.master(path).send(req);
+        match &result {
+            Err(error) if error.class() == crate::error::ErrorClass::Transport => {
+                self.failures += 1;
+                if self.failures < self.threshold {
+                    return (path, result);
+                }
+            }
+            Err(crate::error::Error::Request(_)) => return (path, result),
+            _ => {
+                self.failures = 0;
+                return (path, result);
+            }
+        }
+
+        self.active = path.other();
+        self.failures = 0;
+        self.failovers += 1;
+        #[cfg(feature = "log")]
+        log::warn!(
+            "{path} path failed {} transactions in a row, failing over",
+            self.threshold
+        );
+        let path = self.active;
+        let result = self.master(path).send(req);
+        if let Err(error) = &result
+            && error.class() == crate::error::ErrorClass::Transport
+        {
+            self.failures += 1;
+        }
+        (path, result)
+    }
+}
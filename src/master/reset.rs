@@ -0,0 +1,46 @@
+/// ## PortReset
+///
+/// Escalation step run by a [`Master`](crate::Master) after a configurable
+/// number of consecutive failed transactions; see
+/// [`Master::set_reset_after`](crate::Master::set_reset_after).
+///
+/// The hook receives the port after both of its buffers were cleared and may
+/// reconfigure it, replace it with a freshly opened one, or power-cycle the
+/// adapter through other means. Any closure with a matching signature is
+/// also a hook.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use modbus_rtu::Master;
+///
+/// # fn demo() -> serialport::Result<()> {
+/// let mut master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+/// master.set_reset_after(5);
+/// master.set_port_reset(|port: &mut Box<dyn serialport::SerialPort>| {
+///     *port = serialport::new("/dev/ttyUSB0", 9_600).open()?;
+///     Ok(())
+/// });
+/// # Ok(())
+/// # }
+/// ```
+///
+pub trait PortReset: Send {
+    /// Resets `port`, possibly replacing it.
+    fn reset(&mut self, port: &mut Box<dyn serialport::SerialPort>) -> std::io::Result<()>;
+}
+
+impl<F> PortReset for F
+where
+    F: FnMut(&mut Box<dyn serialport::SerialPort>) -> std::io::Result<()> + Send,
+{
+    fn reset(&mut self, port: &mut Box<dyn serialport::SerialPort>) -> std::io::Result<()> {
+        self(port)
+    }
+}
+
+impl core::fmt::Debug for dyn PortReset {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("PortReset")
+    }
+}
@@ -0,0 +1,51 @@
+//! Retransmission of requests whose exchange failed on the line.
+
+
+/// ## RetryPolicy
+///
+/// How often [`Master::send`](crate::Master::send) repeats a request whose
+/// exchange failed, set with
+/// [`Master::set_retry_policy`](crate::Master::set_retry_policy) or per
+/// request through [`Master::send_with_retries`](crate::Master::send_with_retries).
+///
+/// Failures are budgeted by their likely cause. A corrupted response (CRC
+/// mismatch, truncated or overlong frame, bytes arriving while the request
+/// was still being sent) usually means noise on a device that is alive, so
+/// it is worth repeating right away. A
+/// timeout more often means the device is gone, where waiting before a
+/// retry, or not retrying at all, keeps the bus free for the others.
+///
+/// The default policy never retries. Exception responses are never retried.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use modbus_rtu::{Master, RetryPolicy};
+/// use std::time::Duration;
+///
+/// # fn demo() -> serialport::Result<()> {
+/// let mut master = Master::new_rs485("/dev/ttyUSB0", 19_200)?;
+/// master.set_retry_policy(RetryPolicy {
+///     crc_retries: 3,
+///     timeout_retries: 1,
+///     timeout_delay: Duration::from_millis(500),
+///     ..RetryPolicy::default()
+/// });
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RetryPolicy {
+    /// Retries after a CRC mismatch or another framing error.
+    pub crc_retries: u32,
+
+    /// Pause before each retry after a framing error.
+    pub crc_delay: core::time::Duration,
+
+    /// Retries after the response timed out.
+    pub timeout_retries: u32,
+
+    /// Pause before each retry after a timeout.
+    pub timeout_delay: core::time::Duration,
+}
@@ -0,0 +1,493 @@
+//! Serial ports of remote device servers, reached over RFC 2217.
+
+use std::io::{Read, Write};
+
+use serialport::SerialPort;
+
+use crate::{CharFormat, Master};
+
+
+/// Telnet "interpret as command" escape.
+const IAC: u8 = 255;
+const DONT: u8 = 254;
+const DO: u8 = 253;
+const WONT: u8 = 252;
+const WILL: u8 = 251;
+const SB: u8 = 250;
+const SE: u8 = 240;
+
+/// Telnet options negotiated with the device server.
+const BINARY: u8 = 0;
+const SUPPRESS_GO_AHEAD: u8 = 3;
+const COM_PORT_OPTION: u8 = 44;
+
+/// COM-PORT-OPTION subcommands sent by the client; the server answers with
+/// the same subcommand plus 100.
+const SET_BAUDRATE: u8 = 1;
+const SET_DATASIZE: u8 = 2;
+const SET_PARITY: u8 = 3;
+const SET_STOPSIZE: u8 = 4;
+const SET_CONTROL: u8 = 5;
+const NOTIFY_MODEMSTATE: u8 = 7;
+const PURGE_DATA: u8 = 12;
+const SERVER_OFFSET: u8 = 100;
+
+
+/// ## Rfc2217Port
+///
+/// A [`serialport::SerialPort`] of a remote serial device server (Moxa
+/// NPort, Lantronix, `ser2net`, ...), reached over TCP with the Telnet
+/// COM-PORT-OPTION of RFC 2217.
+///
+/// Line settings such as the baud rate and character format are forwarded
+/// to the device server, so it drives its serial port as if it were local;
+/// no virtual COM driver is needed on the host. Usually opened through
+/// [`Master::open_rfc2217`].
+///
+/// Modem line states reflect the last notification of the device server;
+/// [`bytes_to_read`](serialport::SerialPort::bytes_to_read) counts the bytes
+/// received from the network, Telnet commands included.
+///
+#[derive(Debug)]
+pub struct Rfc2217Port {
+    stream: std::net::TcpStream,
+    address: String,
+    baud_rate: u32,
+    data_bits: serialport::DataBits,
+    parity: serialport::Parity,
+    stop_bits: serialport::StopBits,
+    flow_control: serialport::FlowControl,
+    timeout: core::time::Duration,
+
+    /// Decoder of the incoming Telnet stream, shared with `&self` methods
+    /// that discard input.
+    telnet: core::cell::RefCell<Telnet>,
+}
+
+impl Rfc2217Port {
+    /// Connects to the device server at `address`, written `host:port` with
+    /// an optional `rfc2217://` scheme, and configures its serial port for
+    /// `baud_rate` and `char_format` without flow control.
+    ///
+    /// The port timeout is set to the Modbus RTU silent interval (T3.5).
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`serialport::Error`] when the connection cannot be
+    /// established or the settings cannot be sent.
+    pub fn open(
+        address: &str,
+        baud_rate: impl Into<u32>,
+        char_format: CharFormat,
+    ) -> serialport::Result<Self> {
+        let baud_rate = baud_rate.into();
+        let address = address.strip_prefix("rfc2217://").unwrap_or(address);
+        let stream = std::net::TcpStream::connect(address)?;
+        stream.set_nodelay(true)?;
+        let (data_bits, parity, stop_bits) = super::sync::serial_settings(char_format);
+        let mut port = Self {
+            stream,
+            address: address.to_owned(),
+            baud_rate,
+            data_bits,
+            parity,
+            stop_bits,
+            flow_control: serialport::FlowControl::None,
+            timeout: core::time::Duration::ZERO,
+            telnet: core::cell::RefCell::new(Telnet::default()),
+        };
+        #[rustfmt::skip]
+        port.stream.write_all(&[
+            IAC, WILL, COM_PORT_OPTION,
+            IAC, WILL, BINARY, IAC, DO, BINARY,
+            IAC, WILL, SUPPRESS_GO_AHEAD, IAC, DO, SUPPRESS_GO_AHEAD,
+        ])?;
+        port.set_baud_rate(baud_rate)?;
+        port.set_data_bits(data_bits)?;
+        port.set_parity(parity)?;
+        port.set_stop_bits(stop_bits)?;
+        port.set_flow_control(serialport::FlowControl::None)?;
+        port.set_timeout(char_format.idle_time(baud_rate))?;
+        Ok(port)
+    }
+
+    /// Returns the `host:port` address of the device server.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Sends the COM-PORT-OPTION `subcommand` with `value` to the device
+    /// server.
+    fn command(&self, subcommand: u8, value: &[u8]) -> serialport::Result<()> {
+        let mut frame = vec![IAC, SB, COM_PORT_OPTION, subcommand];
+        escape(value, &mut frame);
+        frame.extend_from_slice(&[IAC, SE]);
+        (&self.stream).write_all(&frame)?;
+        Ok(())
+    }
+}
+
+impl Read for Rfc2217Port {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let mut raw = vec![0u8; buf.len()];
+        loop {
+            let received = match self.stream.read(&mut raw) {
+                Ok(0) => return Err(std::io::ErrorKind::ConnectionAborted.into()),
+                Ok(received) => received,
+                // Unix reports an expired socket timeout as `WouldBlock`,
+                // serial ports as `TimedOut`.
+                Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => {
+                    return Err(std::io::ErrorKind::TimedOut.into());
+                }
+                Err(error) => return Err(error),
+            };
+            let mut replies = Vec::new();
+            let len = self
+                .telnet
+                .get_mut()
+                .receive(&raw[..received], buf, &mut replies);
+            if !replies.is_empty() {
+                self.stream.write_all(&replies)?;
+            }
+            if len > 0 {
+                return Ok(len);
+            }
+        }
+    }
+}
+
+impl Write for Rfc2217Port {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut escaped = Vec::with_capacity(buf.len());
+        escape(buf, &mut escaped);
+        self.stream.write_all(&escaped)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+impl SerialPort for Rfc2217Port {
+    fn name(&self) -> Option<String> {
+        Some(format!("rfc2217://{}", self.address))
+    }
+
+    fn baud_rate(&self) -> serialport::Result<u32> {
+        Ok(self.baud_rate)
+    }
+
+    fn data_bits(&self) -> serialport::Result<serialport::DataBits> {
+        Ok(self.data_bits)
+    }
+
+    fn flow_control(&self) -> serialport::Result<serialport::FlowControl> {
+        Ok(self.flow_control)
+    }
+
+    fn parity(&self) -> serialport::Result<serialport::Parity> {
+        Ok(self.parity)
+    }
+
+    fn stop_bits(&self) -> serialport::Result<serialport::StopBits> {
+        Ok(self.stop_bits)
+    }
+
+    fn timeout(&self) -> core::time::Duration {
+        self.timeout
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> serialport::Result<()> {
+        self.command(SET_BAUDRATE, &baud_rate.to_be_bytes())?;
+        self.baud_rate = baud_rate;
+        Ok(())
+    }
+
+    fn set_data_bits(&mut self, data_bits: serialport::DataBits) -> serialport::Result<()> {
+        self.command(SET_DATASIZE, &[u8::from(data_bits)])?;
+        self.data_bits = data_bits;
+        Ok(())
+    }
+
+    fn set_flow_control(
+        &mut self,
+        flow_control: serialport::FlowControl,
+    ) -> serialport::Result<()> {
+        let value = match flow_control {
+            serialport::FlowControl::None => 1,
+            serialport::FlowControl::Software => 2,
+            serialport::FlowControl::Hardware => 3,
+        };
+        self.command(SET_CONTROL, &[value])?;
+        self.flow_control = flow_control;
+        Ok(())
+    }
+
+    fn set_parity(&mut self, parity: serialport::Parity) -> serialport::Result<()> {
+        let value = match parity {
+            serialport::Parity::None => 1,
+            serialport::Parity::Odd => 2,
+            serialport::Parity::Even => 3,
+        };
+        self.command(SET_PARITY, &[value])?;
+        self.parity = parity;
+        Ok(())
+    }
+
+    fn set_stop_bits(&mut self, stop_bits: serialport::StopBits) -> serialport::Result<()> {
+        self.command(SET_STOPSIZE, &[u8::from(stop_bits)])?;
+        self.stop_bits = stop_bits;
+        Ok(())
+    }
+
+    fn set_timeout(&mut self, timeout: core::time::Duration) -> serialport::Result<()> {
+        // Sockets reject a zero timeout.
+        self.stream
+            .set_read_timeout(Some(timeout.max(core::time::Duration::from_micros(1))))?;
+        self.timeout = timeout;
+        Ok(())
+    }
+
+    fn write_request_to_send(&mut self, level: bool) -> serialport::Result<()> {
+        self.command(SET_CONTROL, &[if level { 11 } else { 12 }])
+    }
+
+    fn write_data_terminal_ready(&mut self, level: bool) -> serialport::Result<()> {
+        self.command(SET_CONTROL, &[if level { 8 } else { 9 }])
+    }
+
+    fn read_clear_to_send(&mut self) -> serialport::Result<bool> {
+        Ok(self.telnet.get_mut().modem_state & 0x10 != 0)
+    }
+
+    fn read_data_set_ready(&mut self) -> serialport::Result<bool> {
+        Ok(self.telnet.get_mut().modem_state & 0x20 != 0)
+    }
+
+    fn read_ring_indicator(&mut self) -> serialport::Result<bool> {
+        Ok(self.telnet.get_mut().modem_state & 0x40 != 0)
+    }
+
+    fn read_carrier_detect(&mut self) -> serialport::Result<bool> {
+        Ok(self.telnet.get_mut().modem_state & 0x80 != 0)
+    }
+
+    fn bytes_to_read(&self) -> serialport::Result<u32> {
+        let mut probe = [0u8; 512];
+        self.stream.set_nonblocking(true)?;
+        let peeked = self.stream.peek(&mut probe);
+        self.stream.set_nonblocking(false)?;
+        match peeked {
+            Ok(len) => Ok(len as u32),
+            Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => Ok(0),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    fn bytes_to_write(&self) -> serialport::Result<u32> {
+        Ok(0)
+    }
+
+    fn clear(&self, buffer_to_clear: serialport::ClearBuffer) -> serialport::Result<()> {
+        let value = match buffer_to_clear {
+            serialport::ClearBuffer::Input => 1,
+            serialport::ClearBuffer::Output => 2,
+            serialport::ClearBuffer::All => 3,
+        };
+        self.command(PURGE_DATA, &[value])?;
+        if value == 2 {
+            return Ok(());
+        }
+        let mut raw = [0u8; 512];
+        let mut discarded = [0u8; 512];
+        let mut replies = Vec::new();
+        self.stream.set_nonblocking(true)?;
+        let drained = loop {
+            match (&self.stream).read(&mut raw) {
+                Ok(0) => break Ok(()),
+                Ok(received) => {
+                    self.telnet.borrow_mut().receive(
+                        &raw[..received],
+                        &mut discarded,
+                        &mut replies,
+                    );
+                }
+                Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => break Ok(()),
+                Err(error) => break Err(error),
+            }
+        };
+        self.stream.set_nonblocking(false)?;
+        drained?;
+        (&self.stream).write_all(&replies)?;
+        Ok(())
+    }
+
+    fn try_clone(&self) -> serialport::Result<Box<dyn SerialPort>> {
+        Ok(Box::new(Self {
+            stream: self.stream.try_clone()?,
+            address: self.address.clone(),
+            baud_rate: self.baud_rate,
+            data_bits: self.data_bits,
+            parity: self.parity,
+            stop_bits: self.stop_bits,
+            flow_control: self.flow_control,
+            timeout: self.timeout,
+            telnet: core::cell::RefCell::new(Telnet {
+                state: State::Data,
+                modem_state: self.telnet.borrow().modem_state,
+            }),
+        }))
+    }
+
+    fn set_break(&self) -> serialport::Result<()> {
+        self.command(SET_CONTROL, &[5])
+    }
+
+    fn clear_break(&self) -> serialport::Result<()> {
+        self.command(SET_CONTROL, &[6])
+    }
+}
+
+
+/// Position of the decoder within the Telnet stream.
+#[derive(Debug, Default)]
+enum State {
+    /// Serial data.
+    #[default]
+    Data,
+
+    /// After `IAC`.
+    Command,
+
+    /// After `IAC` and a negotiation command, awaiting its option.
+    Option(u8),
+
+    /// Within a subnegotiation, collecting its body.
+    Sub(Vec<u8>),
+
+    /// After `IAC` within a subnegotiation.
+    SubCommand(Vec<u8>),
+}
+
+
+/// Decoder of the Telnet stream received from the device server.
+#[derive(Debug, Default)]
+struct Telnet {
+    state: State,
+
+    /// Modem lines of the last NOTIFY-MODEMSTATE: CD, RI, DSR and CTS in
+    /// the upper four bits.
+    modem_state: u8,
+}
+
+impl Telnet {
+    /// Decodes `raw` into the serial data written to `data`, which must be
+    /// at least as long as `raw`, and returns its length. Answers to option
+    /// negotiations are appended to `replies`.
+    fn receive(&mut self, raw: &[u8], data: &mut [u8], replies: &mut Vec<u8>) -> usize {
+        let mut len = 0;
+        for &byte in raw {
+            self.state = match core::mem::take(&mut self.state) {
+                State::Data if byte == IAC => State::Command,
+                State::Data => {
+                    data[len] = byte;
+                    len += 1;
+                    State::Data
+                }
+                State::Command => match byte {
+                    IAC => {
+                        data[len] = IAC;
+                        len += 1;
+                        State::Data
+                    }
+                    WILL | WONT | DO | DONT => State::Option(byte),
+                    SB => State::Sub(Vec::new()),
+                    _ => State::Data,
+                },
+                State::Option(command) => {
+                    let supported = matches!(byte, BINARY | SUPPRESS_GO_AHEAD | COM_PORT_OPTION);
+                    match command {
+                        DO if !supported => replies.extend_from_slice(&[IAC, WONT, byte]),
+                        WILL if !supported => replies.extend_from_slice(&[IAC, DONT, byte]),
+                        _ => {}
+                    }
+                    State::Data
+                }
+                State::Sub(body) if byte == IAC => State::SubCommand(body),
+                State::Sub(mut body) => {
+                    body.push(byte);
+                    State::Sub(body)
+                }
+                State::SubCommand(body) if byte == SE => {
+                    if let [COM_PORT_OPTION, subcommand, modem_state] = body[..]
+                        && subcommand == NOTIFY_MODEMSTATE + SERVER_OFFSET
+                    {
+                        self.modem_state = modem_state;
+                    }
+                    State::Data
+                }
+                State::SubCommand(mut body) => {
+                    body.push(byte);
+                    State::Sub(body)
+                }
+            };
+        }
+        len
+    }
+}
+
+
+/// Appends `bytes` to `out`, doubling every `IAC` as Telnet requires.
+fn escape(bytes: &[u8], out: &mut Vec<u8>) {
+    for &byte in bytes {
+        out.push(byte);
+        if byte == IAC {
+            out.push(IAC);
+        }
+    }
+}
+
+
+impl Master {
+    /// Builds a master on the serial port of a remote device server, reached
+    /// over RFC 2217 at `address`, e.g. `"rfc2217://10.0.0.5:4001"` or just
+    /// `"10.0.0.5:4001"`.
+    ///
+    /// The device server is configured for `baud_rate` and `char_format`,
+    /// and the silent intervals follow them as with [`open`](Self::open).
+    /// Network latency adds to every response time, so timeouts usually
+    /// need to be longer than on a local port.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`serialport::Error`] when the device server cannot be
+    /// reached or configured.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{Baudrate, CharFormat, Master};
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let master = Master::open_rfc2217("rfc2217://10.0.0.5:4001", Baudrate::Baud19200, CharFormat::E81)?;
+    /// assert_eq!(master.baud_rate(), 19_200);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn open_rfc2217(
+        address: &str,
+        baud_rate: impl Into<u32>,
+        char_format: CharFormat,
+    ) -> serialport::Result<Self> {
+        let baud_rate = baud_rate.into();
+        let port = Rfc2217Port::open(address, baud_rate, char_format)?;
+        let mut master = Self::from_port(Box::new(port), baud_rate)?;
+        master.set_char_format(char_format)?;
+        Ok(master)
+    }
+}
@@ -0,0 +1,154 @@
+//! Connection self-test for configuration UIs.
+
+use crate::{CharFormat, Function, Master, Request, Response};
+
+
+/// A check performed by [`Master::self_test`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelfTestStep {
+    /// The CRC implementation reproduces a specification example.
+    Crc,
+
+    /// The slave answers a read of the probe register.
+    Read,
+}
+
+impl core::fmt::Display for SelfTestStep {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SelfTestStep::Crc => "CRC sanity",
+                SelfTestStep::Read => "register read",
+            }
+        )
+    }
+}
+
+
+/// Result of one [`SelfTestStep`]: its duration when it passed, or a
+/// description of the failure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfTestResult {
+    step: SelfTestStep,
+    outcome: Result<core::time::Duration, String>,
+}
+
+impl SelfTestResult {
+    /// Returns the step.
+    pub fn step(&self) -> SelfTestStep {
+        self.step
+    }
+
+    /// Returns how long the step took, or why it failed.
+    pub fn outcome(&self) -> Result<core::time::Duration, &str> {
+        self.outcome.as_ref().copied().map_err(String::as_str)
+    }
+
+    /// Returns `true` when the step passed.
+    pub fn passed(&self) -> bool {
+        self.outcome.is_ok()
+    }
+}
+
+
+/// Structured report returned by [`Master::self_test`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfTestReport {
+    modbus_id: u8,
+    results: Vec<SelfTestResult>,
+}
+
+impl SelfTestReport {
+    /// Returns the slave that was tested.
+    pub fn modbus_id(&self) -> u8 {
+        self.modbus_id
+    }
+
+    /// Returns the result of every step, in execution order.
+    pub fn results(&self) -> &[SelfTestResult] {
+        &self.results
+    }
+
+    /// Returns `true` when every step passed.
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(SelfTestResult::passed)
+    }
+}
+
+impl core::fmt::Display for SelfTestReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "self-test of slave {}:", self.modbus_id)?;
+        for result in &self.results {
+            match &result.outcome {
+                Ok(duration) => write!(f, "\n  {}: ok ({duration:?})", result.step)?,
+                Err(reason) => write!(f, "\n  {}: failed ({reason})", result.step)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+
+impl Master {
+    /// Runs a short scripted check of the connection to `modbus_id` and
+    /// reports the outcome of every step, e.g. for a "test connection"
+    /// button.
+    ///
+    /// The steps are a CRC sanity check against a specification example and
+    /// a read of holding register `register`, which the slave must answer
+    /// with a valid, non-exception response. Later steps run even when
+    /// earlier ones failed.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::Master;
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 19_200)?;
+    /// let report = master.self_test(0x01, 0x0000);
+    /// println!("{report}");
+    /// assert!(report.passed());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn self_test(&mut self, modbus_id: u8, register: u16) -> SelfTestReport {
+        let mut results = Vec::new();
+
+        let started = std::time::Instant::now();
+        let vector = crate::test_vectors::READ_HOLDING_REGISTERS;
+        let (body, crc) = vector.request.split_at(vector.request.len() - 2);
+        let outcome = if crate::crc::generate(body).to_le_bytes() == crc {
+            Ok(started.elapsed())
+        } else {
+            Err("checksum of the specification example does not match".to_string())
+        };
+        results.push(SelfTestResult {
+            step: SelfTestStep::Crc,
+            outcome,
+        });
+
+        let function = Function::ReadHoldingRegisters {
+            starting_address: register,
+            quantity: 1,
+        };
+        let request = Request::new(modbus_id, &function, core::time::Duration::ZERO);
+        let timeout = request.suggested_timeout(self.baud_rate(), CharFormat::N81, 100);
+        let request = Request::new(modbus_id, &function, timeout);
+        let started = std::time::Instant::now();
+        let outcome = match self.send(&request) {
+            Ok(Response::Value(_)) => Ok(started.elapsed()),
+            Ok(response) => Err(format!("unexpected response: {response}")),
+            Err(error) => Err(error.to_string()),
+        };
+        results.push(SelfTestResult {
+            step: SelfTestStep::Read,
+            outcome,
+        });
+
+        SelfTestReport { modbus_id, results }
+    }
+}
@@ -0,0 +1,318 @@
+//! Long-running stability runs checked against an error budget.
+
+use crate::error::{Error, ResponsePacketError};
+use crate::{Function, Master, Request};
+
+
+/// Error budget a [`Soak`] run must stay within.
+///
+/// The default budget tolerates no failure and no exception at all.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct SoakBudget {
+    /// Largest tolerated share of failed transactions, within `0.0..=1.0`.
+    pub max_failure_rate: f64,
+
+    /// Largest tolerated share of exception responses, within `0.0..=1.0`.
+    pub max_exception_rate: f64,
+
+    /// Longest tolerated run of failed transactions in a row.
+    pub max_consecutive_failures: u32,
+}
+
+
+/// ## Soak
+///
+/// Runs a mix of requests in a loop for a given duration, typically hours,
+/// and checks the outcome against a [`SoakBudget`].
+///
+/// The run works on any [`Master`], so the same mix can be pointed at a real
+/// bus before a firmware release, or at a simulated one through
+/// [`Master::from_port`] (e.g. a [`FaultyPort`](crate::FaultyPort)) to
+/// validate retry logic.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use modbus_rtu::{Function, Master, Soak, SoakBudget};
+/// use std::time::Duration;
+///
+/// # fn demo() -> serialport::Result<()> {
+/// let mut master = Master::new_rs485("/dev/ttyUSB0", 19_200)?;
+/// let budget = SoakBudget { max_failure_rate: 0.001, max_exception_rate: 0.0, max_consecutive_failures: 3 };
+/// let soak = Soak::new(Duration::from_secs(8 * 3600), budget)
+///     .request(0x01, Function::ReadHoldingRegisters { starting_address: 0, quantity: 125 }, Duration::from_millis(200))
+///     .request(0x02, Function::WriteSingleCoil { address: 0, value: true }, Duration::from_millis(100));
+///
+/// let report = soak.run(&mut master);
+/// std::fs::write("soak.json", report.to_json())?;
+/// assert!(report.passed(), "{report}");
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Soak {
+    duration: core::time::Duration,
+    budget: SoakBudget,
+    mix: Vec<(u8, Function, core::time::Duration)>,
+}
+
+impl Soak {
+    /// Creates a run lasting `duration`, checked against `budget`, without
+    /// requests yet.
+    pub fn new(duration: core::time::Duration, budget: SoakBudget) -> Self {
+        Self {
+            duration,
+            budget,
+            mix: Vec::new(),
+        }
+    }
+
+    /// Adds `function` for `modbus_id`, answered within `timeout`, to the
+    /// mix. Requests are sent in the order they were added, over and over.
+    pub fn request(
+        mut self,
+        modbus_id: u8,
+        function: Function,
+        timeout: core::time::Duration,
+    ) -> Self {
+        self.mix.push((modbus_id, function, timeout));
+        self
+    }
+
+    /// Runs the mix on `master` until the duration elapses.
+    pub fn run(&self, master: &mut Master) -> SoakReport {
+        self.run_with(master, |_| true)
+    }
+
+    /// Runs the mix on `master` until the duration elapses or `progress`,
+    /// called with the report so far after every transaction, returns
+    /// `false`.
+    pub fn run_with(
+        &self,
+        master: &mut Master,
+        mut progress: impl FnMut(&SoakReport) -> bool,
+    ) -> SoakReport {
+        let mut report = SoakReport::new(self.budget);
+        let started = std::time::Instant::now();
+        for (modbus_id, function, timeout) in self.mix.iter().cycle() {
+            if started.elapsed() >= self.duration {
+                break;
+            }
+            let request = Request::new(*modbus_id, function, *timeout);
+            report.record(master.send(&request).map(|response| response.is_success()));
+            report.elapsed = started.elapsed();
+            if !progress(&report) {
+                break;
+            }
+        }
+        report
+    }
+}
+
+
+/// ## SoakReport
+///
+/// Outcome counts of a [`Soak`] run and their verdict against its budget.
+///
+/// [`to_json`](Self::to_json) renders the report as a single JSON object for
+/// CI pipelines and release records; [`Display`](core::fmt::Display) renders
+/// it for humans.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoakReport {
+    budget: SoakBudget,
+    elapsed: core::time::Duration,
+    transactions: u64,
+    failures: u64,
+    timeouts: u64,
+    crc_errors: u64,
+    exceptions: u64,
+    consecutive_failures: u32,
+    max_consecutive_failures: u32,
+}
+
+impl SoakReport {
+    /// Creates an empty report checked against `budget`.
+    fn new(budget: SoakBudget) -> Self {
+        Self {
+            budget,
+            elapsed: core::time::Duration::ZERO,
+            transactions: 0,
+            failures: 0,
+            timeouts: 0,
+            crc_errors: 0,
+            exceptions: 0,
+            consecutive_failures: 0,
+            max_consecutive_failures: 0,
+        }
+    }
+
+    /// Counts one transaction: `Ok(true)` for a success, `Ok(false)` for an
+    /// exception response.
+    fn record(&mut self, outcome: Result<bool, Error>) {
+        self.transactions += 1;
+        match outcome {
+            Ok(true) => self.consecutive_failures = 0,
+            Ok(false) | Err(Error::Exception(..)) => {
+                self.exceptions += 1;
+                self.consecutive_failures = 0;
+            }
+            Err(error) => {
+                self.failures += 1;
+                match error {
+                    Error::IO(error) if error.kind() == std::io::ErrorKind::TimedOut => {
+                        self.timeouts += 1
+                    }
+                    Error::Response(ResponsePacketError::CRCMismatch { .. }) => {
+                        self.crc_errors += 1
+                    }
+                    _ => {}
+                }
+                self.consecutive_failures += 1;
+                self.max_consecutive_failures =
+                    self.max_consecutive_failures.max(self.consecutive_failures);
+            }
+        }
+    }
+
+    /// Returns the budget the run is checked against.
+    pub fn budget(&self) -> SoakBudget {
+        self.budget
+    }
+
+    /// Returns how long the run lasted.
+    pub fn elapsed(&self) -> core::time::Duration {
+        self.elapsed
+    }
+
+    /// Returns the number of transactions sent.
+    pub fn transactions(&self) -> u64 {
+        self.transactions
+    }
+
+    /// Returns the number of transactions that failed, i.e. ended without a
+    /// response from the device.
+    pub fn failures(&self) -> u64 {
+        self.failures
+    }
+
+    /// Returns the number of failures due to a timeout.
+    pub fn timeouts(&self) -> u64 {
+        self.timeouts
+    }
+
+    /// Returns the number of failures due to a CRC mismatch.
+    pub fn crc_errors(&self) -> u64 {
+        self.crc_errors
+    }
+
+    /// Returns the number of exception responses, `Acknowledge` excluded.
+    pub fn exceptions(&self) -> u64 {
+        self.exceptions
+    }
+
+    /// Returns the longest run of failed transactions in a row.
+    pub fn max_consecutive_failures(&self) -> u32 {
+        self.max_consecutive_failures
+    }
+
+    /// Returns the share of failed transactions, or `0.0` before the first
+    /// transaction.
+    pub fn failure_rate(&self) -> f64 {
+        Self::rate(self.failures, self.transactions)
+    }
+
+    /// Returns the share of exception responses, or `0.0` before the first
+    /// transaction.
+    pub fn exception_rate(&self) -> f64 {
+        Self::rate(self.exceptions, self.transactions)
+    }
+
+    /// Returns a description of every budget limit the run exceeded.
+    pub fn violations(&self) -> Vec<String> {
+        let mut violations = Vec::new();
+        if self.failure_rate() > self.budget.max_failure_rate {
+            violations.push(format!(
+                "failure rate {:.4}% exceeds {:.4}%",
+                self.failure_rate() * 100.0,
+                self.budget.max_failure_rate * 100.0
+            ));
+        }
+        if self.exception_rate() > self.budget.max_exception_rate {
+            violations.push(format!(
+                "exception rate {:.4}% exceeds {:.4}%",
+                self.exception_rate() * 100.0,
+                self.budget.max_exception_rate * 100.0
+            ));
+        }
+        if self.max_consecutive_failures > self.budget.max_consecutive_failures {
+            violations.push(format!(
+                "{} consecutive failures exceed {}",
+                self.max_consecutive_failures, self.budget.max_consecutive_failures
+            ));
+        }
+        violations
+    }
+
+    /// Returns `true` when the run stayed within its budget.
+    pub fn passed(&self) -> bool {
+        self.violations().is_empty()
+    }
+
+    /// Renders the report as a single JSON object.
+    pub fn to_json(&self) -> String {
+        let violations: Vec<String> = self
+            .violations()
+            .iter()
+            .map(|violation| format!("\"{violation}\""))
+            .collect();
+        format!(
+            "{{\"elapsed_s\":{},\"transactions\":{},\"failures\":{},\"timeouts\":{},\"crc_errors\":{},\"exceptions\":{},\"max_consecutive_failures\":{},\"failure_rate\":{},\"exception_rate\":{},\"budget\":{{\"max_failure_rate\":{},\"max_exception_rate\":{},\"max_consecutive_failures\":{}}},\"passed\":{},\"violations\":[{}]}}",
+            self.elapsed.as_secs_f64(),
+            self.transactions,
+            self.failures,
+            self.timeouts,
+            self.crc_errors,
+            self.exceptions,
+            self.max_consecutive_failures,
+            self.failure_rate(),
+            self.exception_rate(),
+            self.budget.max_failure_rate,
+            self.budget.max_exception_rate,
+            self.budget.max_consecutive_failures,
+            self.passed(),
+            violations.join(",")
+        )
+    }
+
+    /// Returns `count` as a share of `total`, or `0.0` when `total` is zero.
+    fn rate(count: u64, total: u64) -> f64 {
+        if total == 0 {
+            0.0
+        } else {
+            count as f64 / total as f64
+        }
+    }
+}
+
+impl core::fmt::Display for SoakReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "soak {} after {:?}: {} transactions, {} failures ({} timeouts, {} CRC errors), {} exceptions, at most {} consecutive failures",
+            if self.passed() { "passed" } else { "failed" },
+            self.elapsed,
+            self.transactions,
+            self.failures,
+            self.timeouts,
+            self.crc_errors,
+            self.exceptions,
+            self.max_consecutive_failures
+        )?;
+        for violation in self.violations() {
+            write!(f, "\n  - {violation}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+use crate::error::ResponsePacketError;
+use crate::{BitOrder, Function, FunctionKind, Response};
+
+
+/// ## StaleInput
+///
+/// What a [`Master`](crate::Master) does with bytes already waiting in the
+/// receive buffer when it is about to transmit a request; see
+/// [`Master::set_stale_input`](crate::Master::set_stale_input).
+///
+/// Such bytes are usually the tail of a response that arrived after its
+/// request timed out. Left in place, they end up in front of the next
+/// response.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StaleInput {
+    /// Leave the bytes in place. Leading bytes that do not start the expected
+    /// response are skipped when it is decoded.
+    #[default]
+    Keep,
+
+    /// Discard the bytes without looking at them.
+    Clear,
+
+    /// Read the bytes, log them with the `log` feature, and discard them.
+    Drain,
+
+    /// Read the bytes, decode them as the late response to the previous
+    /// request, report them to the observers as a [`LateResponse`], and
+    /// discard them.
+    Attribute,
+}
+
+
+/// The last request transmitted, kept to attribute late responses to it.
+#[derive(Debug, Clone)]
+pub(crate) struct PreviousRequest {
+    pub(crate) transaction_id: u64,
+    pub(crate) modbus_id: u8,
+    pub(crate) function: Function,
+    pub(crate) bit_order: BitOrder,
+    pub(crate) transmitted: std::time::Instant,
+}
+
+
+/// ## LateResponse
+///
+/// Bytes found in the receive buffer before a request was transmitted,
+/// attributed to the previous request under [`StaleInput::Attribute`] and
+/// reported through [`Observer::on_late_response`](crate::Observer::on_late_response).
+///
+/// A late response that decodes correctly means the device did answer, only
+/// after the timeout expired: the timeout of that request is too short.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use modbus_rtu::{LateResponse, Master, Observer, StaleInput};
+///
+/// struct TimeoutTuner;
+///
+/// impl Observer for TimeoutTuner {
+///     fn on_late_response(&mut self, late: &LateResponse) {
+///         if late.response().is_ok() {
+///             println!(
+///                 "transaction #{} to slave {} answered within {:?}",
+///                 late.transaction_id(),
+///                 late.modbus_id(),
+///                 late.observed_after()
+///             );
+///         }
+///     }
+/// }
+///
+/// # fn demo() -> serialport::Result<()> {
+/// let mut master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+/// master.set_stale_input(StaleInput::Attribute);
+/// master.add_observer(TimeoutTuner);
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LateResponse {
+    pub(crate) transaction_id: u64,
+    pub(crate) modbus_id: u8,
+    pub(crate) function: FunctionKind,
+    pub(crate) frame: Box<[u8]>,
+    pub(crate) response: Result<Response, ResponsePacketError>,
+    pub(crate) observed_after: core::time::Duration,
+}
+
+impl LateResponse {
+    /// Returns the id of the transaction the bytes are attributed to.
+    pub fn transaction_id(&self) -> u64 {
+        self.transaction_id
+    }
+
+    /// Returns the slave id the previous request was addressed to.
+    pub fn modbus_id(&self) -> u8 {
+        self.modbus_id
+    }
+
+    /// Returns the function of the previous request.
+    pub fn function(&self) -> FunctionKind {
+        self.function
+    }
+
+    /// Returns the stale bytes as received.
+    pub fn frame(&self) -> &[u8] {
+        &self.frame
+    }
+
+    /// Returns the bytes decoded as the response to the previous request, or
+    /// why they are not a valid response to it.
+    pub fn response(&self) -> Result<&Response, ResponsePacketError> {
+        self.response.as_ref().map_err(|error| *error)
+    }
+
+    /// Returns how long after the previous request was transmitted the bytes
+    /// were found. The response arrived at some point between its timeout
+    /// and this bound.
+    pub fn observed_after(&self) -> core::time::Duration {
+        self.observed_after
+    }
+}
@@ -0,0 +1,210 @@
+//! Traffic counters reported by [`Master::statistics`](crate::Master::statistics).
+
+
+/// ## Statistics
+///
+/// Counters accumulated by a [`Master`](crate::Master) since it was created
+/// or since [`reset_statistics`](crate::Master::reset_statistics), returned
+/// by [`Master::statistics`](crate::Master::statistics).
+///
+/// Besides the retry counters, the response latency of every slave is
+/// tracked in a [`LatencyHistogram`], so that one device slowing down on a
+/// busy bus stands out. The histograms of all 247 slave ids live in fixed
+/// storage, so recording never allocates.
+///
+/// With the `serde` feature enabled the snapshot implements `Serialize`.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use modbus_rtu::Master;
+///
+/// # fn demo() -> serialport::Result<()> {
+/// let master = Master::new_rs485("/dev/ttyUSB0", 19_200)?;
+/// // ... poll the bus ...
+/// for (modbus_id, latency) in master.statistics().latencies() {
+///     println!("slave {modbus_id}: 95% within {:?}", latency.quantile(0.95));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Statistics {
+    pub(crate) transactions: u64,
+    pub(crate) crc_retries: u64,
+    pub(crate) timeout_retries: u64,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_latencies"))]
+    latencies: [Option<LatencyHistogram>; MAX_SLAVE_ID as usize],
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        Self {
+            transactions: 0,
+            crc_retries: 0,
+            timeout_retries: 0,
+            latencies: [const { None }; MAX_SLAVE_ID as usize],
+        }
+    }
+}
+
+impl Statistics {
+    /// Returns the number of requests sent through
+    /// [`send`](crate::Master::send), retries not counted.
+    pub fn transactions(&self) -> u64 {
+        self.transactions
+    }
+
+    /// Returns the number of retries after a CRC mismatch or another framing
+    /// error, see [`RetryPolicy::crc_retries`](crate::RetryPolicy::crc_retries).
+    pub fn crc_retries(&self) -> u64 {
+        self.crc_retries
+    }
+
+    /// Returns the number of retries after a timeout, see
+    /// [`RetryPolicy::timeout_retries`](crate::RetryPolicy::timeout_retries).
+    pub fn timeout_retries(&self) -> u64 {
+        self.timeout_retries
+    }
+
+    /// Returns the response latency histogram of `modbus_id`, or [`None`]
+    /// when the slave never answered.
+    pub fn latency(&self, modbus_id: u8) -> Option<&LatencyHistogram> {
+        self.latencies
+            .get(usize::from(modbus_id).checked_sub(1)?)?
+            .as_ref()
+    }
+
+    /// Returns the response latency histogram of every slave that answered,
+    /// ordered by id.
+    pub fn latencies(&self) -> impl Iterator<Item = (u8, &LatencyHistogram)> {
+        (1..=MAX_SLAVE_ID)
+            .zip(&self.latencies)
+            .filter_map(|(modbus_id, histogram)| {
+                histogram.as_ref().map(|histogram| (modbus_id, histogram))
+            })
+    }
+
+    /// Counts a response of `modbus_id` that took `latency`. Ids outside
+    /// the unicast range `1..=247` are not tracked.
+    pub(crate) fn record_latency(&mut self, modbus_id: u8, latency: core::time::Duration) {
+        if let Some(slot) = usize::from(modbus_id)
+            .checked_sub(1)
+            .and_then(|index| self.latencies.get_mut(index))
+        {
+            slot.get_or_insert_default().record(latency);
+        }
+    }
+}
+
+
+/// Serializes the latency histograms as a map from slave id to histogram,
+/// leaving out the slaves that never answered.
+#[cfg(feature = "serde")]
+fn serialize_latencies<S: serde::Serializer>(
+    latencies: &[Option<LatencyHistogram>; MAX_SLAVE_ID as usize],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map((1..=MAX_SLAVE_ID).zip(latencies).filter_map(
+        |(modbus_id, histogram)| histogram.as_ref().map(|histogram| (modbus_id, histogram)),
+    ))
+}
+
+
+/// ## LatencyHistogram
+///
+/// Response latencies of one slave, measured from the end of the request to
+/// the last byte of the response, counted in fixed buckets.
+///
+/// The buckets end at the [`BOUNDS`](Self::BOUNDS), from 1 ms to 5 s in a
+/// 1-2-5 series, followed by one bucket for everything slower. Recording a
+/// latency only increments a counter.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::LatencyHistogram;
+/// use std::time::Duration;
+///
+/// let histogram = LatencyHistogram::default();
+/// assert_eq!(histogram.count(), 0);
+/// assert_eq!(histogram.quantile(0.5), None);
+/// assert_eq!(histogram.buckets().count(), LatencyHistogram::BOUNDS.len() + 1);
+/// ```
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LatencyHistogram {
+    counts: [u64; LATENCY_BUCKETS],
+}
+
+impl LatencyHistogram {
+    /// Upper bounds of the buckets, each inclusive; a last bucket counts the
+    /// latencies above the highest bound.
+    pub const BOUNDS: [core::time::Duration; LATENCY_BUCKETS - 1] = [
+        core::time::Duration::from_millis(1),
+        core::time::Duration::from_millis(2),
+        core::time::Duration::from_millis(5),
+        core::time::Duration::from_millis(10),
+        core::time::Duration::from_millis(20),
+        core::time::Duration::from_millis(50),
+        core::time::Duration::from_millis(100),
+        core::time::Duration::from_millis(200),
+        core::time::Duration::from_millis(500),
+        core::time::Duration::from_secs(1),
+        core::time::Duration::from_secs(2),
+        core::time::Duration::from_secs(5),
+    ];
+
+    /// Returns the number of latencies recorded.
+    pub fn count(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the buckets in ascending order, each as its upper bound
+    /// ([`None`] for the last one) and the number of latencies it counts.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<core::time::Duration>, u64)> + '_ {
+        Self::BOUNDS
+            .iter()
+            .copied()
+            .map(Some)
+            .chain([None])
+            .zip(self.counts.iter().copied())
+    }
+
+    /// Returns the upper bound of the bucket holding the `q` quantile, e.g.
+    /// `0.95`, so that at least that share of the latencies lies within it.
+    ///
+    /// Returns [`None`] when nothing was recorded, and [`Duration::MAX`](core::time::Duration::MAX)
+    /// when the quantile falls into the last bucket.
+    pub fn quantile(&self, q: f64) -> Option<core::time::Duration> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+        let target = ((q.clamp(0.0, 1.0) * count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bound, n) in self.buckets() {
+            seen += n;
+            if seen >= target {
+                return Some(bound.unwrap_or(core::time::Duration::MAX));
+            }
+        }
+        Some(core::time::Duration::MAX)
+    }
+
+    /// Counts one latency.
+    pub(crate) fn record(&mut self, latency: core::time::Duration) {
+        let bucket = Self::BOUNDS.partition_point(|bound| *bound < latency);
+        self.counts[bucket] += 1;
+    }
+}
+
+
+/// Number of buckets of a [`LatencyHistogram`].
+const LATENCY_BUCKETS: usize = 13;
+
+/// Highest unicast slave id, and the number of latency histograms kept.
+const MAX_SLAVE_ID: u8 = 247;
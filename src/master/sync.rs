@@ -1,6 +1,9 @@
 //! Blocking Modbus RTU master backed by the `serialport` crate.
 
-use crate::{Request, Response};
+use crate::{
+    CharFormat, Observer, PortReset, QuirkHook, Request, Response, RetryPolicy, StaleInput,
+    Statistics, Timestamp, Transaction,
+};
 
 
 /// Blocking Modbus RTU master that enforces Modbus idle timing rules between frames.
@@ -12,8 +15,93 @@ pub struct Master {
     /// Timestamp of the last transmitted frame, used to honor the 3.5-char gap.
     last_tx: std::time::Instant,
 
+    /// Timestamp of the last received byte.
+    last_rx: std::time::Instant,
+
     /// Cached baud rate so higher-level code can inspect the active speed.
     baud_rate: u32,
+
+    /// Character framing on the line, which timing calculations depend on.
+    char_format: CharFormat,
+
+    /// Whether responses are subjected to additional strict validation.
+    strict: bool,
+
+    /// Whether defects that leave the payload intact are tolerated.
+    permissive: bool,
+
+    /// Slaves allowed to send their CRC high byte first, with the number of
+    /// frames accepted that way.
+    swapped_crc: std::collections::HashMap<u8, u64>,
+
+    /// Ids bridges answer with instead of the requested slave id, with the
+    /// number of responses remapped that way.
+    responder_alias: std::collections::HashMap<u8, (u8, u64)>,
+
+    /// Response workarounds registered per slave, applied in insertion order.
+    quirks: std::collections::HashMap<u8, Vec<Box<dyn QuirkHook>>>,
+
+    /// Id assigned to the most recent transaction.
+    transaction_id: u64,
+
+    /// Bounded history of recent transactions, oldest first.
+    journal: std::collections::VecDeque<Transaction>,
+
+    /// Maximum number of transactions kept in the journal.
+    journal_capacity: usize,
+
+    /// Observers notified about every transaction.
+    observers: Vec<Box<dyn Observer>>,
+
+    /// Largest share of line time this master may occupy, if capped.
+    utilization_cap: Option<f64>,
+
+    /// Earliest moment the next transaction may start under the utilization cap.
+    quiet_until: std::time::Instant,
+
+    /// Minimum spacing between requests per slave, with the moment the last
+    /// transaction with that slave completed.
+    spacing: std::collections::HashMap<u8, (core::time::Duration, Option<std::time::Instant>)>,
+
+    /// Number of transactions that failed in a row, reset by any answer.
+    consecutive_failures: u32,
+
+    /// Consecutive failures that trigger a port reset; `0` disables resets.
+    reset_after: u32,
+
+    /// Escalation run after the buffers are cleared during a port reset.
+    port_reset: Option<Box<dyn PortReset>>,
+
+    /// Recent per-slave outcomes summarized by [`health`](Self::health).
+    health: super::health::HealthTracker,
+
+    /// Exception counts checked against the limits set with
+    /// [`set_exception_alarm`](Self::set_exception_alarm).
+    exception_alarms: super::alarm::ExceptionAlarms,
+
+    /// Handling of bytes already received when a request is transmitted.
+    stale_input: StaleInput,
+
+    /// Last request transmitted, kept under [`StaleInput::Attribute`].
+    previous_request: Option<super::stale::PreviousRequest>,
+
+    /// Whether transmitted frames are compared with their loopback echo.
+    collision_detection: bool,
+
+    /// Retransmissions attempted after a collision before giving up.
+    collision_retries: u32,
+
+    /// Number of collisions detected so far.
+    collisions: u64,
+
+    /// Source of the randomized backoff after collisions.
+    backoff_rng: super::fault::XorShift,
+
+    /// Retries applied by [`send`](Self::send) to failed exchanges.
+    retry_policy: RetryPolicy,
+
+    /// Counters reported by [`statistics`](Self::statistics).
+    statistics: Statistics,
 }
 
 
@@ -22,27 +110,130 @@ impl Master {
     ///
     /// The port timeout is pinned to the Modbus RTU silent interval (T3.5) for
     /// the supplied baud rate so that the reader can detect frame boundaries.
+    /// The rate is either a [`Baudrate`](crate::Baudrate) or, for custom
+    /// rates, a bare `u32`.
     ///
     /// ---
     /// # Examples
     /// ```ignore
-    /// use modbus_rtu::Master;
+    /// use modbus_rtu::{Baudrate, Master};
     ///
     /// # fn demo() -> serialport::Result<()> {
-    /// let master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+    /// let master = Master::new_rs485("/dev/ttyUSB0", Baudrate::Baud9600)?;
     /// assert_eq!(master.baud_rate(), 9_600);
+    ///
+    /// let custom = Master::new_rs485("/dev/ttyUSB1", 250_000)?;
+    /// assert_eq!(custom.baud_rate(), 250_000);
     /// # Ok(())
     /// # }
     /// ```
     /// 
-    pub fn new_rs485(path: &str, baud_rate: u32) -> serialport::Result<Self> {
+    pub fn new_rs485(path: &str, baud_rate: impl Into<u32>) -> serialport::Result<Self> {
+        Self::open(path, baud_rate, CharFormat::N81)
+    }
+
+    /// Builds a master on `path` using `char_format`, e.g. the 8E1 framing the
+    /// Modbus RTU specification defaults to.
+    ///
+    /// The silent intervals and the minimum timeout follow the number of bits
+    /// per character of `char_format`.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{Baudrate, CharFormat, Master};
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let master = Master::open("/dev/ttyUSB0", Baudrate::Baud19200, CharFormat::E81)?;
+    /// assert_eq!(master.char_format(), CharFormat::E81);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn open(
+        path: &str,
+        baud_rate: impl Into<u32>,
+        char_format: CharFormat,
+    ) -> serialport::Result<Self> {
+        let baud_rate = baud_rate.into();
+        let (data_bits, parity, stop_bits) = serial_settings(char_format);
         let port = serialport::new(path, baud_rate)
-            .data_bits(serialport::DataBits::Eight)
-            .parity(serialport::Parity::None)
-            .stop_bits(serialport::StopBits::One)
-            .timeout(Self::idle_time_rs485(baud_rate))
+            .data_bits(data_bits)
+            .parity(parity)
+            .stop_bits(stop_bits)
             .open()?;
-        Ok(Self { port, last_tx: (std::time::Instant::now() - Self::idle_time_rs485(baud_rate)), baud_rate })
+        let mut master = Self::from_port(port, baud_rate)?;
+        master.char_format = char_format;
+        master.port.set_timeout(master.idle_time())?;
+        Ok(master)
+    }
+
+    /// Builds a master on top of an already opened serial port.
+    ///
+    /// The port keeps its character format; only its timeout is set to the
+    /// Modbus RTU silent interval (T3.5) for `baud_rate`. This allows masters
+    /// on custom transports such as a [`FaultyPort`](crate::FaultyPort).
+    /// Timing assumes 8N1 framing until
+    /// [`set_char_format`](Self::set_char_format) says otherwise.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::Master;
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let port = serialport::new("/dev/ttyUSB0", 19_200)
+    ///     .parity(serialport::Parity::Even)
+    ///     .open()?;
+    /// let master = Master::from_port(port, 19_200)?;
+    /// assert_eq!(master.baud_rate(), 19_200);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn from_port(
+        mut port: Box<dyn serialport::SerialPort>,
+        baud_rate: impl Into<u32>,
+    ) -> serialport::Result<Self> {
+        let baud_rate = baud_rate.into();
+        let idle_time = CharFormat::N81.idle_time(baud_rate);
+        port.set_timeout(idle_time)?;
+        Ok(Self {
+            port,
+            last_tx: (std::time::Instant::now() - idle_time),
+            last_rx: std::time::Instant::now(),
+            baud_rate,
+            char_format: CharFormat::N81,
+            strict: false,
+            permissive: false,
+            swapped_crc: std::collections::HashMap::new(),
+            responder_alias: std::collections::HashMap::new(),
+            quirks: std::collections::HashMap::new(),
+            transaction_id: 0,
+            journal: std::collections::VecDeque::new(),
+            journal_capacity: 0,
+            observers: Vec::new(),
+            utilization_cap: None,
+            quiet_until: std::time::Instant::now(),
+            spacing: std::collections::HashMap::new(),
+            consecutive_failures: 0,
+            reset_after: 0,
+            port_reset: None,
+            health: super::health::HealthTracker::new(),
+            exception_alarms: super::alarm::ExceptionAlarms::default(),
+            stale_input: StaleInput::Keep,
+            previous_request: None,
+            collision_detection: false,
+            collision_retries: 3,
+            collisions: 0,
+            backoff_rng: super::fault::XorShift::new(
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map_or(1, |elapsed| elapsed.as_nanos() as u64),
+            ),
+            retry_policy: RetryPolicy::default(),
+            statistics: Statistics::default(),
+        })
     }
 
     /// Returns the baud rate currently configured on the serial link.
@@ -78,19 +269,651 @@ impl Master {
     /// # }
     /// ```
     /// 
-    pub fn set_baudrate(&mut self, baud_rate: u32) -> serialport::Result<()> {
+    pub fn set_baudrate(&mut self, baud_rate: impl Into<u32>) -> serialport::Result<()> {
+        let baud_rate = baud_rate.into();
         self.port.set_baud_rate(baud_rate)?;
-        self.port.set_timeout(Self::idle_time_rs485(baud_rate))?;
+        self.port
+            .set_timeout(self.char_format.idle_time(baud_rate))?;
         self.baud_rate = baud_rate;
         self.last_tx = std::time::Instant::now();
         Ok(())
     }
 
+    /// Returns the character framing the timing calculations assume.
+    pub fn char_format(&self) -> CharFormat {
+        self.char_format
+    }
+
+    /// Reconfigures the port for `char_format` and updates the silent
+    /// intervals to match.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{CharFormat, Master};
+    ///
+    /// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+    /// master.set_char_format(CharFormat::E81)?;
+    /// assert_eq!(master.char_format().bits_per_char(), 11);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_char_format(&mut self, char_format: CharFormat) -> serialport::Result<()> {
+        let (data_bits, parity, stop_bits) = serial_settings(char_format);
+        self.port.set_data_bits(data_bits)?;
+        self.port.set_parity(parity)?;
+        self.port.set_stop_bits(stop_bits)?;
+        self.port
+            .set_timeout(char_format.idle_time(self.baud_rate))?;
+        self.char_format = char_format;
+        self.last_tx = std::time::Instant::now();
+        Ok(())
+    }
+
+    /// Returns `true` when strict response validation is enabled.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Enables or disables strict response validation.
+    ///
+    /// Echoed addresses, values, and quantities of write responses are always
+    /// compared against the request. In strict mode the master additionally
+    /// waits for the request to leave the transmitter and rejects any bytes
+    /// that were received before that moment, or during the T3.5 turnaround
+    /// that follows, with
+    /// [`ResponsePacketError::PrematureResponse`](crate::error::ResponsePacketError::PrematureResponse).
+    /// A slave cannot answer before the line turnaround, so such bytes are
+    /// almost always an adapter echoing our own frame back.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::Master;
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+    /// master.set_strict(true);
+    /// assert!(master.is_strict());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Returns `true` when permissive response decoding is enabled.
+    pub fn is_permissive(&self) -> bool {
+        self.permissive
+    }
+
+    /// Enables or disables permissive response decoding.
+    ///
+    /// When enabled, responses are decoded with
+    /// [`Response::from_bytes_permissive`]: defects that leave the payload
+    /// intact, such as padding after a write echo, no longer fail the
+    /// transaction and are logged as warnings instead (with the `log`
+    /// feature).
+    pub fn set_permissive(&mut self, permissive: bool) {
+        self.permissive = permissive;
+    }
+
+    /// Sets what happens to bytes already waiting in the receive buffer when
+    /// a request is about to be transmitted; kept by default.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{Master, StaleInput};
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+    /// master.set_stale_input(StaleInput::Drain);
+    /// assert_eq!(master.stale_input(), StaleInput::Drain);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_stale_input(&mut self, stale_input: StaleInput) {
+        self.stale_input = stale_input;
+    }
+
+    /// Returns what happens to bytes already received when a request is
+    /// transmitted.
+    pub fn stale_input(&self) -> StaleInput {
+        self.stale_input
+    }
+
+    /// Enables or disables collision detection, for adapters that loop the
+    /// line back to the receiver while transmitting.
+    ///
+    /// When enabled, every transmitted frame is read back and compared with
+    /// what was sent. A difference means another master was transmitting at
+    /// the same time: the collision is counted, the line is left to go idle,
+    /// and the frame is retransmitted after a random backoff that doubles in
+    /// range with every attempt. Once the retries set by
+    /// [`set_collision_retries`](Self::set_collision_retries) are exhausted,
+    /// the transaction fails with
+    /// [`Error::BusCollision`](crate::error::Error::BusCollision).
+    ///
+    /// Adapters without loopback never echo anything, so every transmission
+    /// would be reported as a collision; leave detection disabled for them.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::Master;
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+    /// master.set_collision_detection(true);
+    /// master.set_collision_retries(5);
+    /// // ...
+    /// println!("{} collisions so far", master.collision_count());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_collision_detection(&mut self, enabled: bool) {
+        self.collision_detection = enabled;
+    }
+
+    /// Sets how many times a frame is retransmitted after a collision; `3`
+    /// by default.
+    pub fn set_collision_retries(&mut self, retries: u32) {
+        self.collision_retries = retries;
+    }
+
+    /// Returns the number of collisions detected since the master was
+    /// created, including those resolved by a retransmission.
+    pub fn collision_count(&self) -> u64 {
+        self.collisions
+    }
+
+    /// Allows or disallows responses from `modbus_id` whose CRC bytes are sent
+    /// high byte first.
+    ///
+    /// Some fielded devices place the CRC in big-endian order. When allowed,
+    /// such a frame is accepted after the swapped CRC is verified and the
+    /// occurrence is counted; see [`swapped_crc_count`](Self::swapped_crc_count).
+    /// Disallowing resets the counter.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::Master;
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+    /// master.set_accept_swapped_crc(0x07, true);
+    /// assert_eq!(master.swapped_crc_count(0x07), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_accept_swapped_crc(&mut self, modbus_id: u8, accept: bool) {
+        if accept {
+            self.swapped_crc.entry(modbus_id).or_insert(0);
+        } else {
+            self.swapped_crc.remove(&modbus_id);
+        }
+    }
+
+    /// Returns how many responses from `modbus_id` were accepted with a
+    /// byte-swapped CRC.
+    pub fn swapped_crc_count(&self, modbus_id: u8) -> u64 {
+        self.swapped_crc.get(&modbus_id).copied().unwrap_or(0)
+    }
+
+    /// Accepts responses to requests for `modbus_id` that arrive from slave id
+    /// `responder` instead, as sent by bridges that remap unit ids, or stops
+    /// doing so with [`None`].
+    ///
+    /// The override only applies while strict validation is disabled; see
+    /// [`set_strict`](Self::set_strict). A frame from `responder` with a valid
+    /// CRC is then treated as coming from `modbus_id`, and the anomaly is
+    /// counted; see [`remapped_response_count`](Self::remapped_response_count).
+    /// Responses from any other id still fail with
+    /// [`ResponsePacketError::UnexpectedResponder`](crate::error::ResponsePacketError::UnexpectedResponder).
+    /// Changing the override resets the counter.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::Master;
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+    /// // The gateway answers requests for unit 17 as unit 1.
+    /// master.set_responder_alias(17, Some(1));
+    /// assert_eq!(master.remapped_response_count(17), 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_responder_alias(&mut self, modbus_id: u8, responder: Option<u8>) {
+        match responder {
+            Some(responder) => {
+                self.responder_alias.insert(modbus_id, (responder, 0));
+            }
+            None => {
+                self.responder_alias.remove(&modbus_id);
+            }
+        }
+    }
+
+    /// Returns how many responses to requests for `modbus_id` were accepted
+    /// from its alias.
+    pub fn remapped_response_count(&self, modbus_id: u8) -> u64 {
+        self.responder_alias
+            .get(&modbus_id)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    /// Registers a [`QuirkHook`] applied to every response frame received from
+    /// `modbus_id` before it is validated and decoded.
+    ///
+    /// Multiple hooks for the same slave run in the order they were added.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{Master, Request};
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+    /// master.add_quirk(0x03, |_: &Request, frame: &mut Vec<u8>| {
+    ///     if frame.first() == Some(&0xFF) {
+    ///         frame.remove(0);
+    ///     }
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn add_quirk(&mut self, modbus_id: u8, hook: impl QuirkHook + 'static) {
+        self.quirks.entry(modbus_id).or_default().push(Box::new(hook));
+    }
+
+    /// Removes every [`QuirkHook`] registered for `modbus_id`.
+    pub fn clear_quirks(&mut self, modbus_id: u8) {
+        self.quirks.remove(&modbus_id);
+    }
+
+    /// Returns the id of the most recent transaction, or `0` before the first
+    /// call to [`send`](Self::send).
+    ///
+    /// Ids start at `1` and increase by one per transaction, so an error
+    /// returned by `send` can be correlated with its journal entry and
+    /// [`Observer`] event.
+    pub fn last_transaction_id(&self) -> u64 {
+        self.transaction_id
+    }
+
+    /// Keeps the last `capacity` transactions in a journal for post-mortem
+    /// debugging. A capacity of `0` (the default) disables the journal.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::Master;
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+    /// master.set_journal_capacity(64);
+    ///
+    /// // ... after a failure:
+    /// for transaction in master.journal() {
+    ///     eprintln!("#{} {:02X?} -> {:?}", transaction.id(), transaction.request_frame(), transaction.outcome());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_journal_capacity(&mut self, capacity: usize) {
+        self.journal_capacity = capacity;
+        while self.journal.len() > capacity {
+            self.journal.pop_front();
+        }
+    }
+
+    /// Returns the journaled transactions, oldest first.
+    pub fn journal(&self) -> impl Iterator<Item = &Transaction> {
+        self.journal.iter()
+    }
+
+    /// Registers an [`Observer`] notified after every transaction.
+    pub fn add_observer(&mut self, observer: impl Observer + 'static) {
+        self.observers.push(Box::new(observer));
+    }
+
+    /// Returns the share of line time this master is allowed to occupy, or
+    /// `None` when uncapped (the default).
+    pub fn utilization_cap(&self) -> Option<f64> {
+        self.utilization_cap
+    }
+
+    /// Caps the share of line time used by this master to `cap` (`0.0 < cap <= 1.0`),
+    /// or removes the cap with `None`.
+    ///
+    /// After a transaction that occupied the line for `d`, the next one is
+    /// delayed until `d * (1 - cap) / cap` has passed, so that other devices
+    /// on a shared installation keep the remaining headroom.
+    ///
+    /// ---
+    /// # Panics
+    /// Panics when `cap` is not within `(0.0, 1.0]`.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::Master;
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+    /// master.set_utilization_cap(Some(0.6));
+    /// assert_eq!(master.utilization_cap(), Some(0.6));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_utilization_cap(&mut self, cap: Option<f64>) {
+        if let Some(cap) = cap {
+            assert!(
+                cap > 0.0 && cap <= 1.0,
+                "utilization cap must be within (0.0, 1.0]"
+            );
+        }
+        self.utilization_cap = cap;
+        self.quiet_until = std::time::Instant::now();
+    }
+
+    /// Requires at least `spacing` between the end of a transaction with
+    /// `modbus_id` and the start of the next request addressed to it, on top
+    /// of the T3.5 gap that applies to every frame. A zero spacing (the
+    /// default) removes the requirement.
+    ///
+    /// Useful for devices whose documentation demands a turnaround time
+    /// before they accept another request. Requests to other slaves are not
+    /// delayed.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::Master;
+    /// use std::time::Duration;
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+    /// master.set_slave_spacing(0x04, Duration::from_millis(20));
+    /// assert_eq!(master.slave_spacing(0x04), Duration::from_millis(20));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_slave_spacing(&mut self, modbus_id: u8, spacing: core::time::Duration) {
+        if spacing.is_zero() {
+            self.spacing.remove(&modbus_id);
+        } else {
+            self.spacing.entry(modbus_id).or_insert((spacing, None)).0 = spacing;
+        }
+    }
+
+    /// Returns the minimum spacing between requests to `modbus_id`.
+    pub fn slave_spacing(&self, modbus_id: u8) -> core::time::Duration {
+        self.spacing
+            .get(&modbus_id)
+            .map_or(core::time::Duration::ZERO, |(spacing, _)| *spacing)
+    }
+
+    /// Resets the port after `failures` consecutive failed transactions; `0`
+    /// (the default) disables resets.
+    ///
+    /// Transactions fail when no valid answer arrives (timeouts, CRC and
+    /// framing errors, I/O errors); exception responses and requests rejected
+    /// before transmission do not count. A reset clears the input and output
+    /// buffers, runs the hook set with [`set_port_reset`](Self::set_port_reset)
+    /// if any, restores the T3.5 port timeout, and notifies every
+    /// [`Observer`] through [`Observer::on_port_reset`].
+    pub fn set_reset_after(&mut self, failures: u32) {
+        self.reset_after = failures;
+    }
+
+    /// Installs the [`PortReset`] hook used to escalate a port reset, e.g. to
+    /// reopen the device or power-cycle the adapter.
+    pub fn set_port_reset(&mut self, hook: impl PortReset + 'static) {
+        self.port_reset = Some(Box::new(hook));
+    }
+
+    /// Returns the number of transactions that failed in a row.
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    /// Sets the [`RetryPolicy`] applied by [`send`](Self::send); by default
+    /// failed exchanges are not retried.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retry_policy = policy;
+    }
+
+    /// Returns the [`RetryPolicy`] applied by [`send`](Self::send).
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    /// Returns the [`Statistics`] counted so far.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::Master;
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+    /// let statistics = master.statistics();
+    /// println!(
+    ///     "{} transactions, {} CRC retries, {} timeout retries",
+    ///     statistics.transactions(),
+    ///     statistics.crc_retries(),
+    ///     statistics.timeout_retries(),
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    /// Sets every counter of the [`statistics`](Self::statistics) back to
+    /// zero.
+    pub fn reset_statistics(&mut self) {
+        self.statistics = Statistics::default();
+    }
+
+    /// Returns a [`BusHealth`](crate::BusHealth) snapshot: the baud rate,
+    /// whether the port answers, the current run of failed transactions, and
+    /// per slave the last success and the outcome counts within the health
+    /// window.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::Master;
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+    /// let health = master.health();
+    /// for slave in health.slaves() {
+    ///     println!("slave {}: {:.1}% errors", slave.modbus_id(), slave.error_rate() * 100.0);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn health(&self) -> crate::BusHealth {
+        crate::BusHealth {
+            baud_rate: self.baud_rate,
+            port_ok: self.port.bytes_to_read().is_ok(),
+            consecutive_failures: self.consecutive_failures,
+            window: self.health.window,
+            slaves: self.health.slaves(),
+        }
+    }
+
+    /// Sets the sliding window over which [`health`](Self::health) counts
+    /// outcomes; one minute by default.
+    pub fn set_health_window(&mut self, window: core::time::Duration) {
+        self.health.window = window;
+    }
+
+    /// Raises an [`ExceptionAlarm`](crate::ExceptionAlarm) through the
+    /// observers when a slave returns `exception` more than `limit` times
+    /// within `window`, replacing any previous limit for that exception.
+    ///
+    /// Exceptions are counted per slave, so one misconfigured device does
+    /// not hide behind a busy bus.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{Exception, ExceptionAlarm, Master, Observer};
+    /// use std::time::Duration;
+    ///
+    /// struct Alarms;
+    ///
+    /// impl Observer for Alarms {
+    ///     fn on_exception_alarm(&mut self, alarm: &ExceptionAlarm) {
+    ///         eprintln!("check the register map: {alarm}");
+    ///     }
+    /// }
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 19_200)?;
+    /// master.add_observer(Alarms);
+    /// master.set_exception_alarm(Exception::IllegalDataAddress, 5, Duration::from_secs(60));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn set_exception_alarm(
+        &mut self,
+        exception: crate::Exception,
+        limit: u32,
+        window: core::time::Duration,
+    ) {
+        self.exception_alarms.set(exception, limit, window);
+    }
+
+    /// Stops counting `exception` for alarms.
+    pub fn remove_exception_alarm(&mut self, exception: crate::Exception) {
+        self.exception_alarms.remove(exception);
+    }
+
+    /// Returns the shortest response timeout that can be satisfied at the
+    /// current baud rate: the T3.5 turnaround plus the time of one character.
+    ///
+    /// [`send`](Self::send) rejects requests with a shorter timeout instead of
+    /// reporting a guaranteed [`TimedOut`](std::io::ErrorKind::TimedOut).
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::Master;
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+    /// assert!(master.minimum_timeout() > std::time::Duration::from_millis(4));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn minimum_timeout(&self) -> core::time::Duration {
+        self.idle_time() + self.char_format.char_time(self.baud_rate)
+    }
+
+    /// Blocks until every byte written to the port has left the transmitter.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`Error::IO`](crate::error::Error::IO) when the port fails.
+    pub fn flush(&mut self) -> Result<(), crate::error::Error> {
+        let pending = self
+            .port
+            .bytes_to_write()
+            .map_err(|e| crate::error::Error::IO(e.into()))?;
+        self.port.flush().map_err(crate::error::Error::IO)?;
+        if pending > 0 {
+            self.last_tx = std::time::Instant::now();
+        }
+        Ok(())
+    }
+
+    /// Flushes the port and blocks until the line has been silent for the
+    /// T3.5 interval, counted from the last byte transmitted or received.
+    ///
+    /// Use it to hand the line over to non-Modbus traffic, e.g. a bootloader
+    /// protocol, without the other side mistaking the tail of a frame for
+    /// its own data. Bytes arriving meanwhile restart the interval but stay
+    /// in the receive buffer, so a line that never goes quiet blocks for good.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`Error::IO`](crate::error::Error::IO) when the port fails.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{Function, Master, Request};
+    ///
+    /// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 115_200)?;
+    /// // Vendor-specific: coil 0x0100 makes the device reboot into its bootloader.
+    /// let func = Function::WriteSingleCoil { address: 0x0100, value: true };
+    /// master.send(&Request::new(0x01, &func, std::time::Duration::from_millis(100)))?;
+    /// master.wait_idle()?;
+    /// // The line is free for the bootloader protocol now.
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn wait_idle(&mut self) -> Result<(), crate::error::Error> {
+        self.flush()?;
+        let idle_time = self.idle_time();
+        let mut pending = 0;
+        loop {
+            let available = self
+                .port
+                .bytes_to_read()
+                .map_err(|e| crate::error::Error::IO(e.into()))?;
+            if available != pending {
+                pending = available;
+                self.last_rx = std::time::Instant::now();
+            }
+            let silent = self.last_tx.max(self.last_rx).elapsed();
+            if silent > idle_time {
+                return Ok(());
+            }
+            std::thread::sleep(idle_time - silent);
+        }
+    }
+
     /// Sends a Modbus RTU request and waits for the corresponding response.
     ///
     /// Broadcast requests return immediately after the frame is flushed because
     /// the Modbus RTU spec forbids responses to slave id 0.
     ///
+    /// Requests whose timeout is below [`minimum_timeout`](Self::minimum_timeout)
+    /// fail with [`RequestPacketError::TimeoutTooShort`](crate::error::RequestPacketError::TimeoutTooShort)
+    /// before anything is transmitted.
+    ///
+    /// Failed exchanges are repeated as the
+    /// [`retry_policy`](Self::set_retry_policy) allows; the outcome of the
+    /// last attempt is returned.
+    ///
     /// ---
     /// # Examples
     /// ```ignore
@@ -107,39 +930,663 @@ impl Master {
     /// ```
     /// 
     pub fn send(&mut self, req: &Request) -> Result<Response, crate::error::Error> {
-        while self.last_tx.elapsed() <= Self::idle_time_rs485(self.baud_rate) {
+        self.send_with_frame(req).map(|(response, _)| response)
+    }
+
+    /// Sends a Modbus RTU request like [`send`](Self::send), retrying as
+    /// `policy` allows instead of the master's
+    /// [`retry_policy`](Self::set_retry_policy).
+    ///
+    /// ---
+    /// # Errors
+    /// Fails like [`send`](Self::send).
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{Function, Master, Request, RetryPolicy};
+    ///
+    /// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 19_200)?;
+    /// let func = Function::WriteSingleCoil { address: 0x0000, value: true };
+    /// let request = Request::new(0x01, &func, std::time::Duration::from_millis(200));
+    /// // Noise is worth retrying, a missing device is not.
+    /// let policy = RetryPolicy { crc_retries: 5, ..RetryPolicy::default() };
+    /// master.send_with_retries(&request, policy)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn send_with_retries(
+        &mut self,
+        req: &Request,
+        policy: RetryPolicy,
+    ) -> Result<Response, crate::error::Error> {
+        self.exchange(req, policy).map(|(response, _)| response)
+    }
+
+    /// Sends a Modbus RTU request like [`send`](Self::send), and returns the
+    /// response together with the exact bytes received for it, e.g. to
+    /// archive them for an audit trail.
+    ///
+    /// The frame is kept as it came off the line, before any quirk hook,
+    /// responder alias or noise skipping is applied. It is empty for
+    /// broadcasts.
+    ///
+    /// ---
+    /// # Errors
+    /// Fails like [`send`](Self::send).
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{Function, Master, Request};
+    ///
+    /// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 19_200)?;
+    /// let func = Function::ReadHoldingRegisters { starting_address: 0x0000, quantity: 2 };
+    /// let request = Request::new(0x01, &func, std::time::Duration::from_millis(200));
+    /// let (response, frame) = master.send_with_frame(&request)?;
+    /// println!("{response} from {frame:02X?}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn send_with_frame(
+        &mut self,
+        req: &Request,
+    ) -> Result<(Response, Box<[u8]>), crate::error::Error> {
+        self.exchange(req, self.retry_policy)
+    }
+
+    /// Performs a transaction retried as `policy` allows, and accounts for it
+    /// in the health, statistics, journal and observers.
+    fn exchange(
+        &mut self,
+        req: &Request,
+        policy: RetryPolicy,
+    ) -> Result<(Response, Box<[u8]>), crate::error::Error> {
+        self.transaction_id += 1;
+        self.statistics.transactions += 1;
+        self.pace(req.modbus_id());
+        let started = std::time::Instant::now();
+        let mut tx = Box::default();
+        let mut rx = Vec::new();
+        let result = self.transact_with_retries(req, policy, &mut tx, &mut rx);
+        if !rx.is_empty() {
+            let latency = self.last_rx.saturating_duration_since(self.last_tx);
+            self.statistics.record_latency(req.modbus_id(), latency);
+        }
+        let elapsed = started.elapsed();
+        if let Some(cap) = self.utilization_cap {
+            self.quiet_until = std::time::Instant::now() + elapsed.mul_f64((1.0 - cap) / cap);
+        }
+        if let Some((_, last)) = self.spacing.get_mut(&req.modbus_id()) {
+            *last = Some(std::time::Instant::now());
+        }
+        self.health.record(req.modbus_id(), &result);
+        if let Ok(Response::Exception(exception))
+        | Err(crate::error::Error::Exception(exception, _)) = &result
+            && let Some(alarm) = self.exception_alarms.record(req.modbus_id(), *exception)
+        {
+            #[cfg(feature = "log")]
+            log::warn!("{alarm}");
+            for observer in self.observers.iter_mut() {
+                observer.on_exception_alarm(&alarm);
+            }
+        }
+        match &result {
+            Ok(_) | Err(crate::error::Error::Exception(..)) => self.consecutive_failures = 0,
+            Err(crate::error::Error::Request(_)) => {}
+            Err(_) => {
+                self.consecutive_failures += 1;
+                if self.reset_after > 0 && self.consecutive_failures >= self.reset_after {
+                    self.reset_port();
+                }
+            }
+        }
+        #[cfg(feature = "log")]
+        Self::log_transaction(req, &result, elapsed);
+        #[cfg(feature = "metrics")]
+        Self::record_metrics(req, &result, elapsed);
+        let frame = rx.into_boxed_slice();
+        if self.journal_capacity > 0 || !self.observers.is_empty() {
+            let transmitted = (!tx.is_empty()).then(|| Timestamp::from_instant(self.last_tx));
+            let received = (!frame.is_empty()).then(|| Timestamp::from_instant(self.last_rx));
+            let transaction = Transaction {
+                id: self.transaction_id,
+                modbus_id: req.modbus_id(),
+                function: req.function().kind(),
+                request: tx,
+                response: frame.clone(),
+                outcome: result.as_ref().cloned().map_err(|e| e.to_string()),
+                started,
+                duration: elapsed,
+                transmitted,
+                received,
+            };
+            for observer in self.observers.iter_mut() {
+                observer.on_transaction(&transaction);
+            }
+            if self.journal_capacity > 0 {
+                if self.journal.len() == self.journal_capacity {
+                    self.journal.pop_front();
+                }
+                self.journal.push_back(transaction);
+            }
+        }
+        result.map(|response| (response, frame))
+    }
+
+    /// Sends a complete, already framed request as is and returns the raw
+    /// response, for functions the typed API does not model.
+    ///
+    /// Frames are typically built with [`raw::frame`](crate::raw::frame). The
+    /// exchange keeps the line timing of [`send`](Self::send), but only the
+    /// framing of the response is checked: its CRC, and that it comes from
+    /// the addressed slave. Exception responses are returned like any other
+    /// frame. `expected_len` is the length of a complete response, if known;
+    /// reception ends at the first silence once that many bytes arrived.
+    ///
+    /// Frames addressed to slave id 0 return an empty response right after
+    /// transmission. Raw exchanges are not journaled, observed or counted in
+    /// [`health`](Self::health).
+    ///
+    /// ---
+    /// # Errors
+    /// Fails like [`send`](Self::send) on I/O errors, timeouts and overflows,
+    /// and with [`ResponsePacketError`](crate::error::ResponsePacketError)
+    /// when the response does not pass [`raw::check`](crate::raw::check) or
+    /// comes from another slave. Frames shorter than
+    /// [`limits::MIN_FRAME_LEN`](crate::limits::MIN_FRAME_LEN) are rejected
+    /// with [`RequestPacketError::FrameTooShort`](crate::error::RequestPacketError::FrameTooShort)
+    /// before anything is sent.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{raw, Master};
+    ///
+    /// # fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut master = Master::new_rs485("/dev/ttyUSB0", 19_200)?;
+    /// let mut buf = Vec::new();
+    /// // Vendor-specific function 0x41 without payload.
+    /// let frame = raw::frame(0x01, 0x41, &[], &mut buf);
+    /// let response = master.send_raw(frame, 0, std::time::Duration::from_millis(200))?;
+    /// let (_, function_code, payload) = raw::check(&response)?;
+    /// println!("0x{function_code:02X}: {payload:02X?}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn send_raw(
+        &mut self,
+        frame: &[u8],
+        expected_len: usize,
+        timeout: core::time::Duration,
+    ) -> Result<Box<[u8]>, crate::error::Error> {
+        if frame.len() < crate::limits::MIN_FRAME_LEN {
+            return Err(crate::error::Error::Request(
+                crate::error::RequestPacketError::FrameTooShort {
+                    len: frame.len(),
+                    min: crate::limits::MIN_FRAME_LEN,
+                },
+            ));
+        }
+        let modbus_id = frame[0];
+        self.pace(modbus_id);
+        while self.last_tx.max(self.last_rx).elapsed() <= self.idle_time() {
             std::hint::spin_loop();
         }
-        let frame = req.to_bytes().map_err(|e| crate::error::Error::Request(e))?;
+        let minimum = self.minimum_timeout();
+        if modbus_id != 0 && timeout < minimum {
+            return Err(crate::error::Error::Request(
+                crate::error::RequestPacketError::TimeoutTooShort { timeout, minimum },
+            ));
+        }
+        self.port
+            .clear(serialport::ClearBuffer::Output)
+            .map_err(|e| crate::error::Error::IO(e.into()))?;
+        self.handle_stale_input()?;
+        self.transmit(frame)?;
+        self.previous_request = None;
+        if modbus_id == 0 {
+            return Ok(Box::default());
+        }
+        if self.strict {
+            self.reject_premature_response()?;
+        }
+        let mut rx = Vec::new();
+        self.receive(timeout, expected_len, None, &mut rx)?;
+        let (responder, _, _) = crate::raw::check(&rx).map_err(crate::error::Error::Response)?;
+        if responder != modbus_id {
+            return Err(crate::error::Error::Response(
+                crate::error::ResponsePacketError::UnexpectedResponder(responder),
+            ));
+        }
+        Ok(rx.into_boxed_slice())
+    }
+
+    /// Clears the port buffers, runs the reset hook, and reports the outcome
+    /// to the observers.
+    fn reset_port(&mut self) {
+        let failures = std::mem::take(&mut self.consecutive_failures);
+        let mut result = self
+            .port
+            .clear(serialport::ClearBuffer::All)
+            .map_err(std::io::Error::from);
+        if result.is_ok()
+            && let Some(hook) = self.port_reset.as_mut()
+        {
+            result = hook.reset(&mut self.port);
+        }
+        if result.is_ok() {
+            result = self
+                .port
+                .set_timeout(self.idle_time())
+                .map_err(std::io::Error::from);
+        }
+        self.last_tx = std::time::Instant::now();
+        #[cfg(feature = "log")]
+        log::warn!("port reset after {failures} consecutive failures: {result:?}");
+        for observer in self.observers.iter_mut() {
+            observer.on_port_reset(failures, &result);
+        }
+    }
+
+    /// Blocks until the utilization cap and the spacing configured for
+    /// `modbus_id` allow the next transaction.
+    fn pace(&self, modbus_id: u8) {
+        let mut until = self.quiet_until;
+        if let Some((spacing, Some(last))) = self.spacing.get(&modbus_id) {
+            until = until.max(*last + *spacing);
+        }
+        let now = std::time::Instant::now();
+        if until > now {
+            std::thread::sleep(until - now);
+        }
+    }
+
+    /// Repeats [`transact`](Self::transact) while the failure has retries
+    /// left under `policy`, leaving the frames of the last attempt in `tx`
+    /// and `rx`.
+    fn transact_with_retries(
+        &mut self,
+        req: &Request,
+        policy: RetryPolicy,
+        tx: &mut Box<[u8]>,
+        rx: &mut Vec<u8>,
+    ) -> Result<Response, crate::error::Error> {
+        let (mut crc_retries, mut timeout_retries) = (0, 0);
+        loop {
+            let result = self.transact(req, tx, rx);
+            let delay = match &result {
+                Err(crate::error::Error::IO(error))
+                    if error.kind() == std::io::ErrorKind::TimedOut
+                        && timeout_retries < policy.timeout_retries =>
+                {
+                    timeout_retries += 1;
+                    self.statistics.timeout_retries += 1;
+                    policy.timeout_delay
+                }
+                Err(error @ crate::error::Error::Response(_))
+                    if error.class() == crate::error::ErrorClass::Transport
+                        && crc_retries < policy.crc_retries =>
+                {
+                    crc_retries += 1;
+                    self.statistics.crc_retries += 1;
+                    policy.crc_delay
+                }
+                _ => return result,
+            };
+            #[cfg(feature = "log")]
+            if let Err(error) = &result {
+                log::debug!(
+                    "retrying request to slave 0x{:02X} after {error}",
+                    req.modbus_id()
+                );
+            }
+            std::thread::sleep(delay);
+            rx.clear();
+        }
+    }
+
+    /// Performs one request/response exchange on the line, leaving the
+    /// transmitted frame in `tx` and the raw received bytes in `rx`.
+    fn transact(
+        &mut self,
+        req: &Request,
+        tx: &mut Box<[u8]>,
+        rx: &mut Vec<u8>,
+    ) -> Result<Response, crate::error::Error> {
+        while self.last_tx.max(self.last_rx).elapsed() <= self.idle_time() {
+            std::hint::spin_loop();
+        }
+        let frame = req.to_bytes().map_err(crate::error::Error::Request)?;
+        let minimum = self.minimum_timeout();
+        if !req.is_broadcasting() && req.timeout() < minimum {
+            return Err(crate::error::Error::Request(
+                crate::error::RequestPacketError::TimeoutTooShort { timeout: req.timeout(), minimum },
+            ));
+        }
         self.port.clear(serialport::ClearBuffer::Output).map_err(|e| crate::error::Error::IO(e.into()))?;
-        self.write(&frame)?;
+        self.handle_stale_input()?;
+        self.transmit(&frame)?;
+        *tx = frame;
+        self.previous_request = (self.stale_input == StaleInput::Attribute
+            && !req.is_broadcasting())
+        .then(|| super::stale::PreviousRequest {
+            transaction_id: self.transaction_id,
+            modbus_id: req.modbus_id(),
+            function: req.function().clone(),
+            bit_order: req.bit_order(),
+            transmitted: self.last_tx,
+        });
         if req.is_broadcasting() {
             return Ok(Response::Success);
         }
-        let post_tx_idle = Self::idle_time_rs485(self.baud_rate);
+        if self.strict {
+            self.reject_premature_response()?;
+        }
+        self.receive(req.timeout(), req.function().expected_len(), Some(req), rx)?;
+        let mut frame = rx.clone();
+        if let Some(hooks) = self.quirks.get(&req.modbus_id()) {
+            for hook in hooks {
+                hook.fix_response(req, &mut frame);
+            }
+        }
+        let alias = self
+            .responder_alias
+            .get(&req.modbus_id())
+            .filter(|_| !self.strict)
+            .map(|&(alias, _)| alias);
+        Self::skip_leading_noise(req, alias, &mut frame);
+        if !self.strict
+            && let Some((alias, count)) = self.responder_alias.get_mut(&req.modbus_id())
+            && frame.first() == Some(alias)
+            && crate::crc::validate(&frame).is_ok()
+        {
+            *count += 1;
+            #[cfg(feature = "log")]
+            log::warn!(
+                "accepted response for slave 0x{:02X} from 0x{alias:02X} ({count} so far)",
+                req.modbus_id()
+            );
+            let len = frame.len();
+            frame[0] = req.modbus_id();
+            let crc = crate::crc::generate(&frame[..len - 2]);
+            frame[len - 2..].copy_from_slice(&crc.to_le_bytes());
+        }
+        let result = self.decode(req, &frame);
+        if let Err(crate::error::ResponsePacketError::CRCMismatch { expected, received }) = result
+            && received == expected.swap_bytes()
+            && let Some(count) = self.swapped_crc.get_mut(&req.modbus_id())
+        {
+            *count += 1;
+            #[cfg(feature = "log")]
+            log::warn!(
+                "accepted byte-swapped CRC from slave 0x{:02X} ({} so far)",
+                req.modbus_id(),
+                count
+            );
+            let len = frame.len();
+            frame.swap(len - 2, len - 1);
+            return self
+                .decode(req, &frame)
+                .map_err(crate::error::Error::Response);
+        }
+        result.map_err(crate::error::Error::Response)
+    }
+
+    /// Decodes `frame` as the response to `req`, permissively when enabled.
+    fn decode(
+        &self,
+        req: &Request,
+        frame: &[u8],
+    ) -> Result<Response, crate::error::ResponsePacketError> {
+        if !self.permissive {
+            return Response::from_bytes(req, frame);
+        }
+        let (response, warnings) = Response::from_bytes_permissive(req, frame)?;
+        #[cfg(feature = "log")]
+        for warning in &warnings {
+            log::warn!("response from slave 0x{:02X}: {warning}", req.modbus_id());
+        }
+        #[cfg(not(feature = "log"))]
+        let _ = warnings;
+        Ok(response)
+    }
+
+    /// Waits out the turnaround delay after a transmission, then reads the
+    /// response into `rx`, failing when nothing arrives or the response
+    /// overflows the receive buffer.
+    fn receive(
+        &mut self,
+        timeout: core::time::Duration,
+        expected_len: usize,
+        request: Option<&Request>,
+        rx: &mut Vec<u8>,
+    ) -> Result<(), crate::error::Error> {
+        let post_tx_idle = self.idle_time();
         let wait_start = std::time::Instant::now();
         while wait_start.elapsed() <= post_tx_idle {
             std::hint::spin_loop();
         }
+        if self.strict {
+            self.reject_premature_response()?;
+        }
         let mut buf: [u8; 256] = [0; 256];
-        let len = self.read(&mut buf, req.timeout(), req.function().expected_len())?;
+        let len = self.read(&mut buf, timeout, expected_len, request)?;
         if len == 0 {
             return Err(crate::error::Error::IO(std::io::ErrorKind::TimedOut.into()));
         }
-        Response::from_bytes(req, &buf[0..len]).map_err(|e| crate::error::Error::Response(e))
+        rx.extend_from_slice(&buf[0..len]);
+        if len == buf.len() {
+            let discarded = self.discard_until_idle(timeout)?;
+            if discarded > 0 {
+                return Err(crate::error::Error::Response(
+                    crate::error::ResponsePacketError::ResponseOverflow { discarded },
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops stray bytes received ahead of the response, i.e. everything
+    /// before the first position holding the expected slave id, or its
+    /// responder `alias`, followed by the request's function code (or its
+    /// exception form). The frame is left untouched when it already starts
+    /// there or no such position exists.
+    fn skip_leading_noise(req: &Request, alias: Option<u8>, frame: &mut Vec<u8>) {
+        let function_code = req.function().as_code();
+        let start = frame.windows(2).position(|w| {
+            (w[0] == req.modbus_id() || Some(w[0]) == alias) && w[1] & 0x7F == function_code
+        });
+        if let Some(start) = start
+            && start > 0
+        {
+            #[cfg(feature = "log")]
+            log::trace!("discarded leading noise {:02X?}", &frame[..start]);
+            frame.drain(..start);
+        }
+    }
+
+    /// Applies the [`StaleInput`] policy to bytes received before transmitting.
+    fn handle_stale_input(&mut self) -> Result<(), crate::error::Error> {
+        match self.stale_input {
+            StaleInput::Keep => {}
+            StaleInput::Clear => self
+                .port
+                .clear(serialport::ClearBuffer::Input)
+                .map_err(|e| crate::error::Error::IO(e.into()))?,
+            StaleInput::Drain => {
+                let stale = self.drain_input()?;
+                if !stale.is_empty() {
+                    #[cfg(feature = "log")]
+                    log::warn!(
+                        "discarded {} stale bytes before transmitting: {stale:02X?}",
+                        stale.len()
+                    );
+                }
+            }
+            StaleInput::Attribute => {
+                let stale = self.drain_input()?;
+                let previous = self.previous_request.take();
+                if stale.is_empty() {
+                    return Ok(());
+                }
+                let Some(previous) = previous else {
+                    #[cfg(feature = "log")]
+                    log::warn!(
+                        "discarded {} stale bytes before transmitting: {stale:02X?}",
+                        stale.len()
+                    );
+                    return Ok(());
+                };
+                let mut request = Request::new(
+                    previous.modbus_id,
+                    &previous.function,
+                    core::time::Duration::ZERO,
+                );
+                request.set_bit_order(previous.bit_order);
+                let late = crate::LateResponse {
+                    transaction_id: previous.transaction_id,
+                    modbus_id: previous.modbus_id,
+                    function: previous.function.kind(),
+                    response: Response::from_bytes(&request, &stale),
+                    frame: stale.into_boxed_slice(),
+                    observed_after: previous.transmitted.elapsed(),
+                };
+                #[cfg(feature = "log")]
+                log::warn!(
+                    "late response to transaction #{} after at most {:?}: {:02X?}",
+                    late.transaction_id,
+                    late.observed_after,
+                    late.frame
+                );
+                for observer in self.observers.iter_mut() {
+                    observer.on_late_response(&late);
+                }
+            }
+        }
+        Ok(())
     }
 
-    /// Writes a Modbus frame to the serial port and records the transmit instant.
+    /// Reads every byte waiting in the receive buffer.
+    fn drain_input(&mut self) -> Result<Vec<u8>, crate::error::Error> {
+        let available = self
+            .port
+            .bytes_to_read()
+            .map_err(|e| crate::error::Error::IO(e.into()))?;
+        let mut stale = vec![0; available as usize];
+        self.port
+            .read_exact(&mut stale)
+            .map_err(crate::error::Error::IO)?;
+        Ok(stale)
+    }
+
+    /// Writes `frame` and, with collision detection enabled, checks its echo,
+    /// retransmitting after a random backoff on collisions.
+    fn transmit(&mut self, frame: &[u8]) -> Result<(), crate::error::Error> {
+        let mut attempt = 0;
+        loop {
+            self.write(frame)?;
+            if !self.collision_detection {
+                return Ok(());
+            }
+            let echoed = self.read_echo(frame.len())?;
+            if echoed == frame {
+                return Ok(());
+            }
+            self.collisions += 1;
+            #[cfg(feature = "log")]
+            log::warn!("bus collision: sent {frame:02X?}, read back {echoed:02X?}");
+            if attempt >= self.collision_retries {
+                return Err(crate::error::Error::BusCollision {
+                    sent: frame.into(),
+                    echoed: echoed.into_boxed_slice(),
+                });
+            }
+            attempt += 1;
+            let frame_time = self.char_format.char_time(self.baud_rate) * frame.len() as u32;
+            self.discard_until_idle(frame_time * 4)?;
+            let slots = 1 + self.backoff_rng.below(1 << attempt.min(8));
+            std::thread::sleep(frame_time * slots as u32);
+            self.last_tx = std::time::Instant::now();
+        }
+    }
+
+    /// Reads back the `len` bytes of a frame just transmitted on a loopback
+    /// adapter, or fewer if they do not arrive in time.
+    fn read_echo(&mut self, len: usize) -> Result<Vec<u8>, crate::error::Error> {
+        self.port.flush().map_err(crate::error::Error::IO)?;
+        let deadline = std::time::Instant::now()
+            + self.char_format.char_time(self.baud_rate) * len as u32
+            + self.idle_time();
+        let mut echoed = vec![0; len];
+        let mut received = 0;
+        while received < len && std::time::Instant::now() < deadline {
+            match self.port.read(&mut echoed[received..]) {
+                Ok(n) => received += n,
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                Err(e) => return Err(crate::error::Error::IO(e)),
+            }
+        }
+        echoed.truncate(received);
+        Ok(echoed)
+    }
+
+    /// Writes a Modbus frame to the serial port, waits for it to leave the
+    /// transmit buffer and records that instant, so silent intervals and
+    /// response latencies count from the end of the frame on the line.
     fn write(&mut self, frame: &[u8]) -> Result<(), crate::error::Error> {
-        // println!("will write {}bytes ({:?})", frame.len(), frame);
+        #[cfg(feature = "log")]
+        log::trace!("tx {frame:02X?}");
         self.port.write_all(frame)
-            .map_err(|e| crate::error::Error::IO(e.into()))?;
+            .map_err(crate::error::Error::IO)?;
+        self.port.flush().map_err(crate::error::Error::IO)?;
         self.last_tx = std::time::Instant::now();
         Ok(())
     }
 
-    /// Reads bytes until the slave stops responding or `buf` fills up.
-    fn read(&mut self, buf: &mut [u8], timeout: core::time::Duration, expected_len: usize) -> Result<usize, crate::error::Error> {
+    /// Waits for the transmitter to drain and fails if any bytes were received
+    /// while the request was still on the line or its turnaround ran.
+    fn reject_premature_response(&mut self) -> Result<(), crate::error::Error> {
+        self.port.flush().map_err(crate::error::Error::IO)?;
+        let received = self.port.bytes_to_read().map_err(|e| crate::error::Error::IO(e.into()))?;
+        if received > 0 {
+            self.port.clear(serialport::ClearBuffer::Input).map_err(|e| crate::error::Error::IO(e.into()))?;
+            return Err(crate::error::Error::Response(
+                crate::error::ResponsePacketError::PrematureResponse(received as usize),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Reads and drops incoming bytes until the line stays silent for T3.5 or
+    /// `timeout` elapses, returning the number of bytes dropped.
+    fn discard_until_idle(&mut self, timeout: core::time::Duration) -> Result<usize, crate::error::Error> {
+        let start = std::time::Instant::now();
+        let mut scratch = [0u8; 256];
+        let mut discarded = 0;
+        while start.elapsed() <= timeout {
+            match self.port.read(&mut scratch) {
+                Ok(0) => break,
+                Ok(n) => discarded += n,
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => break,
+                Err(e) => return Err(crate::error::Error::IO(e)),
+            }
+        }
+        #[cfg(feature = "log")]
+        if discarded > 0 {
+            log::trace!("rx overflow, discarded {discarded} bytes");
+        }
+        Ok(discarded)
+    }
+
+    /// Reads bytes until the slave stops responding or `buf` fills up, or,
+    /// given the `request`, as soon as its whole response arrived.
+    fn read(&mut self, buf: &mut [u8], timeout: core::time::Duration, expected_len: usize, request: Option<&Request>) -> Result<usize, crate::error::Error> {
         let start = std::time::Instant::now();
         let mut len: usize = 0;
         while start.elapsed() <= timeout {
@@ -160,25 +1607,122 @@ impl Master {
                     // println!("idle detected");
                     break
                 },
-                Err(e) => return Err(crate::error::Error::IO(e.into())),
+                Err(e) => return Err(crate::error::Error::IO(e)),
             };
+            if n > 0 {
+                self.last_rx = std::time::Instant::now();
+            }
             len += n;
             if len >= buf.len() {
                 // println!("buffer full");
                 break;
             }
+            // A frame of the predicted length with a valid CRC is complete
+            // without waiting for the silence, unless trailing bytes are to
+            // be reported.
+            if !self.permissive
+                && let Some(req) = request
+                && crate::assembler::frame_len(
+                    req.function(),
+                    req.expected_response_len(),
+                    &buf[..len],
+                ) == Some(len)
+                && crate::crc::validate(&buf[..len]).is_ok()
+            {
+                break;
+            }
         }
         if start.elapsed() > timeout {
             // println!("timeout detected");
         }
-        // println!("final: {}bytes {:?}", len, &buf[0..len]);
+        #[cfg(feature = "log")]
+        log::trace!("rx {:02X?}", &buf[0..len]);
         Ok(len)
     }
 
-    /// Computes the Modbus RTU T3.5 idle time for a link running 8N1 encoding.
-    fn idle_time_rs485(baud_rate: u32) -> core::time::Duration {
-        const BITS_PER_CHAR: f64 = 10.0;
-        let seconds = 3.5 * BITS_PER_CHAR / baud_rate as f64;
-        core::time::Duration::from_secs_f64(seconds)
+    /// Emits log records describing a completed transaction.
+    #[cfg(feature = "log")]
+    fn log_transaction(
+        req: &Request,
+        result: &Result<Response, crate::error::Error>,
+        elapsed: core::time::Duration,
+    ) {
+        let slave = req.modbus_id();
+        let function = req.function().kind();
+        match result {
+            Ok(response) => {
+                log::debug!("slave 0x{slave:02X} {function}: {response} in {elapsed:?}")
+            }
+            Err(crate::error::Error::Response(
+                error @ crate::error::ResponsePacketError::CRCMismatch { .. },
+            )) => log::warn!("slave 0x{slave:02X} {function}: {error} after {elapsed:?}"),
+            Err(error) => log::debug!("slave 0x{slave:02X} {function}: {error} after {elapsed:?}"),
+        }
     }
+
+    /// Records the outcome of a completed transaction through the `metrics` facade.
+    #[cfg(feature = "metrics")]
+    fn record_metrics(
+        req: &Request,
+        result: &Result<Response, crate::error::Error>,
+        elapsed: core::time::Duration,
+    ) {
+        let slave = req.modbus_id().to_string();
+        let function = req.function().kind().as_code().to_string();
+        metrics::histogram!(
+            "modbus_rtu_request_duration_seconds",
+            "slave" => slave.clone(),
+            "function" => function,
+        )
+        .record(elapsed.as_secs_f64());
+        match result {
+            Ok(Response::Exception(exception)) => metrics::counter!(
+                "modbus_rtu_exceptions_total",
+                "slave" => slave,
+                "code" => format!("0x{:02X}", exception.as_code()),
+            )
+            .increment(1),
+            Err(crate::error::Error::Response(crate::error::ResponsePacketError::CRCMismatch {
+                ..
+            })) => metrics::counter!("modbus_rtu_crc_errors_total", "slave" => slave).increment(1),
+            Err(crate::error::Error::IO(error)) if error.kind() == std::io::ErrorKind::TimedOut => {
+                metrics::counter!("modbus_rtu_timeouts_total", "slave" => slave).increment(1)
+            }
+            _ => {}
+        }
+    }
+
+    /// Computes the Modbus RTU T3.5 idle time for the current baud rate and
+    /// character format.
+    fn idle_time(&self) -> core::time::Duration {
+        self.char_format.idle_time(self.baud_rate)
+    }
+}
+
+
+/// Translates `char_format` into `serialport` settings.
+pub(super) fn serial_settings(
+    char_format: CharFormat,
+) -> (
+    serialport::DataBits,
+    serialport::Parity,
+    serialport::StopBits,
+) {
+    let data_bits = match char_format.data_bits() {
+        5 => serialport::DataBits::Five,
+        6 => serialport::DataBits::Six,
+        7 => serialport::DataBits::Seven,
+        _ => serialport::DataBits::Eight,
+    };
+    let parity = match char_format.parity() {
+        crate::Parity::None => serialport::Parity::None,
+        crate::Parity::Even => serialport::Parity::Even,
+        crate::Parity::Odd => serialport::Parity::Odd,
+    };
+    let stop_bits = if char_format.stop_bits() == 2 {
+        serialport::StopBits::Two
+    } else {
+        serialport::StopBits::One
+    };
+    (data_bits, parity, stop_bits)
 }
@@ -0,0 +1,242 @@
+//! RTU frames carried over a plain TCP connection.
+
+use std::io::{Read, Write};
+
+use serialport::SerialPort;
+
+
+/// ## TcpPort
+///
+/// A [`serialport::SerialPort`] carrying RTU frames unchanged over a TCP
+/// connection ("RTU over TCP"), as offered by gateways and serial device
+/// servers in transparent mode.
+///
+/// This is not Modbus TCP: frames keep their slave id and CRC, and no MBAP
+/// header is added. Line settings live on the gateway, so setting them here
+/// only records the values, e.g. for the silent interval timing of the
+/// [`Master`](crate::Master). Modem lines read as inactive.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use modbus_rtu::{Master, TcpPort};
+///
+/// # fn demo() -> serialport::Result<()> {
+/// let port = TcpPort::connect("10.0.0.5:502")?;
+/// let master = Master::from_port(Box::new(port), 19_200)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Debug)]
+pub struct TcpPort {
+    stream: std::net::TcpStream,
+    address: String,
+    baud_rate: u32,
+    data_bits: serialport::DataBits,
+    parity: serialport::Parity,
+    stop_bits: serialport::StopBits,
+    flow_control: serialport::FlowControl,
+    timeout: core::time::Duration,
+}
+
+impl TcpPort {
+    /// Connects to the gateway at `address`, written `host:port` with an
+    /// optional `tcp://` scheme.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`serialport::Error`] when the connection cannot be
+    /// established.
+    pub fn connect(address: &str) -> serialport::Result<Self> {
+        let address = address.strip_prefix("tcp://").unwrap_or(address);
+        let stream = std::net::TcpStream::connect(address)?;
+        stream.set_nodelay(true)?;
+        let mut port = Self {
+            stream,
+            address: address.to_owned(),
+            baud_rate: 19_200,
+            data_bits: serialport::DataBits::Eight,
+            parity: serialport::Parity::Even,
+            stop_bits: serialport::StopBits::One,
+            flow_control: serialport::FlowControl::None,
+            timeout: core::time::Duration::ZERO,
+        };
+        port.set_timeout(crate::CharFormat::E81.idle_time(port.baud_rate))?;
+        Ok(port)
+    }
+
+    /// Returns the `host:port` address of the gateway.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+}
+
+impl Read for TcpPort {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.stream.read(buf) {
+            Ok(0) if !buf.is_empty() => Err(std::io::ErrorKind::ConnectionAborted.into()),
+            // Unix reports an expired socket timeout as `WouldBlock`, serial
+            // ports as `TimedOut`.
+            Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => {
+                Err(std::io::ErrorKind::TimedOut.into())
+            }
+            result => result,
+        }
+    }
+}
+
+impl Write for TcpPort {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.stream.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+impl SerialPort for TcpPort {
+    fn name(&self) -> Option<String> {
+        Some(format!("tcp://{}", self.address))
+    }
+
+    fn baud_rate(&self) -> serialport::Result<u32> {
+        Ok(self.baud_rate)
+    }
+
+    fn data_bits(&self) -> serialport::Result<serialport::DataBits> {
+        Ok(self.data_bits)
+    }
+
+    fn flow_control(&self) -> serialport::Result<serialport::FlowControl> {
+        Ok(self.flow_control)
+    }
+
+    fn parity(&self) -> serialport::Result<serialport::Parity> {
+        Ok(self.parity)
+    }
+
+    fn stop_bits(&self) -> serialport::Result<serialport::StopBits> {
+        Ok(self.stop_bits)
+    }
+
+    fn timeout(&self) -> core::time::Duration {
+        self.timeout
+    }
+
+    fn set_baud_rate(&mut self, baud_rate: u32) -> serialport::Result<()> {
+        self.baud_rate = baud_rate;
+        Ok(())
+    }
+
+    fn set_data_bits(&mut self, data_bits: serialport::DataBits) -> serialport::Result<()> {
+        self.data_bits = data_bits;
+        Ok(())
+    }
+
+    fn set_flow_control(
+        &mut self,
+        flow_control: serialport::FlowControl,
+    ) -> serialport::Result<()> {
+        self.flow_control = flow_control;
+        Ok(())
+    }
+
+    fn set_parity(&mut self, parity: serialport::Parity) -> serialport::Result<()> {
+        self.parity = parity;
+        Ok(())
+    }
+
+    fn set_stop_bits(&mut self, stop_bits: serialport::StopBits) -> serialport::Result<()> {
+        self.stop_bits = stop_bits;
+        Ok(())
+    }
+
+    fn set_timeout(&mut self, timeout: core::time::Duration) -> serialport::Result<()> {
+        // Sockets reject a zero timeout.
+        self.stream
+            .set_read_timeout(Some(timeout.max(core::time::Duration::from_micros(1))))?;
+        self.timeout = timeout;
+        Ok(())
+    }
+
+    fn write_request_to_send(&mut self, _level: bool) -> serialport::Result<()> {
+        Ok(())
+    }
+
+    fn write_data_terminal_ready(&mut self, _level: bool) -> serialport::Result<()> {
+        Ok(())
+    }
+
+    fn read_clear_to_send(&mut self) -> serialport::Result<bool> {
+        Ok(false)
+    }
+
+    fn read_data_set_ready(&mut self) -> serialport::Result<bool> {
+        Ok(false)
+    }
+
+    fn read_ring_indicator(&mut self) -> serialport::Result<bool> {
+        Ok(false)
+    }
+
+    fn read_carrier_detect(&mut self) -> serialport::Result<bool> {
+        Ok(false)
+    }
+
+    fn bytes_to_read(&self) -> serialport::Result<u32> {
+        let mut probe = [0u8; 512];
+        self.stream.set_nonblocking(true)?;
+        let peeked = self.stream.peek(&mut probe);
+        self.stream.set_nonblocking(false)?;
+        match peeked {
+            Ok(len) => Ok(len as u32),
+            Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => Ok(0),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    fn bytes_to_write(&self) -> serialport::Result<u32> {
+        Ok(0)
+    }
+
+    fn clear(&self, buffer_to_clear: serialport::ClearBuffer) -> serialport::Result<()> {
+        if buffer_to_clear == serialport::ClearBuffer::Output {
+            return Ok(());
+        }
+        let mut discarded = [0u8; 512];
+        self.stream.set_nonblocking(true)?;
+        let drained = loop {
+            match (&self.stream).read(&mut discarded) {
+                Ok(0) => break Ok(()),
+                Ok(_) => {}
+                Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => break Ok(()),
+                Err(error) => break Err(error),
+            }
+        };
+        self.stream.set_nonblocking(false)?;
+        Ok(drained?)
+    }
+
+    fn try_clone(&self) -> serialport::Result<Box<dyn SerialPort>> {
+        Ok(Box::new(Self {
+            stream: self.stream.try_clone()?,
+            address: self.address.clone(),
+            baud_rate: self.baud_rate,
+            data_bits: self.data_bits,
+            parity: self.parity,
+            stop_bits: self.stop_bits,
+            flow_control: self.flow_control,
+            timeout: self.timeout,
+        }))
+    }
+
+    fn set_break(&self) -> serialport::Result<()> {
+        Ok(())
+    }
+
+    fn clear_break(&self) -> serialport::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+//! Records of completed transactions and the observer interface that receives them.
+
+use crate::{FunctionKind, Response};
+
+
+/// ## Timestamp
+///
+/// A moment captured on both the monotonic clock, for accurate deltas, and
+/// the wall clock, for correlation with logs of other systems.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timestamp {
+    instant: std::time::Instant,
+    system: std::time::SystemTime,
+}
+
+impl Timestamp {
+    /// Captures the current moment.
+    pub fn now() -> Self {
+        Self {
+            instant: std::time::Instant::now(),
+            system: std::time::SystemTime::now(),
+        }
+    }
+
+    /// Stamps a past `instant`, deriving the wall-clock time from the
+    /// current offset between both clocks.
+    pub(crate) fn from_instant(instant: std::time::Instant) -> Self {
+        let now = Self::now();
+        Self {
+            instant,
+            system: now.system - now.instant.saturating_duration_since(instant),
+        }
+    }
+
+    /// Returns the moment `delay` after this one.
+    pub(crate) fn after(self, delay: core::time::Duration) -> Self {
+        Self {
+            instant: self.instant + delay,
+            system: self.system + delay,
+        }
+    }
+
+    /// Returns the monotonic time.
+    pub fn instant(&self) -> std::time::Instant {
+        self.instant
+    }
+
+    /// Returns the wall-clock time.
+    pub fn system(&self) -> std::time::SystemTime {
+        self.system
+    }
+}
+
+
+/// ## Transaction
+///
+/// Record of one request/response exchange performed by a [`Master`](crate::Master).
+///
+/// Every call to [`Master::send`](crate::Master::send) is assigned a
+/// monotonically increasing transaction id, which also identifies the record
+/// in the master's journal and in [`Observer`] events.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub(crate) id: u64,
+    pub(crate) modbus_id: u8,
+    pub(crate) function: FunctionKind,
+    pub(crate) request: Box<[u8]>,
+    pub(crate) response: Box<[u8]>,
+    pub(crate) outcome: Result<Response, String>,
+    pub(crate) started: std::time::Instant,
+    pub(crate) duration: core::time::Duration,
+    pub(crate) transmitted: Option<Timestamp>,
+    pub(crate) received: Option<Timestamp>,
+}
+
+impl Transaction {
+    /// Returns the transaction id assigned by the master.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the slave id the request was addressed to.
+    pub fn modbus_id(&self) -> u8 {
+        self.modbus_id
+    }
+
+    /// Returns the function of the request.
+    pub fn function(&self) -> FunctionKind {
+        self.function
+    }
+
+    /// Returns the transmitted request frame; empty when the request could
+    /// not be encoded.
+    pub fn request_frame(&self) -> &[u8] {
+        &self.request
+    }
+
+    /// Returns the raw bytes received in response, before any quirk hook
+    /// ran; empty on timeouts and broadcasts.
+    pub fn response_frame(&self) -> &[u8] {
+        &self.response
+    }
+
+    /// Returns the decoded response, or the description of the error that
+    /// ended the transaction.
+    pub fn outcome(&self) -> Result<&Response, &str> {
+        self.outcome.as_ref().map_err(String::as_str)
+    }
+
+    /// Returns the instant at which the transaction started.
+    pub fn started(&self) -> std::time::Instant {
+        self.started
+    }
+
+    /// Returns how long the transaction took, including the idle waits.
+    pub fn duration(&self) -> core::time::Duration {
+        self.duration
+    }
+
+    /// Returns when the request frame was handed to the port, or `None`
+    /// when nothing was transmitted.
+    pub fn transmitted(&self) -> Option<Timestamp> {
+        self.transmitted
+    }
+
+    /// Returns when the last response byte was received, or `None` when
+    /// nothing was received.
+    pub fn received(&self) -> Option<Timestamp> {
+        self.received
+    }
+}
+
+
+/// ## Observer
+///
+/// Receives events from a [`Master`](crate::Master). All methods have empty
+/// default implementations, so implementors only override what they need.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use modbus_rtu::{Master, Observer, Transaction};
+///
+/// struct Printer;
+///
+/// impl Observer for Printer {
+///     fn on_transaction(&mut self, transaction: &Transaction) {
+///         println!("#{} -> {:?}", transaction.id(), transaction.outcome());
+///     }
+/// }
+///
+/// # fn demo() -> serialport::Result<()> {
+/// let mut master = Master::new_rs485("/dev/ttyUSB0", 9_600)?;
+/// master.add_observer(Printer);
+/// # Ok(())
+/// # }
+/// ```
+///
+pub trait Observer: Send {
+    /// Called after every transaction, successful or not.
+    fn on_transaction(&mut self, transaction: &Transaction) {
+        let _ = transaction;
+    }
+
+    /// Called after the master reset its port following `failures`
+    /// consecutive failed transactions, with the outcome of the reset.
+    fn on_port_reset(&mut self, failures: u32, result: &std::io::Result<()>) {
+        let _ = (failures, result);
+    }
+
+    /// Called when bytes found before transmitting were attributed to the
+    /// previous request; see [`StaleInput::Attribute`](crate::StaleInput::Attribute).
+    fn on_late_response(&mut self, late: &crate::LateResponse) {
+        let _ = late;
+    }
+
+    /// Called when a slave returned an exception more often than tolerated;
+    /// see [`Master::set_exception_alarm`](crate::Master::set_exception_alarm).
+    fn on_exception_alarm(&mut self, alarm: &crate::ExceptionAlarm) {
+        let _ = alarm;
+    }
+}
+
+impl core::fmt::Debug for dyn Observer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Observer")
+    }
+}
@@ -0,0 +1,115 @@
+//! Transports selected by connection strings.
+
+use crate::{CharFormat, Master, Parity};
+
+
+impl Master {
+    /// Builds a master on the transport described by `url`, so that a
+    /// deployment can switch between a local port, an RTU over TCP gateway
+    /// and an RFC 2217 device server through configuration only.
+    ///
+    /// | Scheme | Transport | Target |
+    /// |---|---|---|
+    /// | `rtu://` | local serial port, as [`open`](Self::open) | path, e.g. `rtu:///dev/ttyUSB0` or `rtu://COM3` |
+    /// | `tcp://` | RTU frames over TCP, see [`TcpPort`](crate::TcpPort) | `host:port` |
+    /// | `rfc2217://` | remote serial port, as [`open_rfc2217`](Self::open_rfc2217) | `host:port` |
+    ///
+    /// The `tcp://` and `rfc2217://` schemes need the `tcp` feature.
+    ///
+    /// The line settings follow as query parameters, each optional:
+    /// `baud` (above `0`, default `19200`), `data_bits` (`5` to `8`, default `8`),
+    /// `parity` (`none`, `even` or `odd`, default `even`) and `stop_bits`
+    /// (`1` or `2`, default `1`). Over `tcp://` they only drive the timing
+    /// of the silent intervals.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`serialport::Error`] of kind
+    /// [`InvalidInput`](serialport::ErrorKind::InvalidInput) when `url` is
+    /// malformed or names an unknown scheme or parameter, or the error of
+    /// opening the transport.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{CharFormat, Master};
+    ///
+    /// # fn demo() -> serialport::Result<()> {
+    /// let master = Master::open_url("rtu:///dev/ttyUSB0?baud=9600&parity=none&stop_bits=2")?;
+    /// assert_eq!(master.char_format(), CharFormat::N82);
+    ///
+    /// let gateway = Master::open_url("tcp://10.0.0.5:502")?;
+    /// let server = Master::open_url("rfc2217://10.0.0.6:4001?baud=38400")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    pub fn open_url(url: &str) -> serialport::Result<Self> {
+        let invalid = |description: String| {
+            serialport::Error::new(serialport::ErrorKind::InvalidInput, description)
+        };
+        let (scheme, rest) = url
+            .split_once("://")
+            .ok_or_else(|| invalid(format!("\"{url}\" has no scheme")))?;
+        let (target, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let (baud_rate, char_format) = line_settings(query).map_err(invalid)?;
+        match scheme {
+            "rtu" => Self::open(target, baud_rate, char_format),
+            #[cfg(feature = "tcp")]
+            "rfc2217" => Self::open_rfc2217(target, baud_rate, char_format),
+            #[cfg(feature = "tcp")]
+            "tcp" => {
+                let port = super::TcpPort::connect(target)?;
+                let mut master = Self::from_port(Box::new(port), baud_rate)?;
+                master.set_char_format(char_format)?;
+                Ok(master)
+            }
+            _ => Err(invalid(format!("unsupported scheme \"{scheme}\""))),
+        }
+    }
+}
+
+
+/// Parses the baud rate and character format from the query of a
+/// connection string, or describes the offending parameter.
+fn line_settings(query: &str) -> Result<(u32, CharFormat), String> {
+    let mut baud_rate = 19_200;
+    let (mut data_bits, mut parity, mut stop_bits) = (8, Parity::Even, 1);
+    for parameter in query.split('&').filter(|parameter| !parameter.is_empty()) {
+        let invalid = || format!("invalid parameter \"{parameter}\"");
+        let (key, value) = parameter.split_once('=').ok_or_else(invalid)?;
+        match key {
+            "baud" => {
+                baud_rate = value
+                    .parse()
+                    .ok()
+                    .filter(|&baud_rate| baud_rate > 0)
+                    .ok_or_else(invalid)?
+            }
+            "data_bits" => {
+                data_bits = value
+                    .parse()
+                    .ok()
+                    .filter(|bits| (5..=8).contains(bits))
+                    .ok_or_else(invalid)?
+            }
+            "parity" => {
+                parity = match value {
+                    "none" => Parity::None,
+                    "even" => Parity::Even,
+                    "odd" => Parity::Odd,
+                    _ => return Err(invalid()),
+                }
+            }
+            "stop_bits" => {
+                stop_bits = value
+                    .parse()
+                    .ok()
+                    .filter(|bits| matches!(bits, 1 | 2))
+                    .ok_or_else(invalid)?
+            }
+            _ => return Err(format!("unknown parameter \"{key}\"")),
+        }
+    }
+    Ok((baud_rate, CharFormat::new(data_bits, parity, stop_bits)))
+}
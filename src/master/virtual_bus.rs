@@ -0,0 +1,129 @@
+//! A master and a slave connected through a pseudo-terminal, for tests.
+
+use crate::{Master, Slave};
+
+
+/// ## VirtualBus
+///
+/// Connects a [`Master`] to a [`Slave`] of this crate through a
+/// pseudo-terminal pair, so master code can be exercised end to end on CI
+/// machines without serial hardware.
+///
+/// The slave answers on its own thread until the bus is dropped; its data
+/// model stays reachable through [`slave`](Self::slave) to set up a test or
+/// check what the master wrote.
+///
+/// Pseudo-terminals exist on Unix only. On Windows, a com0com port pair can
+/// be used instead, with [`Slave::handle`] serving one end.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{DataBlock, Function, Request, Response, Slave, VirtualBus};
+/// use std::time::Duration;
+///
+/// let slave = Slave::builder(0x01)
+///     .holding_registers("setpoints", DataBlock::new(0x0000, vec![0; 4]))
+///     .build()
+///     .unwrap();
+/// let (bus, mut master) = VirtualBus::spawn(slave, 115_200u32).unwrap();
+///
+/// let write = Function::WriteSingleRegister { address: 0x0002, value: 42 };
+/// master.send(&Request::new(0x01, &write, Duration::from_millis(500))).unwrap();
+///
+/// let read = Function::ReadHoldingRegisters { starting_address: 0x0000, quantity: 4 };
+/// let response = master.send(&Request::new(0x01, &read, Duration::from_millis(500))).unwrap();
+/// assert_eq!(response, Response::Value(Box::new([0, 0, 42, 0])));
+/// assert_eq!(bus.slave().holding_registers().get(0x0002), Some(&42));
+/// ```
+///
+#[derive(Debug)]
+pub struct VirtualBus {
+    slave: std::sync::Arc<std::sync::Mutex<Slave>>,
+    stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl VirtualBus {
+    /// Opens a pseudo-terminal pair, serves `slave` on one end, and returns
+    /// the bus together with a master on the other end, timed for
+    /// `baud_rate`.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`serialport::Error`] when the pseudo-terminals cannot be
+    /// created or configured.
+    pub fn spawn(slave: Slave, baud_rate: impl Into<u32>) -> serialport::Result<(Self, Master)> {
+        let baud_rate = baud_rate.into();
+        let (master_end, mut slave_end) = serialport::TTYPort::pair()?;
+        let master = Master::from_port(Box::new(master_end), baud_rate)?;
+        serialport::SerialPort::set_timeout(
+            &mut slave_end,
+            crate::CharFormat::N81.idle_time(baud_rate),
+        )?;
+        let slave = std::sync::Arc::new(std::sync::Mutex::new(slave));
+        let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let thread = {
+            let (slave, stop) = (slave.clone(), stop.clone());
+            std::thread::spawn(move || serve(slave_end, &slave, &stop))
+        };
+        let bus = Self {
+            slave,
+            stop,
+            thread: Some(thread),
+        };
+        Ok((bus, master))
+    }
+
+    /// Locks and returns the slave, e.g. to preset its tables or check what
+    /// the master wrote. The slave does not answer while the guard is held.
+    pub fn slave(&self) -> std::sync::MutexGuard<'_, Slave> {
+        self.slave
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for VirtualBus {
+    fn drop(&mut self) {
+        self.stop.store(true, std::sync::atomic::Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+
+/// Answers the frames received on `port` with `slave` until `stop` is set.
+fn serve(
+    mut port: serialport::TTYPort,
+    slave: &std::sync::Mutex<Slave>,
+    stop: &std::sync::atomic::AtomicBool,
+) {
+    use std::io::{Read, Write};
+
+    let mut frame = Vec::new();
+    let mut buf = [0u8; 256];
+    while !stop.load(std::sync::atomic::Ordering::Relaxed) {
+        match port.read(&mut buf) {
+            Ok(len) if len > 0 => frame.extend_from_slice(&buf[..len]),
+            Ok(_) => {}
+            Err(error) if error.kind() == std::io::ErrorKind::TimedOut => {
+                if frame.is_empty() {
+                    continue;
+                }
+                let reply = slave
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .handle(&frame);
+                frame.clear();
+                if let Some(reply) = reply
+                    && port.write_all(&reply).is_err()
+                {
+                    return;
+                }
+            }
+            Err(_) => return,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Per-slave liveness tracking.
+
+use crate::Response;
+
+
+/// Change of a slave's liveness reported by a [`Watchdog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthEvent {
+    /// The slave stopped answering: it failed the configured number of
+    /// consecutive transactions for at least the hold-off time.
+    SlaveDown { modbus_id: u8 },
+
+    /// A slave previously reported down answered again.
+    SlaveRecovered {
+        modbus_id: u8,
+        downtime: core::time::Duration,
+    },
+}
+
+
+/// Liveness state of one slave.
+#[derive(Debug, Clone, Copy, Default)]
+struct SlaveHealth {
+    consecutive_failures: u32,
+    first_failure: Option<std::time::Instant>,
+    down_since: Option<std::time::Instant>,
+}
+
+
+/// ## Watchdog
+///
+/// Tracks consecutive transaction failures per slave and reports when a
+/// slave goes down or recovers.
+///
+/// A slave is declared down once it failed `failures` transactions in a row
+/// *and* the first of those failures lies at least `hold_off` in the past,
+/// so that a short burst of failures on a fast poll does not raise an alarm.
+/// The first answer afterwards, including an exception response, reports the
+/// recovery. Failures to encode a request are local errors and are ignored.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{HealthEvent, Watchdog};
+/// use std::time::Duration;
+///
+/// let mut watchdog = Watchdog::new(3, Duration::ZERO);
+/// for _ in 0..3 {
+///     let timeout = Err(modbus_rtu::error::Error::IO(std::io::ErrorKind::TimedOut.into()));
+///     if let Some(HealthEvent::SlaveDown { modbus_id }) = watchdog.record(0x01, &timeout) {
+///         println!("slave {modbus_id} is down");
+///     }
+/// }
+/// assert!(watchdog.is_down(0x01));
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct Watchdog {
+    failures: u32,
+    hold_off: core::time::Duration,
+    slaves: std::collections::HashMap<u8, SlaveHealth>,
+}
+
+impl Watchdog {
+    /// Creates a watchdog declaring a slave down after `failures` consecutive
+    /// failures spanning at least `hold_off`. A `failures` of `0` is treated as `1`.
+    pub fn new(failures: u32, hold_off: core::time::Duration) -> Self {
+        Self {
+            failures: failures.max(1),
+            hold_off,
+            slaves: std::collections::HashMap::new(),
+        }
+    }
+
+    /// Returns `true` while `modbus_id` is considered down.
+    pub fn is_down(&self, modbus_id: u8) -> bool {
+        self.slaves
+            .get(&modbus_id)
+            .is_some_and(|health| health.down_since.is_some())
+    }
+
+    /// Returns the number of consecutive failures of `modbus_id`.
+    pub fn consecutive_failures(&self, modbus_id: u8) -> u32 {
+        self.slaves
+            .get(&modbus_id)
+            .map_or(0, |health| health.consecutive_failures)
+    }
+
+    /// Records the outcome of a transaction with `modbus_id` and returns the
+    /// resulting change of liveness, if any.
+    pub fn record(
+        &mut self,
+        modbus_id: u8,
+        result: &Result<Response, crate::error::Error>,
+    ) -> Option<HealthEvent> {
+        let now = std::time::Instant::now();
+        let health = self.slaves.entry(modbus_id).or_default();
+        match result {
+            Err(crate::error::Error::Request(_)) => None,
+            Ok(_) | Err(crate::error::Error::Exception(..)) => {
+                let down_since = health.down_since.take();
+                *health = SlaveHealth::default();
+                down_since.map(|since| HealthEvent::SlaveRecovered {
+                    modbus_id,
+                    downtime: now - since,
+                })
+            }
+            Err(_) => {
+                health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+                let first_failure = *health.first_failure.get_or_insert(now);
+                if health.down_since.is_none()
+                    && health.consecutive_failures >= self.failures
+                    && now - first_failure >= self.hold_off
+                {
+                    health.down_since = Some(now);
+                    return Some(HealthEvent::SlaveDown { modbus_id });
+                }
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! One-shot operations for command-line tools and quick scripts.
+//!
+//! Each function opens the serial port, performs a single transaction with a
+//! timeout suggested for the link, and closes the port again. Exception
+//! responses are reported as [`Error::Exception`].
+
+use crate::error::{Error, ResponsePacketError};
+use crate::{CharFormat, Function, Master, Request, Response, Table};
+
+
+/// Processing time granted to the slave on top of the transmission time.
+const SLAVE_PROCESSING_MS: u32 = 100;
+
+
+/// Values read by [`read`], one per address starting at
+/// [`address`](Self::address).
+///
+/// The [`Display`](core::fmt::Display) form prints one `address: value` line
+/// per item.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::Table;
+/// use modbus_rtu::ops::Readout;
+///
+/// let readout = Readout::new(Table::HoldingRegisters, 0x0010, Box::new([0x1234, 7]));
+/// assert_eq!(readout.to_string(), "0x0010: 0x1234\n0x0011: 0x0007");
+///
+/// let coils = Readout::new(Table::Coils, 0x0000, Box::new([1, 0]));
+/// assert_eq!(coils.to_string(), "0x0000: 1\n0x0001: 0");
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Readout {
+    table: Table,
+    address: u16,
+    values: Box<[u16]>,
+}
+
+impl Readout {
+    /// Creates a readout of `values` read from `table` starting at `address`.
+    /// Coil and discrete input states are `0` or `1`.
+    pub fn new(table: Table, address: u16, values: Box<[u16]>) -> Self {
+        Self {
+            table,
+            address,
+            values,
+        }
+    }
+
+    /// Returns the table the values were read from.
+    pub const fn table(&self) -> Table {
+        self.table
+    }
+
+    /// Returns the address of the first value.
+    pub const fn address(&self) -> u16 {
+        self.address
+    }
+
+    /// Returns the values read.
+    pub fn values(&self) -> &[u16] {
+        &self.values
+    }
+}
+
+impl core::fmt::Display for Readout {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (i, value) in self.values.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            let address = self.address.wrapping_add(i as u16);
+            if self.table.is_bit() {
+                write!(f, "0x{address:04X}: {value}")?;
+            } else {
+                write!(f, "0x{address:04X}: 0x{value:04X}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+/// Reads `count` items of `table` starting at `address` from slave
+/// `modbus_id`.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use modbus_rtu::{ops, Table};
+///
+/// # fn demo() -> Result<(), modbus_rtu::error::Error> {
+/// let readout = ops::read("/dev/ttyUSB0", 19_200, 0x01, Table::HoldingRegisters, 0x0000, 4)?;
+/// println!("{readout}");
+/// # Ok(())
+/// # }
+/// ```
+///
+pub fn read(
+    port: &str,
+    baud_rate: u32,
+    modbus_id: u8,
+    table: Table,
+    address: u16,
+    count: u16,
+) -> Result<Readout, Error> {
+    let function = table.read(address, count);
+    let values = match transact(port, baud_rate, modbus_id, &function)? {
+        Response::Status(states) => states.iter().map(|&state| state as u16).collect(),
+        Response::Value(values) => values,
+        _ => return Err(Error::Response(ResponsePacketError::InvalidFormat)),
+    };
+    Ok(Readout::new(table, address, values))
+}
+
+
+/// Writes a single coil on slave `modbus_id`.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// # fn demo() -> Result<(), modbus_rtu::error::Error> {
+/// modbus_rtu::ops::write_coil("/dev/ttyUSB0", 19_200, 0x01, 0x0010, true)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+pub fn write_coil(
+    port: &str,
+    baud_rate: u32,
+    modbus_id: u8,
+    address: u16,
+    value: bool,
+) -> Result<(), Error> {
+    let function = Function::WriteSingleCoil { address, value };
+    transact(port, baud_rate, modbus_id, &function).map(|_| ())
+}
+
+
+/// Writes a single holding register on slave `modbus_id`.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// # fn demo() -> Result<(), modbus_rtu::error::Error> {
+/// modbus_rtu::ops::write_register("/dev/ttyUSB0", 19_200, 0x01, 0x0020, 1500)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+pub fn write_register(
+    port: &str,
+    baud_rate: u32,
+    modbus_id: u8,
+    address: u16,
+    value: u16,
+) -> Result<(), Error> {
+    let function = Function::WriteSingleRegister { address, value };
+    transact(port, baud_rate, modbus_id, &function).map(|_| ())
+}
+
+
+/// Opens `port`, sends `function` to `modbus_id`, and turns exception
+/// responses into errors.
+fn transact(
+    port: &str,
+    baud_rate: u32,
+    modbus_id: u8,
+    function: &Function,
+) -> Result<Response, Error> {
+    let mut master = Master::new_rs485(port, baud_rate).map_err(|e| Error::IO(e.into()))?;
+    let mut request = Request::new(modbus_id, function, core::time::Duration::ZERO);
+    let timeout = request
+        .suggested_timeout(baud_rate, CharFormat::N81, SLAVE_PROCESSING_MS)
+        .max(master.minimum_timeout());
+    request.set_timeout(timeout);
+    master.send(&request)?.into_result(&request)
+}
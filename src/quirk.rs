@@ -0,0 +1,56 @@
+use alloc::vec::Vec;
+
+
+/// ## QuirkHook
+///
+/// A per-device workaround that can rewrite a raw response frame before the
+/// standard validation in [`Response::from_bytes`](crate::Response::from_bytes)
+/// runs.
+///
+/// Hooks see the whole frame including slave id and CRC, and may remove,
+/// insert, or modify bytes (for example stripping a vendor preamble or fixing
+/// an off-by-one byte count). Any closure with a matching signature is also a
+/// hook.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{Function, QuirkHook, Request};
+///
+/// /// Device prefixes every response with a 0xFF wake-up byte.
+/// struct StripPreamble;
+///
+/// impl QuirkHook for StripPreamble {
+///     fn fix_response(&self, _request: &Request, frame: &mut Vec<u8>) {
+///         if frame.first() == Some(&0xFF) {
+///             frame.remove(0);
+///         }
+///     }
+/// }
+///
+/// let func = Function::ReadHoldingRegisters { starting_address: 0, quantity: 1 };
+/// let request = Request::new(0x01, &func, std::time::Duration::from_millis(100));
+/// let mut frame = vec![0xFF, 0x01, 0x03, 0x02, 0x00, 0x2A, 0x38, 0x5B];
+/// StripPreamble.fix_response(&request, &mut frame);
+/// assert_eq!(frame[0], 0x01);
+/// ```
+///
+pub trait QuirkHook: Send {
+    /// Adjusts `frame`, received in answer to `request`, before validation.
+    fn fix_response(&self, request: &crate::Request, frame: &mut Vec<u8>);
+}
+
+impl<F> QuirkHook for F
+where
+    F: Fn(&crate::Request, &mut Vec<u8>) + Send,
+{
+    fn fix_response(&self, request: &crate::Request, frame: &mut Vec<u8>) {
+        self(request, frame)
+    }
+}
+
+impl core::fmt::Debug for dyn QuirkHook {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("QuirkHook")
+    }
+}
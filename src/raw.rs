@@ -0,0 +1,182 @@
+//! Building and checking Modbus RTU frames around arbitrary PDUs.
+//!
+//! The typed API only models the functions listed in
+//! [`FunctionKind`](crate::FunctionKind). These helpers take care of the
+//! framing of everything else, e.g. vendor-specific function codes: the
+//! slave id in front and the CRC at the end.
+
+use alloc::vec::Vec;
+
+
+/// Writes a complete frame for `function_code` and `payload`, addressed to
+/// `modbus_id`, into `buf` and returns it.
+///
+/// `buf` is cleared first, so the same buffer can be reused for every frame.
+/// The payload is taken as is; keeping the frame within the 256 bytes allowed
+/// on a serial line is up to the caller.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::raw;
+///
+/// let mut buf = Vec::new();
+/// let frame = raw::frame(0x11, 0x03, &[0x00, 0x6B, 0x00, 0x03], &mut buf);
+/// assert_eq!(frame, &[0x11, 0x03, 0x00, 0x6B, 0x00, 0x03, 0x76, 0x87]);
+/// ```
+///
+pub fn frame<'a>(
+    modbus_id: u8,
+    function_code: u8,
+    payload: &[u8],
+    buf: &'a mut Vec<u8>,
+) -> &'a [u8] {
+    buf.clear();
+    buf.reserve(payload.len() + 4);
+    buf.push(modbus_id);
+    buf.push(function_code);
+    buf.extend_from_slice(payload);
+    let crc = crate::crc::generate(buf);
+    buf.extend_from_slice(&crc.to_le_bytes());
+    buf
+}
+
+
+/// Validates the CRC of a complete frame and splits it into slave id,
+/// function code and payload.
+///
+/// Nothing but the framing is checked: exception responses (function code
+/// with the `0x80` bit set) are returned like any other frame.
+///
+/// ---
+/// # Errors
+/// Returns [`ResponsePacketError::TooShort`](crate::error::ResponsePacketError::TooShort)
+/// for frames shorter than [`MIN_FRAME_LEN`](crate::limits::MIN_FRAME_LEN) and
+/// [`ResponsePacketError::CRCMismatch`](crate::error::ResponsePacketError::CRCMismatch)
+/// when the CRC does not match.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::raw;
+///
+/// let frame = [0x11, 0x03, 0x06, 0x02, 0x2B, 0x00, 0x00, 0x00, 0x64, 0xC8, 0xBA];
+/// let (modbus_id, function_code, payload) = raw::check(&frame).unwrap();
+/// assert_eq!((modbus_id, function_code), (0x11, 0x03));
+/// assert_eq!(payload, &[0x06, 0x02, 0x2B, 0x00, 0x00, 0x00, 0x64]);
+///
+/// assert!(raw::check(&frame[..10]).is_err());
+/// ```
+///
+pub fn check(frame: &[u8]) -> Result<(u8, u8, &[u8]), crate::error::ResponsePacketError> {
+    let len = frame.len();
+    if len < crate::limits::MIN_FRAME_LEN {
+        return Err(crate::error::ResponsePacketError::TooShort(len));
+    }
+    crate::crc::validate(frame)?;
+    Ok((frame[0], frame[1], &frame[2..len - 2]))
+}
+
+
+/// Function code of the bypass request, which carries a complete RTU frame
+/// through a gateway to a slave on one of its downstream buses.
+pub const BYPASS: u8 = 0x45;
+
+
+/// Writes a bypass frame for `gateway_id` carrying the complete RTU `frame`,
+/// CRC included, into `buf` and returns it.
+///
+/// A master tunnels the request for a downstream slave this way; a gateway
+/// wraps the response of that slave the same way. Sent with
+/// [`Master::send_raw`](crate::Master::send_raw), the response is 4 bytes
+/// longer than the tunnelled one. The tunnelled frame is taken as is;
+/// keeping the bypass frame within 256 bytes is up to the caller.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::raw;
+///
+/// let mut inner = Vec::new();
+/// let request = raw::frame(0x11, 0x03, &[0x00, 0x6B, 0x00, 0x03], &mut inner);
+/// let mut buf = Vec::new();
+/// let frame = raw::tunnel(0x01, request, &mut buf);
+/// assert_eq!(&frame[..2], &[0x01, raw::BYPASS]);
+/// assert_eq!(&frame[2..frame.len() - 2], request);
+/// ```
+///
+pub fn tunnel<'a>(gateway_id: u8, frame: &[u8], buf: &'a mut Vec<u8>) -> &'a [u8] {
+    self::frame(gateway_id, BYPASS, frame, buf)
+}
+
+
+/// Unwraps a bypass frame into the complete RTU frame it carries.
+///
+/// Exceptions are propagated from both levels: the gateway refusing to
+/// forward the frame, and the downstream slave answering the tunnelled
+/// request with an exception.
+///
+/// ---
+/// # Errors
+/// Returns [`TunnelError::Gateway`](crate::error::TunnelError::Gateway) or
+/// [`TunnelError::Tunnelled`](crate::error::TunnelError::Tunnelled) when the
+/// bypass frame or the tunnelled frame fails [`check`],
+/// [`TunnelError::UnexpectedFunction`](crate::error::TunnelError::UnexpectedFunction)
+/// when the bypass frame is not one,
+/// [`TunnelError::GatewayException`](crate::error::TunnelError::GatewayException)
+/// for an exception of the gateway and
+/// [`TunnelError::SlaveException`](crate::error::TunnelError::SlaveException)
+/// for one of the downstream slave.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::error::TunnelError;
+/// use modbus_rtu::{raw, Exception};
+///
+/// // Gateway 0x01 forwarded the read of slave 0x11 on its downstream bus.
+/// let mut inner = Vec::new();
+/// let response = raw::frame(0x11, 0x03, &[0x02, 0x00, 0x2A], &mut inner).to_vec();
+/// let mut buf = Vec::new();
+/// let frame = raw::tunnel(0x01, &response, &mut buf);
+/// assert_eq!(raw::untunnel(frame), Ok(&response[..]));
+///
+/// // The slave did not know the register.
+/// raw::frame(0x11, 0x83, &[0x02], &mut inner);
+/// let frame = raw::tunnel(0x01, &inner, &mut buf);
+/// assert_eq!(
+///     raw::untunnel(frame),
+///     Err(TunnelError::SlaveException { modbus_id: 0x11, exception: Exception::IllegalDataAddress }),
+/// );
+///
+/// // The gateway could not reach the downstream bus.
+/// let frame = raw::frame(0x01, raw::BYPASS | 0x80, &[0x0A], &mut buf);
+/// assert_eq!(
+///     raw::untunnel(frame),
+///     Err(TunnelError::GatewayException(Exception::GatewayPathUnavailable)),
+/// );
+/// ```
+///
+pub fn untunnel(frame: &[u8]) -> Result<&[u8], crate::error::TunnelError> {
+    use crate::error::TunnelError;
+
+    let (_, function_code, payload) = check(frame).map_err(TunnelError::Gateway)?;
+    match function_code {
+        BYPASS => {}
+        code if code == BYPASS | 0x80 => {
+            let exception =
+                crate::Exception::from_code(payload.first().copied().unwrap_or_default());
+            return Err(TunnelError::GatewayException(exception));
+        }
+        code => return Err(TunnelError::UnexpectedFunction(code)),
+    }
+    let (modbus_id, function_code, inner) = check(payload).map_err(TunnelError::Tunnelled)?;
+    if function_code & 0x80 != 0 {
+        let exception = crate::Exception::from_code(inner.first().copied().unwrap_or_default());
+        return Err(TunnelError::SlaveException {
+            modbus_id,
+            exception,
+        });
+    }
+    Ok(payload)
+}
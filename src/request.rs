@@ -1,9 +1,13 @@
+use alloc::{boxed::Box, vec::Vec};
+
+
 /// Represents an outgoing Modbus RTU request along with its metadata.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Request<'a> {
     modbus_id: u8,
     function: &'a crate::Function,
     timeout: core::time::Duration,
+    bit_order: crate::BitOrder,
 }
 
 impl<'a> Request<'a> {
@@ -24,12 +28,13 @@ impl<'a> Request<'a> {
     pub const fn new(
         modbus_id: u8,
         function: &'a crate::Function,
-        timeout: std::time::Duration,
+        timeout: core::time::Duration,
     ) -> Self {
         Self {
             modbus_id,
             function,
             timeout,
+            bit_order: crate::BitOrder::LsbFirst,
         }
     }
 
@@ -54,19 +59,166 @@ impl<'a> Request<'a> {
     }
 
     /// Returns the timeout associated with this request.
-    pub const fn timeout(&self) -> std::time::Duration {
+    pub const fn timeout(&self) -> core::time::Duration {
         self.timeout
     }
 
     /// Updates the timeout associated with this request.
-    pub fn set_timeout(&mut self, timeout: std::time::Duration) {
+    pub fn set_timeout(&mut self, timeout: core::time::Duration) {
         self.timeout = timeout;
     }
 
+    /// Returns the order in which coil states are packed for this request.
+    pub const fn bit_order(&self) -> crate::BitOrder {
+        self.bit_order
+    }
+
+    /// Overrides the coil bit packing for devices that deviate from the
+    /// specification's LSB-first order.
+    ///
+    /// The order applies to the coils written by this request as well as to
+    /// the coil and discrete input states decoded from its response.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{BitOrder, Function, Request, Response};
+    ///
+    /// // Read Coils example from the Modbus application protocol specification.
+    /// let func = Function::ReadCoils { starting_address: 0x0013, quantity: 8 };
+    /// let mut request = Request::new(0x11, &func, std::time::Duration::from_millis(100));
+    /// let frame = [0x11, 0x01, 0x05, 0xCD, 0x6B, 0xB2, 0x0E, 0x1B, 0x45, 0xE6];
+    ///
+    /// let lsb_first = [true, false, true, true, false, false, true, true];
+    /// assert_eq!(Response::from_bytes(&request, &frame), Ok(Response::Status(Box::new(lsb_first))));
+    ///
+    /// request.set_bit_order(BitOrder::MsbFirst);
+    /// let msb_first = [true, true, false, false, true, true, false, true];
+    /// assert_eq!(Response::from_bytes(&request, &frame), Ok(Response::Status(Box::new(msb_first))));
+    /// ```
+    ///
+    pub fn set_bit_order(&mut self, bit_order: crate::BitOrder) {
+        self.bit_order = bit_order;
+    }
+
+    /// Suggests a response timeout for this request on a link running at
+    /// `baud_rate` with the given character format.
+    ///
+    /// The suggestion covers transmitting the request frame, the T3.5 silent
+    /// interval before and after the response, transmitting the expected
+    /// response frame, and `slave_processing_ms` of processing time on the
+    /// device.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{CharFormat, Function, Request};
+    ///
+    /// let func = Function::ReadHoldingRegisters { starting_address: 0, quantity: 2 };
+    /// let request = Request::new(0x01, &func, std::time::Duration::from_millis(100));
+    ///
+    /// // 8 + 9 bytes and two silent intervals at 9600 baud, plus 10 ms processing.
+    /// let timeout = request.suggested_timeout(9_600, CharFormat::N81, 10);
+    /// assert_eq!(timeout.as_millis(), 35);
+    /// ```
+    ///
+    pub fn suggested_timeout(
+        &self,
+        baud_rate: u32,
+        char_format: crate::CharFormat,
+        slave_processing_ms: u32,
+    ) -> core::time::Duration {
+        let chars = self.function().request_len() + self.function().expected_len();
+        char_format.char_time(baud_rate) * chars as u32
+            + char_format.idle_time(baud_rate) * 2
+            + core::time::Duration::from_millis(slave_processing_ms as u64)
+    }
+
+    /// Returns a compact [`RequestSummary`] of this request, identifying the
+    /// slave, function and address range it targets.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{Function, FunctionKind, Request};
+    ///
+    /// let func = Function::ReadHoldingRegisters { starting_address: 0x0100, quantity: 4 };
+    /// let request = Request::new(0x11, &func, std::time::Duration::from_millis(100));
+    /// let summary = request.summary();
+    ///
+    /// assert_eq!(summary.modbus_id(), 0x11);
+    /// assert_eq!(summary.function(), FunctionKind::ReadHoldingRegisters);
+    /// assert_eq!(summary.address_range(), Some(0x0100..=0x0103));
+    /// ```
+    ///
+    pub fn summary(&self) -> RequestSummary {
+        use crate::Function::*;
+        let (starting_address, quantity) = match self.function() {
+            ReadCoils {
+                starting_address,
+                quantity,
+            }
+            | ReadDiscreteInputs {
+                starting_address,
+                quantity,
+            }
+            | ReadHoldingRegisters {
+                starting_address,
+                quantity,
+            }
+            | ReadInputRegisters {
+                starting_address,
+                quantity,
+            } => (Some(*starting_address), *quantity),
+            WriteSingleCoil { address, .. } | WriteSingleRegister { address, .. } => {
+                (Some(*address), 1)
+            }
+            WriteMultipleCoils {
+                starting_address,
+                value,
+            } => (Some(*starting_address), value.len() as u16),
+            WriteMultipleRegisters {
+                starting_address,
+                value,
+            } => (Some(*starting_address), value.len() as u16),
+            EncapsulatedInterface { .. } => (None, 0),
+        };
+        RequestSummary {
+            modbus_id: self.modbus_id(),
+            function: self.function().kind(),
+            starting_address,
+            quantity,
+        }
+    }
+
     pub fn is_broadcasting(&self) -> bool {
         self.modbus_id() == 0
     }
 
+    /// Returns the length of the regular response frame to this request,
+    /// slave id and CRC included, or [`None`] when it depends on the
+    /// response itself, as for the encapsulated interface transport.
+    ///
+    /// An exception response is always 5 bytes long instead.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{Function, Request};
+    ///
+    /// let func = Function::ReadInputRegisters { starting_address: 0x0000, quantity: 10 };
+    /// let request = Request::new(0x01, &func, std::time::Duration::from_millis(100));
+    ///
+    /// assert_eq!(request.expected_response_len(), Some(25));
+    /// ```
+    ///
+    pub const fn expected_response_len(&self) -> Option<usize> {
+        match self.function {
+            crate::Function::EncapsulatedInterface { .. } => None,
+            function => Some(function.expected_len()),
+        }
+    }
+
     /// Serializes the request into a Modbus RTU frame containing the device id,
     /// function payload, and CRC footer.
     ///
@@ -102,10 +254,66 @@ impl<'a> Request<'a> {
         }
         let mut buf: Vec<u8> = Vec::new();
         buf.push(self.modbus_id());
-        let bytes = self.function().to_bytes()?;
+        let bytes = self.function().to_bytes(self.bit_order)?;
         buf.extend_from_slice(&bytes);
         let crc_bytes = crate::crc::generate(&buf[0..buf.len()]);
         buf.extend_from_slice(&crc_bytes.to_le_bytes());
         Ok(buf.into_boxed_slice())
     }
 }
+
+
+/// Compact description of the request that produced a response, kept
+/// alongside exceptions so the failing slave, function and address range can
+/// be identified without the original [`Request`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestSummary {
+    modbus_id: u8,
+    function: crate::FunctionKind,
+    starting_address: Option<u16>,
+    quantity: u16,
+}
+
+impl RequestSummary {
+    /// Returns the Modbus slave identifier the request was sent to.
+    pub const fn modbus_id(&self) -> u8 {
+        self.modbus_id
+    }
+
+    /// Returns the function the request invoked.
+    pub const fn function(&self) -> crate::FunctionKind {
+        self.function
+    }
+
+    /// Returns the first address accessed, or `None` for functions that do
+    /// not address the data model.
+    pub const fn starting_address(&self) -> Option<u16> {
+        self.starting_address
+    }
+
+    /// Returns the number of coils or registers accessed.
+    pub const fn quantity(&self) -> u16 {
+        self.quantity
+    }
+
+    /// Returns the inclusive range of addresses accessed, or `None` for
+    /// functions that do not address the data model or access nothing.
+    pub fn address_range(&self) -> Option<core::ops::RangeInclusive<u16>> {
+        let start = self.starting_address?;
+        let last = start.checked_add(self.quantity.checked_sub(1)?)?;
+        Some(start..=last)
+    }
+}
+
+impl core::fmt::Display for RequestSummary {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "slave {}, {}", self.modbus_id, self.function)?;
+        match self.address_range() {
+            Some(range) if range.start() == range.end() => {
+                write!(f, " at 0x{:04X}", range.start())
+            }
+            Some(range) => write!(f, " at 0x{:04X}..=0x{:04X}", range.start(), range.end()),
+            None => Ok(()),
+        }
+    }
+}
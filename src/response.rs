@@ -1,3 +1,6 @@
+use alloc::{boxed::Box, format, string::ToString, vec::Vec};
+
+
 /// Represents the outcome of a Modbus RTU request, covering data reads, write
 /// acknowledgements, and protocol exceptions.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -8,9 +11,39 @@ pub enum Response {
     /// A collection of register values returned by the device.
     Value(Box<[u16]>),
 
-    /// Confirmation that a write request completed successfully.
+    /// Confirmation that a request completed successfully without any echo
+    /// from the device, e.g. after a broadcast.
     Success,
 
+    /// Confirmation that a write request completed successfully, carrying
+    /// what the device echoed back.
+    ///
+    /// `value_or_quantity` is the written value for single writes (`0xFF00`
+    /// or `0x0000` for coils) and the number of written items for multiple
+    /// writes.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{Function, Request, Response};
+    ///
+    /// let func = Function::WriteSingleRegister { address: 0x0001, value: 0x0003 };
+    /// let request = Request::new(0x11, &func, std::time::Duration::from_millis(100));
+    /// let frame = [0x11, 0x06, 0x00, 0x01, 0x00, 0x03, 0x9A, 0x9B];
+    ///
+    /// let response = Response::from_bytes(&request, &frame).unwrap();
+    /// assert_eq!(response, Response::WriteEcho { address: 0x0001, value_or_quantity: 0x0003 });
+    /// ```
+    ///
+    WriteEcho {
+        address: u16,
+        value_or_quantity: u16,
+    },
+
+    /// Data returned for an Encapsulated Interface Transport `(0x2B)` request,
+    /// without the function code and MEI type.
+    Encapsulated { mei_type: u8, data: Box<[u8]> },
+
     /// A Modbus application exception reported by the device.
     Exception(crate::Exception),
 }
@@ -58,7 +91,7 @@ impl Response {
         }
 
         // crc check
-        crate::crc::validate(&bytes)?;
+        crate::crc::validate(bytes)?;
 
         // exception check
         let function_code = bytes[1];
@@ -87,6 +120,16 @@ impl Response {
         let packet = &bytes[2..(len - 2)];
 
         // analyze
+        Self::analyze(request, function_kind, packet)
+    }
+
+    /// Decodes the payload `packet` of a response to `request` with function
+    /// `function_kind`, the slave id, function code and CRC already checked.
+    fn analyze(
+        request: &crate::Request,
+        function_kind: crate::FunctionKind,
+        packet: &[u8],
+    ) -> Result<Self, crate::error::ResponsePacketError> {
         match function_kind {
             crate::FunctionKind::ReadCoils | crate::FunctionKind::ReadDiscreteInputs => {
                 let byte_count = packet[0];
@@ -95,7 +138,7 @@ impl Response {
                     | crate::Function::ReadDiscreteInputs { quantity, .. } => *quantity,
                     _ => unreachable!(),
                 };
-                if byte_count < (quantity as u8 + 7) / 8 {
+                if (byte_count as usize) < (quantity as usize).div_ceil(8) {
                     return Err(crate::error::ResponsePacketError::InvalidFormat);
                 }
                 if packet.len() < byte_count as usize + 1 {
@@ -107,7 +150,7 @@ impl Response {
                         if (i * 8) + j >= quantity as usize {
                             break;
                         }
-                        let value = byte & (0b1 << j) != 0;
+                        let value = byte & request.bit_order().mask(j) != 0;
                         list.push(value);
                     }
                 }
@@ -120,7 +163,7 @@ impl Response {
                     | crate::Function::ReadInputRegisters { quantity, .. } => *quantity,
                     _ => unreachable!(),
                 };
-                if byte_count < quantity as u8 * 2 {
+                if (byte_count as usize) < quantity as usize * 2 {
                     return Err(crate::error::ResponsePacketError::InvalidFormat);
                 }
                 if packet.len() < byte_count as usize + 1 {
@@ -141,7 +184,7 @@ impl Response {
                 }
                 let (req_address, req_value) = match request.function() {
                     crate::Function::WriteSingleCoil { address, value } => {
-                        (*address, if *value == true { 0xFF00 } else { 0x0000 })
+                        (*address, if *value { 0xFF00 } else { 0x0000 })
                     }
                     crate::Function::WriteSingleRegister { address, value } => (*address, *value),
                     _ => unreachable!(),
@@ -151,7 +194,10 @@ impl Response {
                 if req_address != res_address || req_value != res_value {
                     return Err(crate::error::ResponsePacketError::InvalidFormat);
                 }
-                Ok(Self::Success)
+                Ok(Self::WriteEcho {
+                    address: res_address,
+                    value_or_quantity: res_value,
+                })
             }
             crate::FunctionKind::WriteMultipleCoils
             | crate::FunctionKind::WriteMultipleRegisters => {
@@ -174,8 +220,171 @@ impl Response {
                 if req_address != res_address || req_quantity != res_quantity {
                     return Err(crate::error::ResponsePacketError::InvalidFormat);
                 }
-                Ok(Self::Success)
+                Ok(Self::WriteEcho {
+                    address: res_address,
+                    value_or_quantity: res_quantity,
+                })
+            }
+            crate::FunctionKind::EncapsulatedInterfaceTransport => {
+                let req_mei_type = match request.function() {
+                    crate::Function::EncapsulatedInterface { mei_type, .. } => *mei_type,
+                    _ => unreachable!(),
+                };
+                match packet.split_first() {
+                    Some((mei_type, data)) if *mei_type == req_mei_type => Ok(Self::Encapsulated {
+                        mei_type: *mei_type,
+                        data: data.into(),
+                    }),
+                    _ => Err(crate::error::ResponsePacketError::InvalidFormat),
+                }
+            }
+        }
+    }
+
+    /// Decodes a response frame like [`from_bytes`](Self::from_bytes), but
+    /// tolerates defects that leave the payload intact and reports them as
+    /// [`Warning`](crate::Warning)s instead.
+    ///
+    /// Write responses followed by padding bytes are accepted, and read
+    /// responses that [`from_bytes`](Self::from_bytes) silently accepts are
+    /// checked for an oversized byte count, trailing bytes and set padding
+    /// bits. Marginal devices thus remain usable while their defects stay
+    /// visible.
+    ///
+    /// ---
+    /// # Errors
+    /// Fails like [`from_bytes`](Self::from_bytes) for every other defect.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{raw, Function, Request, Response, Warning};
+    ///
+    /// let func = Function::WriteSingleRegister { address: 0x0001, value: 0x0003 };
+    /// let request = Request::new(0x11, &func, std::time::Duration::from_millis(100));
+    /// let mut buf = Vec::new();
+    /// let padded = raw::frame(0x11, 0x06, &[0x00, 0x01, 0x00, 0x03, 0x00], &mut buf);
+    ///
+    /// assert!(Response::from_bytes(&request, padded).is_err());
+    /// let (response, warnings) = Response::from_bytes_permissive(&request, padded).unwrap();
+    /// assert_eq!(response, Response::WriteEcho { address: 0x0001, value_or_quantity: 0x0003 });
+    /// assert_eq!(warnings, vec![Warning::TrailingBytes(1)]);
+    /// ```
+    ///
+    pub fn from_bytes_permissive(
+        request: &crate::Request,
+        bytes: &[u8],
+    ) -> Result<(Self, Vec<crate::Warning>), crate::error::ResponsePacketError> {
+        use crate::FunctionKind;
+        use crate::Warning;
+
+        let kind = request.function().kind();
+        let response = Self::from_bytes(request, bytes).or_else(|error| {
+            let writes = matches!(
+                kind,
+                FunctionKind::WriteSingleCoil
+                    | FunctionKind::WriteSingleRegister
+                    | FunctionKind::WriteMultipleCoils
+                    | FunctionKind::WriteMultipleRegisters
+            );
+            if error == crate::error::ResponsePacketError::InvalidFormat
+                && writes
+                && bytes.len() > 8
+            {
+                Self::analyze(request, kind, &bytes[2..6])
+            } else {
+                Err(error)
+            }
+        })?;
+        let mut warnings = Vec::new();
+        let packet = &bytes[2..(bytes.len() - 2)];
+        let payload_len = match (&response, request.function()) {
+            (Self::Exception(_), _) => 1,
+            (
+                Self::Status(_),
+                crate::Function::ReadCoils { quantity, .. }
+                | crate::Function::ReadDiscreteInputs { quantity, .. },
+            ) => {
+                let needed = (*quantity as usize).div_ceil(8);
+                let (used, last) = (*quantity as usize % 8, packet[needed]);
+                if used != 0 && (used..8).any(|i| last & request.bit_order().mask(i) != 0) {
+                    warnings.push(Warning::NonZeroPadding);
+                }
+                Self::check_byte_count(packet[0], needed, &mut warnings)
+            }
+            (
+                Self::Value(_),
+                crate::Function::ReadHoldingRegisters { quantity, .. }
+                | crate::Function::ReadInputRegisters { quantity, .. },
+            ) => Self::check_byte_count(packet[0], *quantity as usize * 2, &mut warnings),
+            (Self::WriteEcho { .. }, _) => 4,
+            _ => packet.len(),
+        };
+        if packet.len() > payload_len {
+            warnings.push(Warning::TrailingBytes(packet.len() - payload_len));
+        }
+        Ok((response, warnings))
+    }
+
+    /// Reports a byte count larger than the `needed` data bytes, and returns
+    /// the length of the payload it declares.
+    fn check_byte_count(declared: u8, needed: usize, warnings: &mut Vec<crate::Warning>) -> usize {
+        if declared as usize > needed {
+            warnings.push(crate::Warning::ByteCountTooLarge { declared, needed });
+        }
+        declared as usize + 1
+    }
+
+    /// Decodes a response frame like [`from_bytes`](Self::from_bytes), but
+    /// hands frames carrying a function code the crate does not decode
+    /// itself to the matching decoder of `decoders`.
+    ///
+    /// Delegated frames are checked for their CRC and responder only; the
+    /// function code of `request` is not compared.
+    ///
+    /// ---
+    /// # Errors
+    /// Fails like [`from_bytes`](Self::from_bytes), or with whatever the
+    /// decoder returns.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{raw, DecoderRegistry, Function, Request, Response};
+    ///
+    /// let mut decoders = DecoderRegistry::new();
+    /// decoders.register(0x41, |payload: &[u8]| Ok(Response::Value(Box::new([payload.len() as u16]))));
+    ///
+    /// let func = Function::ReadHoldingRegisters { starting_address: 0, quantity: 1 };
+    /// let request = Request::new(0x01, &func, std::time::Duration::from_millis(100));
+    /// let mut buf = Vec::new();
+    /// let frame = raw::frame(0x01, 0x41, &[0xAA, 0xBB, 0xCC], &mut buf);
+    ///
+    /// assert!(Response::from_bytes(&request, frame).is_err());
+    /// assert_eq!(
+    ///     Response::from_bytes_with(&request, frame, &decoders),
+    ///     Ok(Response::Value(Box::new([3]))),
+    /// );
+    /// ```
+    ///
+    pub fn from_bytes_with(
+        request: &crate::Request,
+        bytes: &[u8],
+        decoders: &crate::DecoderRegistry,
+    ) -> Result<Self, crate::error::ResponsePacketError> {
+        match bytes.get(1) {
+            Some(&code)
+                if crate::FunctionKind::from_code(code).is_none() && decoders.contains(code) =>
+            {
+                let (modbus_id, _, _) = crate::raw::check(bytes)?;
+                if modbus_id != request.modbus_id() {
+                    return Err(crate::error::ResponsePacketError::UnexpectedResponder(
+                        modbus_id,
+                    ));
+                }
+                decoders.decode(bytes)
             }
+            _ => Self::from_bytes(request, bytes),
         }
     }
 
@@ -197,10 +406,138 @@ impl Response {
     ///
     pub fn is_success(&self) -> bool {
         match self {
-            Response::Status(_) | Response::Value(_) | Response::Success => true,
+            Response::Status(_)
+            | Response::Value(_)
+            | Response::Success
+            | Response::WriteEcho { .. }
+            | Response::Encapsulated { .. } => true,
             Response::Exception(exception) => *exception == crate::Exception::Acknowledge,
         }
     }
+
+    /// Converts an exception response into [`Error::Exception`](crate::error::Error::Exception)
+    /// carrying a summary of `request`, and passes every other response
+    /// through.
+    ///
+    /// As with [`is_success`](Self::is_success), `Acknowledge (0x05)` is not
+    /// treated as an error.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{Exception, Function, Request, Response};
+    /// use modbus_rtu::error::Error;
+    ///
+    /// let func = Function::ReadHoldingRegisters { starting_address: 0x0010, quantity: 2 };
+    /// let request = Request::new(0x05, &func, std::time::Duration::from_millis(100));
+    ///
+    /// let error = Response::Exception(Exception::IllegalDataAddress)
+    ///     .into_result(&request)
+    ///     .unwrap_err();
+    /// assert!(matches!(error, Error::Exception(Exception::IllegalDataAddress, _)));
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "device responsed illegal data address to slave 5, Read Holding Registers at 0x0010..=0x0011",
+    /// );
+    ///
+    /// assert!(Response::Value(Box::new([1, 2])).into_result(&request).is_ok());
+    /// ```
+    ///
+    pub fn into_result(self, request: &crate::Request) -> Result<Self, crate::error::Error> {
+        match self {
+            Response::Exception(exception) if exception != crate::Exception::Acknowledge => {
+                Err(crate::error::Error::Exception(exception, request.summary()))
+            }
+            response => Ok(response),
+        }
+    }
+
+    /// Returns the register values of a [`Response::Value`], or nothing for
+    /// any other response.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::Response;
+    ///
+    /// let response = Response::Value(Box::new([0x0010, 0x0020, 0x0030]));
+    /// assert_eq!(response.iter_registers().max(), Some(0x0030));
+    /// assert_eq!(Response::Success.iter_registers().count(), 0);
+    /// ```
+    ///
+    pub fn iter_registers(&self) -> impl Iterator<Item = u16> + '_ {
+        let values: &[u16] = match self {
+            Response::Value(values) => values,
+            _ => &[],
+        };
+        values.iter().copied()
+    }
+
+    /// Returns the coil or discrete input states of a [`Response::Status`],
+    /// or nothing for any other response.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::Response;
+    ///
+    /// let response = Response::Status(Box::new([true, false, true]));
+    /// assert_eq!(response.iter_bits().filter(|state| *state).count(), 2);
+    /// ```
+    ///
+    pub fn iter_bits(&self) -> impl Iterator<Item = bool> + '_ {
+        let states: &[bool] = match self {
+            Response::Status(states) => states,
+            _ => &[],
+        };
+        states.iter().copied()
+    }
+
+    /// Joins consecutive pairs of registers into 32-bit values, assembled in
+    /// `word_order`. A trailing odd register is ignored.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{Response, WordOrder};
+    ///
+    /// let response = Response::Value(Box::new([0x0001, 0x86A0, 0x0000, 0x0002]));
+    ///
+    /// let high_first: Vec<u32> = response.iter_u32(WordOrder::HighFirst).collect();
+    /// assert_eq!(high_first, vec![100_000, 2]);
+    ///
+    /// let low_first: Vec<u32> = response.iter_u32(WordOrder::LowFirst).collect();
+    /// assert_eq!(low_first, vec![0x86A0_0001, 0x0002_0000]);
+    /// ```
+    ///
+    pub fn iter_u32(&self, word_order: crate::WordOrder) -> impl Iterator<Item = u32> + '_ {
+        let values: &[u16] = match self {
+            Response::Value(values) => values,
+            _ => &[],
+        };
+        values
+            .chunks_exact(2)
+            .map(move |pair| word_order.join(pair[0], pair[1]))
+    }
+
+    /// Joins consecutive pairs of registers into IEEE 754 single precision
+    /// values, assembled in `word_order`. A trailing odd register is ignored.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{Response, WordOrder};
+    ///
+    /// let response = Response::Value(Box::new([0x4048, 0xF5C3, 0x0000, 0xBF80]));
+    ///
+    /// let mut values = response.iter_f32(WordOrder::HighFirst);
+    /// assert_eq!(values.next(), Some(3.14));
+    /// assert_eq!(response.iter_f32(WordOrder::LowFirst).nth(1), Some(-1.0));
+    /// ```
+    ///
+    pub fn iter_f32(&self, word_order: crate::WordOrder) -> impl Iterator<Item = f32> + '_ {
+        self.iter_u32(word_order).map(f32::from_bits)
+    }
 }
 
 impl core::fmt::Display for Response {
@@ -212,6 +549,12 @@ impl core::fmt::Display for Response {
                 Response::Status(items) => format!("{:?}", items),
                 Response::Value(items) => format!("{:?}", items),
                 Response::Success => "Success".to_string(),
+                Response::WriteEcho {
+                    address,
+                    value_or_quantity,
+                } => format!("Success (0x{address:04X}: 0x{value_or_quantity:04X})"),
+                Response::Encapsulated { mei_type, data } =>
+                    format!("MEI 0x{mei_type:02X} {data:02X?}"),
                 Response::Exception(exception) => exception.to_string(),
             }
         )
@@ -0,0 +1,220 @@
+use crate::error::ResponsePacketError;
+use crate::{BitOrder, Exception, Function, FunctionKind, Request, Response};
+
+
+/// ## ResponseView
+///
+/// Validated response frame, borrowed from the receive buffer.
+///
+/// Unlike [`Response::from_bytes`], which copies every register or coil of a
+/// read into a new collection, a view decodes values only when they are
+/// asked for. Reading one field out of a 125 register block costs a single
+/// lookup.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{Function, Request, ResponseView};
+///
+/// let function = Function::ReadInputRegisters { starting_address: 0x0000, quantity: 2 };
+/// let request = Request::new(0x01, &function, std::time::Duration::from_millis(100));
+/// let frame = [0x01, 0x04, 0x04, 0x00, 0x10, 0x00, 0x20, 0xFB, 0x99];
+///
+/// let view = ResponseView::parse(&request, &frame).unwrap();
+/// assert_eq!(view.len(), 2);
+/// assert_eq!(view.register(1), Some(0x0020));
+/// assert_eq!(view.registers().sum::<u16>(), 0x0030);
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResponseView<'a> {
+    frame: &'a [u8],
+    kind: Option<FunctionKind>,
+    quantity: u16,
+    bit_order: BitOrder,
+}
+
+impl<'a> ResponseView<'a> {
+    /// Validates `bytes` as the response to `request` and borrows it.
+    ///
+    /// The frame is checked exactly as [`Response::from_bytes`] would check
+    /// it, but the values of reads are left in place.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`ResponsePacketError`] when the frame does not pass
+    /// validation (CRC mismatch, unexpected responder, malformed payload,
+    /// etc.).
+    pub fn parse(request: &Request, bytes: &'a [u8]) -> Result<Self, ResponsePacketError> {
+        let len = bytes.len();
+        if len < 5 {
+            return Err(ResponsePacketError::TooShort(len));
+        }
+        crate::crc::validate(bytes)?;
+        let mut view = Self {
+            frame: bytes,
+            kind: None,
+            quantity: 0,
+            bit_order: request.bit_order(),
+        };
+        if bytes[1] & 0x80 != 0 {
+            return Ok(view);
+        }
+        if bytes[0] != request.modbus_id() {
+            return Err(ResponsePacketError::UnexpectedResponder(bytes[0]));
+        }
+        let kind = request.function().kind();
+        if FunctionKind::from_code(bytes[1]) != Some(kind) {
+            return Err(ResponsePacketError::InvalidFormat);
+        }
+        let (quantity, needed) = match request.function() {
+            Function::ReadCoils { quantity, .. }
+            | Function::ReadDiscreteInputs { quantity, .. } => {
+                (*quantity, (*quantity as usize).div_ceil(8))
+            }
+            Function::ReadHoldingRegisters { quantity, .. }
+            | Function::ReadInputRegisters { quantity, .. } => (*quantity, *quantity as usize * 2),
+            _ => {
+                Response::from_bytes(request, bytes)?;
+                (0, 0)
+            }
+        };
+        if kind.is_read() {
+            let byte_count = bytes[2] as usize;
+            if byte_count < needed || len - 4 < byte_count + 1 {
+                return Err(ResponsePacketError::InvalidFormat);
+            }
+        }
+        view.kind = Some(kind);
+        view.quantity = quantity;
+        Ok(view)
+    }
+
+    /// Returns the whole frame, including slave id and CRC.
+    pub fn frame(&self) -> &'a [u8] {
+        self.frame
+    }
+
+    /// Returns the exception reported by the device, if any.
+    pub fn exception(&self) -> Option<Exception> {
+        if self.kind.is_none() {
+            Some(Exception::from_code(self.frame[2]))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the number of coils or registers read, or `0` for any other
+    /// response.
+    pub fn len(&self) -> usize {
+        self.quantity as usize
+    }
+
+    /// Returns `true` when the response carries no coil or register.
+    pub fn is_empty(&self) -> bool {
+        self.quantity == 0
+    }
+
+    /// Returns the registers read, decoded one at a time.
+    ///
+    /// The iterator is empty unless the response answers a register read.
+    pub fn registers(&self) -> impl Iterator<Item = u16> + 'a {
+        let count = if self.holds_registers() {
+            self.len()
+        } else {
+            0
+        };
+        self.frame[3..]
+            .chunks_exact(2)
+            .take(count)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+    }
+
+    /// Returns the `index`-th register read, counted from the starting
+    /// address of the request.
+    pub fn register(&self, index: usize) -> Option<u16> {
+        if !self.holds_registers() || index >= self.len() {
+            return None;
+        }
+        let offset = 3 + index * 2;
+        Some(u16::from_be_bytes([
+            self.frame[offset],
+            self.frame[offset + 1],
+        ]))
+    }
+
+    /// Returns the coil or discrete input states read, decoded one at a
+    /// time.
+    ///
+    /// The iterator is empty unless the response answers a coil or discrete
+    /// input read.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::test_vectors::READ_COILS;
+    /// use modbus_rtu::{Function, Request, ResponseView};
+    ///
+    /// let function = Function::ReadCoils { starting_address: 0x0013, quantity: 19 };
+    /// let request = Request::new(0x11, &function, std::time::Duration::from_millis(100));
+    ///
+    /// let view = ResponseView::parse(&request, READ_COILS.response).unwrap();
+    /// assert_eq!(view.bit(2), Some(true));
+    /// assert_eq!(view.bit(19), None);
+    /// assert_eq!(view.bits().filter(|state| *state).count(), 12);
+    /// ```
+    ///
+    pub fn bits(&self) -> impl Iterator<Item = bool> + 'a {
+        let count = if self.holds_bits() { self.len() } else { 0 };
+        let (frame, bit_order) = (self.frame, self.bit_order);
+        (0..count).map(move |index| frame[3 + index / 8] & bit_order.mask(index % 8) != 0)
+    }
+
+    /// Returns the `index`-th coil or discrete input state read, counted from
+    /// the starting address of the request.
+    pub fn bit(&self, index: usize) -> Option<bool> {
+        if !self.holds_bits() || index >= self.len() {
+            return None;
+        }
+        Some(self.frame[3 + index / 8] & self.bit_order.mask(index % 8) != 0)
+    }
+
+    /// Decodes the whole frame into an owned [`Response`].
+    pub fn to_response(&self) -> Response {
+        let payload = &self.frame[2..self.frame.len() - 2];
+        match self.kind {
+            None => Response::Exception(Exception::from_code(self.frame[2])),
+            Some(FunctionKind::ReadCoils | FunctionKind::ReadDiscreteInputs) => {
+                Response::Status(self.bits().collect())
+            }
+            Some(FunctionKind::ReadHoldingRegisters | FunctionKind::ReadInputRegisters) => {
+                Response::Value(self.registers().collect())
+            }
+            Some(FunctionKind::EncapsulatedInterfaceTransport) => Response::Encapsulated {
+                mei_type: payload[0],
+                data: payload[1..].into(),
+            },
+            Some(_) => Response::WriteEcho {
+                address: u16::from_be_bytes([payload[0], payload[1]]),
+                value_or_quantity: u16::from_be_bytes([payload[2], payload[3]]),
+            },
+        }
+    }
+
+    /// Returns `true` when the response answers a coil or discrete input
+    /// read.
+    fn holds_bits(&self) -> bool {
+        matches!(
+            self.kind,
+            Some(FunctionKind::ReadCoils | FunctionKind::ReadDiscreteInputs)
+        )
+    }
+
+    /// Returns `true` when the response answers a register read.
+    fn holds_registers(&self) -> bool {
+        matches!(
+            self.kind,
+            Some(FunctionKind::ReadHoldingRegisters | FunctionKind::ReadInputRegisters)
+        )
+    }
+}
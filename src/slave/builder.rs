@@ -0,0 +1,500 @@
+use crate::error::{ModelConflict, ModelError};
+use crate::{
+    AccessProfile, AddressSpace, DataBlock, DataConstraint, Exception, Function, FunctionPolicy,
+    Response, Slave, Table,
+};
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+
+/// Application callback run when a master writes an address.
+pub(crate) struct WriteCallback {
+    pub(crate) table: Table,
+    pub(crate) address: u16,
+    pub(crate) callback: Box<dyn FnMut(u16) + Send>,
+}
+
+impl core::fmt::Debug for WriteCallback {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "WriteCallback({} 0x{:04X})", self.table, self.address)
+    }
+}
+
+
+/// Application callback run when a broadcast request was received.
+pub(crate) struct BroadcastCallback(pub(crate) Box<BroadcastFn>);
+
+type BroadcastFn = dyn FnMut(&Function, &Response) + Send;
+
+impl core::fmt::Debug for BroadcastCallback {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("BroadcastCallback")
+    }
+}
+
+
+/// Application check run before a master's write is applied.
+pub(crate) struct WriteValidator(pub(crate) Box<ValidateFn>);
+
+type ValidateFn = dyn Fn(&Slave, Table, u16, &[u16]) -> Result<(), Exception> + Send;
+
+impl core::fmt::Debug for WriteValidator {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("WriteValidator")
+    }
+}
+
+
+/// ## SlaveBuilder
+///
+/// Describes the data model of a [`Slave`]: named blocks for each of the four
+/// tables, callbacks run when a master writes specific addresses, and the
+/// values a master may write to specific holding registers.
+///
+/// [`build`](Self::build) validates the whole description and fails with a
+/// [`ModelError`] listing every conflict: blocks of the same table that
+/// declare an address twice or share a name, callbacks attached to
+/// addresses that no block defines or that a master cannot write, and
+/// constraints on undefined holding registers or violated by the initial
+/// value of their register, so a slave never starts serving invalid data.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::error::ModelConflict;
+/// use modbus_rtu::{DataBlock, Slave, Table};
+///
+/// let error = Slave::builder(0x01)
+///     .holding_registers("setpoints", DataBlock::new(0x0000, vec![0; 8]))
+///     .holding_registers("limits", DataBlock::new(0x0006, vec![0; 4]))
+///     .on_write(Table::HoldingRegisters, 0x0020, |_| {})
+///     .build()
+///     .unwrap_err();
+///
+/// assert_eq!(
+///     error.conflicts(),
+///     &[
+///         ModelConflict::Overlap {
+///             table: Table::HoldingRegisters,
+///             address: 0x0006,
+///             first: "setpoints".to_string(),
+///             second: "limits".to_string(),
+///         },
+///         ModelConflict::UnbackedCallback { table: Table::HoldingRegisters, address: 0x0020 },
+///     ]
+/// );
+/// println!("{error}");
+/// ```
+///
+#[derive(Debug)]
+pub struct SlaveBuilder {
+    modbus_id: u8,
+    coils: AddressSpace<bool>,
+    discrete_inputs: AddressSpace<bool>,
+    holding_registers: AddressSpace<u16>,
+    input_registers: AddressSpace<u16>,
+    callbacks: Vec<WriteCallback>,
+    broadcast: Option<BroadcastCallback>,
+    policies: BTreeMap<u8, FunctionPolicy>,
+    constraints: Vec<(u16, DataConstraint)>,
+    validators: Vec<WriteValidator>,
+    program_complete: Option<u16>,
+    record_accesses: bool,
+    replay: bool,
+}
+
+impl SlaveBuilder {
+    /// Starts describing a slave answering to `modbus_id`, with empty tables.
+    pub fn new(modbus_id: u8) -> Self {
+        Self {
+            modbus_id,
+            coils: AddressSpace::new(Table::Coils),
+            discrete_inputs: AddressSpace::new(Table::DiscreteInputs),
+            holding_registers: AddressSpace::new(Table::HoldingRegisters),
+            input_registers: AddressSpace::new(Table::InputRegisters),
+            callbacks: Vec::new(),
+            broadcast: None,
+            policies: BTreeMap::new(),
+            constraints: Vec::new(),
+            validators: Vec::new(),
+            program_complete: None,
+            record_accesses: false,
+            replay: false,
+        }
+    }
+
+    /// Adds a block of coils named `name`.
+    pub fn coils(mut self, name: impl Into<String>, block: DataBlock<bool>) -> Self {
+        self.coils.push(name.into(), block);
+        self
+    }
+
+    /// Adds a block of discrete inputs named `name`.
+    pub fn discrete_inputs(mut self, name: impl Into<String>, block: DataBlock<bool>) -> Self {
+        self.discrete_inputs.push(name.into(), block);
+        self
+    }
+
+    /// Adds a block of holding registers named `name`.
+    pub fn holding_registers(mut self, name: impl Into<String>, block: DataBlock<u16>) -> Self {
+        self.holding_registers.push(name.into(), block);
+        self
+    }
+
+    /// Adds a block of input registers named `name`.
+    pub fn input_registers(mut self, name: impl Into<String>, block: DataBlock<u16>) -> Self {
+        self.input_registers.push(name.into(), block);
+        self
+    }
+
+    /// Runs `callback` with the new value whenever a master writes `address`
+    /// of `table`. Coil values are passed as `0` or `1`.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{DataBlock, Function, Slave, Table};
+    /// use std::sync::atomic::{AtomicU16, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let setpoint = Arc::new(AtomicU16::new(0));
+    /// let observed = setpoint.clone();
+    /// let mut slave = Slave::builder(0x01)
+    ///     .holding_registers("setpoints", DataBlock::new(0x0000, vec![0; 4]))
+    ///     .on_write(Table::HoldingRegisters, 0x0002, move |value| {
+    ///         observed.store(value, Ordering::Relaxed)
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// slave.process(&Function::WriteMultipleRegisters { starting_address: 0x0001, value: Box::new([7, 42]) });
+    /// assert_eq!(setpoint.load(Ordering::Relaxed), 42);
+    /// ```
+    ///
+    pub fn on_write(
+        mut self,
+        table: Table,
+        address: u16,
+        callback: impl FnMut(u16) + Send + 'static,
+    ) -> Self {
+        self.callbacks.push(WriteCallback {
+            table,
+            address,
+            callback: Box::new(callback),
+        });
+        self
+    }
+
+    /// Runs `callback` whenever a broadcast request (slave id 0) was
+    /// received, with the function and the outcome of applying it.
+    ///
+    /// Broadcast writes change the tables and run the write callbacks like
+    /// addressed ones, but are never answered, so the outcome is the only
+    /// place where e.g. an [`Exception::IllegalDataAddress`](crate::Exception::IllegalDataAddress)
+    /// shows. Broadcast reads are meaningless and ignored.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{DataBlock, Function, Request, Response, Slave};
+    /// use std::sync::atomic::{AtomicU32, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// let broadcasts = Arc::new(AtomicU32::new(0));
+    /// let counter = broadcasts.clone();
+    /// let mut slave = Slave::builder(0x01)
+    ///     .holding_registers("setpoints", DataBlock::new(0x0000, vec![0; 4]))
+    ///     .on_broadcast(move |_, response| {
+    ///         assert!(!matches!(response, Response::Exception(_)));
+    ///         counter.fetch_add(1, Ordering::Relaxed);
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let write = Function::WriteSingleRegister { address: 0x0002, value: 42 };
+    /// let frame = Request::new(0x00, &write, std::time::Duration::ZERO).to_bytes().unwrap();
+    ///
+    /// assert_eq!(slave.handle(&frame), None);
+    /// assert_eq!(slave.holding_registers().get(0x0002), Some(&42));
+    /// assert_eq!(broadcasts.load(Ordering::Relaxed), 1);
+    /// ```
+    ///
+    pub fn on_broadcast(
+        mut self,
+        callback: impl FnMut(&Function, &Response) + Send + 'static,
+    ) -> Self {
+        self.broadcast = Some(BroadcastCallback(Box::new(callback)));
+        self
+    }
+
+    /// Sets how requests with `function_code` are treated, replacing any
+    /// previous policy for that code. Codes without a policy are served.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{test_vectors, DataBlock, FunctionPolicy, Slave};
+    ///
+    /// let mut slave = Slave::builder(0x11)
+    ///     .holding_registers("example", DataBlock::new(0x006B, vec![0x022B, 0x0000, 0x0064]))
+    ///     .function_policy(0x03, FunctionPolicy::Silent)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(slave.handle(test_vectors::READ_HOLDING_REGISTERS.request), None);
+    /// ```
+    ///
+    pub fn function_policy(mut self, function_code: u8, policy: FunctionPolicy) -> Self {
+        self.policies.insert(function_code, policy);
+        self
+    }
+
+    /// Restricts the values a master may write to the holding register at
+    /// `address` to those `constraint` allows. A register can carry several
+    /// constraints, which must all be satisfied.
+    ///
+    /// Writes are checked as a whole: when a single value of a Write
+    /// Multiple Registers request is not allowed, the request is rejected
+    /// with [`Exception::IllegalDataValue`](crate::Exception::IllegalDataValue)
+    /// and none of the values is written. Updates by the application through
+    /// [`Slave::holding_registers_mut`] are not checked.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{DataBlock, DataConstraint, Exception, Function, Response, Slave};
+    ///
+    /// let mut slave = Slave::builder(0x01)
+    ///     .holding_registers("config", DataBlock::new(0x0000, vec![0, 1, 100]))
+    ///     .constrain(0x0001, DataConstraint::Enum(&[1, 2, 5]))
+    ///     .constrain(0x0002, DataConstraint::Step { min: 100, max: 3000, step: 10 })
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let write = Function::WriteMultipleRegisters { starting_address: 0x0000, value: Box::new([7, 5, 1255]) };
+    /// assert_eq!(slave.process(&write), Response::Exception(Exception::IllegalDataValue));
+    /// assert_eq!(slave.holding_registers().range(0x0000, 3), Some(vec![0, 1, 100]));
+    ///
+    /// let write = Function::WriteMultipleRegisters { starting_address: 0x0000, value: Box::new([7, 5, 1250]) };
+    /// assert!(!matches!(slave.process(&write), Response::Exception(_)));
+    /// assert_eq!(slave.holding_registers().range(0x0000, 3), Some(vec![7, 5, 1250]));
+    /// ```
+    ///
+    /// Initial values are checked by [`build`](Self::build):
+    /// ```rust
+    /// use modbus_rtu::error::ModelConflict;
+    /// use modbus_rtu::{DataBlock, DataConstraint, Slave};
+    ///
+    /// let constraint = DataConstraint::Range { min: 1, max: 10 };
+    /// let error = Slave::builder(0x01)
+    ///     .holding_registers("config", DataBlock::new(0x0000, vec![0; 2]))
+    ///     .constrain(0x0001, constraint)
+    ///     .build()
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error.conflicts(),
+    ///     &[ModelConflict::InitialValue { address: 0x0001, value: 0, constraint }]
+    /// );
+    /// ```
+    ///
+    pub fn constrain(mut self, address: u16, constraint: DataConstraint) -> Self {
+        self.constraints.push((address, constraint));
+        self
+    }
+
+    /// Runs `validate` on every write of a master, with the slave, the
+    /// table, the first address and the written values, coils passed as `0`
+    /// or `1`. Validators run in the order they were added, after the
+    /// protocol checks and the [`DataConstraint`]s.
+    ///
+    /// The write is staged before the validators run, so the slave they see
+    /// already holds the new values. Returning an [`Exception`] rolls the
+    /// whole write back, leaving every value as it was, and answers the
+    /// request with it. Write callbacks only run once all validators passed.
+    ///
+    /// Validators cover rules spanning several registers, e.g. a minimum
+    /// that must not exceed a maximum, which single-register constraints
+    /// cannot express.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{DataBlock, Exception, Function, Response, Slave};
+    ///
+    /// // Register 0 holds a minimum and register 1 a maximum setpoint.
+    /// let mut slave = Slave::builder(0x01)
+    ///     .holding_registers("minimum", DataBlock::new(0x0000, vec![10]))
+    ///     .holding_registers("maximum", DataBlock::new(0x0001, vec![20]))
+    ///     .validate_write(|slave, _, _, _| {
+    ///         let registers = slave.holding_registers();
+    ///         if registers.get(0x0000) <= registers.get(0x0001) {
+    ///             Ok(())
+    ///         } else {
+    ///             Err(Exception::IllegalDataValue)
+    ///         }
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// let rejected = Response::Exception(Exception::IllegalDataValue);
+    ///
+    /// let write = Function::WriteSingleRegister { address: 0x0000, value: 25 };
+    /// assert_eq!(slave.process(&write), rejected);
+    ///
+    /// // Spanning both blocks, the first value passes on its own but the
+    /// // second breaks the rule, so neither is written.
+    /// let write = Function::WriteMultipleRegisters { starting_address: 0x0000, value: Box::new([15, 12]) };
+    /// assert_eq!(slave.process(&write), rejected);
+    /// assert_eq!(slave.holding_registers().range(0x0000, 2), Some(vec![10, 20]));
+    ///
+    /// let write = Function::WriteMultipleRegisters { starting_address: 0x0000, value: Box::new([25, 30]) };
+    /// assert!(!matches!(slave.process(&write), Response::Exception(_)));
+    /// assert_eq!(slave.holding_registers().range(0x0000, 2), Some(vec![25, 30]));
+    /// ```
+    ///
+    pub fn validate_write(
+        mut self,
+        validate: impl Fn(&Slave, Table, u16, &[u16]) -> Result<(), Exception> + Send + 'static,
+    ) -> Self {
+        self.validators.push(WriteValidator(Box::new(validate)));
+        self
+    }
+
+    /// Makes the input register at `address` report whether every request
+    /// accepted under [`FunctionPolicy::Acknowledge`] was completed: it reads
+    /// 1 once they all were and 0 while one is pending, so a master can poll
+    /// it for program completion. See [`Slave::complete_pending`].
+    pub fn program_complete_register(mut self, address: u16) -> Self {
+        self.program_complete = Some(address);
+        self
+    }
+
+    /// Counts the reads and writes of every address, see [`AccessProfile`].
+    pub fn record_accesses(mut self) -> Self {
+        self.record_accesses = true;
+        self
+    }
+
+    /// Answers a write request repeated byte for byte with the cached
+    /// response instead of executing it again, see [`Slave::handle`].
+    pub fn replay_duplicate_writes(mut self) -> Self {
+        self.replay = true;
+        self
+    }
+
+    /// Validates the description and creates the slave.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns a [`ModelError`] listing every [`ModelConflict`] found.
+    pub fn build(mut self) -> Result<Slave, ModelError> {
+        let mut conflicts = Vec::new();
+        check_blocks(&self.coils, &mut conflicts);
+        check_blocks(&self.discrete_inputs, &mut conflicts);
+        check_blocks(&self.holding_registers, &mut conflicts);
+        check_blocks(&self.input_registers, &mut conflicts);
+        for callback in &self.callbacks {
+            let (table, address) = (callback.table, callback.address);
+            let backed = match table {
+                Table::Coils => self.coils.contains(address, 1),
+                Table::DiscreteInputs => self.discrete_inputs.contains(address, 1),
+                Table::HoldingRegisters => self.holding_registers.contains(address, 1),
+                Table::InputRegisters => self.input_registers.contains(address, 1),
+            };
+            if !table.is_writable() {
+                conflicts.push(ModelConflict::ReadOnlyCallback { table, address });
+            } else if !backed {
+                conflicts.push(ModelConflict::UnbackedCallback { table, address });
+            }
+        }
+        for (&function_code, &policy) in &self.policies {
+            let write = crate::FunctionKind::from_code(function_code)
+                .is_some_and(|kind| !kind.is_read() && kind.table().is_some());
+            if policy == FunctionPolicy::Acknowledge && !write {
+                conflicts.push(ModelConflict::AcknowledgedNonWrite { function_code });
+            }
+        }
+        for &(address, constraint) in &self.constraints {
+            match self.holding_registers.get(address) {
+                None => conflicts.push(ModelConflict::UnbackedConstraint { address }),
+                Some(&value) if !constraint.allows(value) => {
+                    conflicts.push(ModelConflict::InitialValue {
+                        address,
+                        value,
+                        constraint,
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+        if let Some(address) = self.program_complete {
+            match self.input_registers.get_mut(address) {
+                Some(value) => *value = 1,
+                None => conflicts.push(ModelConflict::UnbackedProgramComplete { address }),
+            }
+        }
+        if !conflicts.is_empty() {
+            return Err(ModelError { conflicts });
+        }
+        Ok(Slave {
+            modbus_id: self.modbus_id,
+            coils: self.coils,
+            discrete_inputs: self.discrete_inputs,
+            holding_registers: self.holding_registers,
+            input_registers: self.input_registers,
+            callbacks: self.callbacks,
+            broadcast: self.broadcast,
+            policies: self.policies,
+            constraints: self.constraints,
+            validators: self.validators,
+            pending: Vec::new(),
+            program_complete: self.program_complete,
+            profile: self.record_accesses.then(AccessProfile::default),
+            injections: BTreeMap::new(),
+            replay: self.replay,
+            last_write: None,
+        })
+    }
+}
+
+
+/// Reports blocks of `space` that share a name, once per name, or declare
+/// the same address.
+fn check_blocks<T>(space: &AddressSpace<T>, conflicts: &mut Vec<ModelConflict>) {
+    let table = space.table();
+    let named: Vec<_> = space.named().collect();
+    for (i, (name, _)) in named.iter().enumerate() {
+        if named[..i].iter().any(|(earlier, _)| earlier == name) {
+            continue;
+        }
+        let starts: Vec<u16> = named[i..]
+            .iter()
+            .filter(|(other, _)| other == name)
+            .map(|(_, block)| block.start())
+            .collect();
+        if starts.len() > 1 {
+            conflicts.push(ModelConflict::DuplicateName {
+                table,
+                name: name.to_string(),
+                starts,
+            });
+        }
+    }
+    let blocks: Vec<_> = space.blocks().collect();
+    for (i, (first, _, a)) in blocks.iter().enumerate() {
+        for (second, _, b) in &blocks[i + 1..] {
+            if a.start() <= b.end() && b.start() <= a.end() {
+                conflicts.push(ModelConflict::Overlap {
+                    table,
+                    address: *a.start().max(b.start()),
+                    first: first.to_string(),
+                    second: second.to_string(),
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+/// ## DataConstraint
+///
+/// Values a master may write to a holding register, attached with
+/// [`SlaveBuilder::constrain`](crate::SlaveBuilder::constrain).
+///
+/// Writes of a value the constraint does not allow are rejected with
+/// [`Exception::IllegalDataValue`](crate::Exception::IllegalDataValue), and
+/// the register keeps its value.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::DataConstraint;
+///
+/// let mode = DataConstraint::Enum(&[1, 2, 5]);
+/// assert!(mode.allows(2) && !mode.allows(3));
+///
+/// let speed = DataConstraint::Step { min: 100, max: 3000, step: 10 };
+/// assert!(speed.allows(1250) && !speed.allows(1255) && !speed.allows(3010));
+/// assert_eq!(speed.to_string(), "100..=3000 step 10");
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataConstraint {
+    /// The value must lie within `min..=max`.
+    Range { min: u16, max: u16 },
+
+    /// The value must be one of the listed values.
+    Enum(&'static [u16]),
+
+    /// The value must lie within `min..=max` and be `min` plus a multiple
+    /// of `step`. A `step` of 0 allows `min` only.
+    Step { min: u16, max: u16, step: u16 },
+}
+
+impl DataConstraint {
+    /// Returns `true` when `value` satisfies the constraint.
+    pub fn allows(&self, value: u16) -> bool {
+        match *self {
+            Self::Range { min, max } => (min..=max).contains(&value),
+            Self::Enum(values) => values.contains(&value),
+            Self::Step { min, max, step } => {
+                (min..=max).contains(&value)
+                    && match (value - min).checked_rem(step) {
+                        Some(rest) => rest == 0,
+                        None => value == min,
+                    }
+            }
+        }
+    }
+}
+
+impl core::fmt::Display for DataConstraint {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Range { min, max } => write!(f, "{min}..={max}"),
+            Self::Enum(values) => {
+                f.write_str("one of ")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                Ok(())
+            }
+            Self::Step { min, max, step } => write!(f, "{min}..={max} step {step}"),
+        }
+    }
+}
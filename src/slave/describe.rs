@@ -0,0 +1,212 @@
+use crate::{DataConstraint, Slave, Table};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+
+
+/// ## RegisterMap
+///
+/// Machine-readable description of the data model of a [`Slave`], returned
+/// by [`Slave::describe`], e.g. to generate the register sheet of a device
+/// from its firmware.
+///
+/// Every block is described by one [`RegisterDescription`], split where the
+/// [`DataConstraint`]s change, so a constrained register gets an entry of its
+/// own. [`to_csv`](Self::to_csv) and [`to_markdown`](Self::to_markdown)
+/// render the entries as a table.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{DataBlock, DataConstraint, Slave};
+///
+/// let slave = Slave::builder(0x01)
+///     .holding_registers("config", DataBlock::new(0x0000, vec![1, 0, 0, 0]))
+///     .constrain(0x0000, DataConstraint::Enum(&[1, 2, 5]))
+///     .input_registers("telemetry", DataBlock::new(0x1000, vec![0; 0x40]))
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(
+///     slave.describe().to_csv(),
+///     "table,start,end,name,access,constraint\n\
+///      holding_registers,0x0000,0x0000,config,read-write,\"one of 1, 2, 5\"\n\
+///      holding_registers,0x0001,0x0003,config,read-write,\n\
+///      input_registers,0x1000,0x103F,telemetry,read-only,\n"
+/// );
+/// assert_eq!(
+///     slave.describe().to_markdown(),
+///     "| Table | Addresses | Name | Access | Constraint |\n\
+///      |---|---|---|---|---|\n\
+///      | Holding Registers | 0x0000 | config | read-write | one of 1, 2, 5 |\n\
+///      | Holding Registers | 0x0001..=0x0003 | config | read-write |  |\n\
+///      | Input Registers | 0x1000..=0x103F | telemetry | read-only |  |\n"
+/// );
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterMap {
+    entries: Vec<RegisterDescription>,
+}
+
+impl RegisterMap {
+    /// Returns the entries, table by table and in declaration order within
+    /// each table.
+    pub fn entries(&self) -> &[RegisterDescription] {
+        &self.entries
+    }
+
+    /// Renders the entries as CSV with a header row, one line per entry.
+    /// Addresses are written in hexadecimal and tables in `snake_case`;
+    /// fields containing a comma or a quote are quoted.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("table,start,end,name,access,constraint\n");
+        for entry in &self.entries {
+            let _ = writeln!(
+                csv,
+                "{},0x{:04X},0x{:04X},{},{},{}",
+                table_key(entry.table),
+                entry.start,
+                entry.end,
+                csv_field(&entry.name),
+                entry.access(),
+                csv_field(&entry.constraint_text()),
+            );
+        }
+        csv
+    }
+
+    /// Renders the entries as a Markdown table.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::from(
+            "| Table | Addresses | Name | Access | Constraint |\n|---|---|---|---|---|\n",
+        );
+        for entry in &self.entries {
+            let addresses = if entry.start == entry.end {
+                format!("0x{:04X}", entry.start)
+            } else {
+                format!("0x{:04X}..=0x{:04X}", entry.start, entry.end)
+            };
+            let _ = writeln!(
+                markdown,
+                "| {} | {addresses} | {} | {} | {} |",
+                entry.table,
+                entry.name.replace('|', "\\|"),
+                entry.access(),
+                entry.constraint_text(),
+            );
+        }
+        markdown
+    }
+}
+
+
+/// ## RegisterDescription
+///
+/// One entry of a [`RegisterMap`]: a run of addresses of one block sharing
+/// the same constraints.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisterDescription {
+    table: Table,
+    name: String,
+    start: u16,
+    end: u16,
+    constraints: Vec<DataConstraint>,
+}
+
+impl RegisterDescription {
+    /// Returns the table of the addresses.
+    pub fn table(&self) -> Table {
+        self.table
+    }
+
+    /// Returns the name of the block the addresses belong to.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the addresses described.
+    pub fn addresses(&self) -> core::ops::RangeInclusive<u16> {
+        self.start..=self.end
+    }
+
+    /// Returns `true` when a master can write the addresses.
+    pub fn is_writable(&self) -> bool {
+        self.table.is_writable()
+    }
+
+    /// Returns the constraints every address of the entry carries.
+    pub fn constraints(&self) -> &[DataConstraint] {
+        &self.constraints
+    }
+
+    /// Returns the access as written in the exports.
+    fn access(&self) -> &'static str {
+        if self.is_writable() {
+            "read-write"
+        } else {
+            "read-only"
+        }
+    }
+
+    /// Returns the constraints as written in the exports.
+    fn constraint_text(&self) -> String {
+        let texts: Vec<String> = self.constraints.iter().map(ToString::to_string).collect();
+        texts.join("; ")
+    }
+}
+
+
+impl Slave {
+    /// Describes every block of the data model with its addresses, access
+    /// and constraints, see [`RegisterMap`].
+    pub fn describe(&self) -> RegisterMap {
+        let mut entries: Vec<RegisterDescription> = Vec::new();
+        for (name, table, range) in self.blocks() {
+            let first = entries.len();
+            for address in range {
+                let constraints: Vec<DataConstraint> = match table {
+                    Table::HoldingRegisters => self.constraints(address).copied().collect(),
+                    _ => Vec::new(),
+                };
+                match entries[first..].last_mut() {
+                    Some(entry) if entry.constraints == constraints => entry.end = address,
+                    _ => entries.push(RegisterDescription {
+                        table,
+                        name: name.to_string(),
+                        start: address,
+                        end: address,
+                        constraints,
+                    }),
+                }
+            }
+        }
+        RegisterMap { entries }
+    }
+}
+
+
+/// Returns the `snake_case` name of `table`, as serialized with `serde`.
+fn table_key(table: Table) -> &'static str {
+    match table {
+        Table::Coils => "coils",
+        Table::DiscreteInputs => "discrete_inputs",
+        Table::HoldingRegisters => "holding_registers",
+        Table::InputRegisters => "input_registers",
+    }
+}
+
+
+/// Quotes `field` for CSV when it contains a separator, a quote or a line
+/// break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,620 @@
+//! Slave-side request dispatch over an in-memory data model.
+
+use super::builder::{BroadcastCallback, WriteCallback, WriteValidator};
+use crate::error::RequestFrameError;
+use crate::limits::{MAX_READ_COILS, MAX_READ_REGISTERS, MAX_WRITE_COILS, MAX_WRITE_REGISTERS};
+use crate::{
+    AccessProfile, AddressSpace, DataConstraint, Exception, Function, FunctionPolicy, Response,
+    SlaveBuilder, Table,
+};
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+
+
+/// A request frame and the response frame it was answered with.
+type Exchange = (Box<[u8]>, Box<[u8]>);
+
+
+/// ## Slave
+///
+/// Serves the four Modbus tables of one device, turning request frames into
+/// response frames. Slaves are described and validated with a
+/// [`SlaveBuilder`], see [`Slave::builder`].
+///
+/// Requests are checked in the order given by the Modbus application protocol
+/// specification: an unsupported function code is answered with
+/// [`Exception::IllegalFunction`], a quantity outside the protocol limits
+/// with [`Exception::IllegalDataValue`], and a range containing an address
+/// that no block defines (including ranges that overlap a block only
+/// partially) with [`Exception::IllegalDataAddress`], and a write of a value
+/// that a [`DataConstraint`] does not allow with
+/// [`Exception::IllegalDataValue`]. Write validators, see
+/// [`SlaveBuilder::validate_write`], run last, on the model with the write
+/// staged. Writes are all or nothing: a rejected request leaves every value
+/// in place, however many of them passed. Frames addressed to
+/// other slaves or failing the CRC check are ignored. Broadcast writes are
+/// applied without an answer, see [`SlaveBuilder::on_broadcast`]. Single
+/// function codes can be refused or ignored with a [`FunctionPolicy`].
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{test_vectors, DataBlock, Slave};
+///
+/// // Holding registers 108-110 of the specification example.
+/// let mut slave = Slave::builder(0x11)
+///     .holding_registers("example", DataBlock::new(0x006B, vec![0x022B, 0x0000, 0x0064]))
+///     .build()
+///     .unwrap();
+///
+/// let vector = test_vectors::READ_HOLDING_REGISTERS;
+/// assert_eq!(slave.handle(vector.request).as_deref(), Some(vector.response));
+///
+/// // Coils are not defined at all, so every coil address is illegal.
+/// let vector = test_vectors::EXCEPTION_ILLEGAL_DATA_ADDRESS;
+/// assert_eq!(slave.handle(vector.request).as_deref(), Some(vector.response));
+///
+/// // Frames for other slaves stay unanswered.
+/// assert_eq!(slave.handle(&[0x01, 0x03, 0x00, 0x00, 0x00, 0x02, 0xC4, 0x0B]), None);
+/// ```
+///
+#[derive(Debug)]
+pub struct Slave {
+    pub(crate) modbus_id: u8,
+    pub(crate) coils: AddressSpace<bool>,
+    pub(crate) discrete_inputs: AddressSpace<bool>,
+    pub(crate) holding_registers: AddressSpace<u16>,
+    pub(crate) input_registers: AddressSpace<u16>,
+    pub(crate) callbacks: Vec<WriteCallback>,
+    pub(crate) broadcast: Option<BroadcastCallback>,
+    pub(crate) policies: BTreeMap<u8, FunctionPolicy>,
+    pub(crate) constraints: Vec<(u16, DataConstraint)>,
+    pub(crate) validators: Vec<WriteValidator>,
+    pub(crate) pending: Vec<Function>,
+    pub(crate) program_complete: Option<u16>,
+    pub(crate) profile: Option<AccessProfile>,
+    pub(crate) injections: BTreeMap<u8, (Exception, u32)>,
+    pub(crate) replay: bool,
+    pub(crate) last_write: Option<Exchange>,
+}
+
+impl Slave {
+    /// Starts describing a slave answering to `modbus_id`.
+    pub fn builder(modbus_id: u8) -> SlaveBuilder {
+        SlaveBuilder::new(modbus_id)
+    }
+
+    /// Returns the id this slave answers to.
+    pub fn modbus_id(&self) -> u8 {
+        self.modbus_id
+    }
+
+    /// Returns the policy for requests with `function_code`.
+    pub fn function_policy(&self, function_code: u8) -> FunctionPolicy {
+        self.policies
+            .get(&function_code)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Answers the next `count` requests with `function_code` addressed to
+    /// this slave with `exception` instead of serving them, to test how a
+    /// master handles exceptions and retries. A `count` of 0 withdraws the
+    /// injection. Broadcasts and functions made [`Silent`](FunctionPolicy::Silent)
+    /// are not affected.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{DataBlock, Exception, Function, Request, Response, Slave};
+    ///
+    /// let mut slave = Slave::builder(0x01)
+    ///     .holding_registers("config", DataBlock::new(0x0000, vec![0; 4]))
+    ///     .build()
+    ///     .unwrap();
+    /// slave.inject_exception(0x03, Exception::DeviceBusy, 2);
+    ///
+    /// let function = Function::ReadHoldingRegisters { starting_address: 0x0000, quantity: 1 };
+    /// let request = Request::new(0x01, &function, std::time::Duration::from_millis(100));
+    /// let frame = request.to_bytes().unwrap();
+    /// let mut respond = || Response::from_bytes(&request, &slave.handle(&frame).unwrap()).unwrap();
+    ///
+    /// assert_eq!(respond(), Response::Exception(Exception::DeviceBusy));
+    /// assert_eq!(respond(), Response::Exception(Exception::DeviceBusy));
+    /// assert_eq!(respond(), Response::Value(Box::new([0])));
+    /// ```
+    ///
+    pub fn inject_exception(&mut self, function_code: u8, exception: Exception, count: u32) {
+        if count == 0 {
+            self.injections.remove(&function_code);
+        } else {
+            self.injections.insert(function_code, (exception, count));
+        }
+    }
+
+    /// Returns the constraints on the holding register at `address`.
+    pub fn constraints(&self, address: u16) -> impl Iterator<Item = &DataConstraint> {
+        self.constraints
+            .iter()
+            .filter(move |(a, _)| *a == address)
+            .map(|(_, constraint)| constraint)
+    }
+
+    /// Returns the name, table and address range of every block, table by
+    /// table in the order coils, discrete inputs, holding registers, input
+    /// registers, see [`AddressSpace::blocks`].
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{DataBlock, Slave, Table};
+    ///
+    /// let slave = Slave::builder(0x01)
+    ///     .holding_registers("config", DataBlock::new(0x0000, vec![0; 0x20]))
+    ///     .input_registers("telemetry", DataBlock::new(0x1000, vec![0; 0x40]))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// for (name, table, range) in slave.blocks() {
+    ///     println!("{name}: {table} 0x{:04X}..=0x{:04X}", range.start(), range.end());
+    /// }
+    /// assert_eq!(slave.blocks().nth(1), Some(("telemetry", Table::InputRegisters, 0x1000..=0x103F)));
+    /// ```
+    ///
+    pub fn blocks(&self) -> impl Iterator<Item = (&str, Table, core::ops::RangeInclusive<u16>)> {
+        self.coils
+            .blocks()
+            .chain(self.discrete_inputs.blocks())
+            .chain(self.holding_registers.blocks())
+            .chain(self.input_registers.blocks())
+    }
+
+    /// Returns the coil table.
+    pub fn coils(&self) -> &AddressSpace<bool> {
+        &self.coils
+    }
+
+    /// Returns the coil table for updates by the application.
+    pub fn coils_mut(&mut self) -> &mut AddressSpace<bool> {
+        &mut self.coils
+    }
+
+    /// Returns the discrete input table.
+    pub fn discrete_inputs(&self) -> &AddressSpace<bool> {
+        &self.discrete_inputs
+    }
+
+    /// Returns the discrete input table for updates by the application.
+    pub fn discrete_inputs_mut(&mut self) -> &mut AddressSpace<bool> {
+        &mut self.discrete_inputs
+    }
+
+    /// Returns the holding register table.
+    pub fn holding_registers(&self) -> &AddressSpace<u16> {
+        &self.holding_registers
+    }
+
+    /// Returns the holding register table for updates by the application.
+    pub fn holding_registers_mut(&mut self) -> &mut AddressSpace<u16> {
+        &mut self.holding_registers
+    }
+
+    /// Returns the input register table.
+    pub fn input_registers(&self) -> &AddressSpace<u16> {
+        &self.input_registers
+    }
+
+    /// Returns the input register table for updates by the application.
+    pub fn input_registers_mut(&mut self) -> &mut AddressSpace<u16> {
+        &mut self.input_registers
+    }
+
+    /// Handles a complete request frame and returns the response frame to
+    /// send, or [`None`] when the frame must stay unanswered.
+    ///
+    /// With [`SlaveBuilder::replay_duplicate_writes`], a write request
+    /// repeated byte for byte right after it was executed, typically a retry
+    /// after the response got lost, is not executed again; the cached
+    /// response is sent instead.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{DataBlock, Function, Request, Slave};
+    ///
+    /// let mut slave = Slave::builder(0x01)
+    ///     .holding_registers("config", DataBlock::new(0x0000, vec![0; 4]))
+    ///     .replay_duplicate_writes()
+    ///     .build()
+    ///     .unwrap();
+    /// let function = Function::WriteSingleRegister { address: 0x0000, value: 1 };
+    /// let frame = Request::new(0x01, &function, std::time::Duration::from_millis(100))
+    ///     .to_bytes()
+    ///     .unwrap();
+    ///
+    /// let response = slave.handle(&frame);
+    /// // The application consumed the value meanwhile.
+    /// slave.holding_registers_mut().write(0x0000, &[0]);
+    ///
+    /// // The retry is answered, but the value is not written a second time.
+    /// assert_eq!(slave.handle(&frame), response);
+    /// assert_eq!(slave.holding_registers().get(0x0000), Some(&0));
+    /// ```
+    ///
+    pub fn handle(&mut self, frame: &[u8]) -> Option<Box<[u8]>> {
+        if !self.replay || frame.first() != Some(&self.modbus_id) {
+            return self.answer(frame);
+        }
+        if let Some((request, response)) = &self.last_write
+            && **request == *frame
+        {
+            return Some(response.clone());
+        }
+        // Unanswered frames, e.g. truncated by noise, neither get cached nor
+        // drop the cached write.
+        let response = self.answer(frame)?;
+        // Only executed writes are worth replaying; a repeated read must see
+        // fresh values and a rejected write changed nothing.
+        let write = crate::FunctionKind::from_code(frame[1])
+            .is_some_and(|kind| !kind.is_read() && kind.table().is_some());
+        self.last_write =
+            (write && response[1] == frame[1]).then(|| (frame.into(), response.clone()));
+        Some(response)
+    }
+
+    /// Answers `frame` without looking at the replay cache.
+    fn answer(&mut self, frame: &[u8]) -> Option<Box<[u8]>> {
+        let policy = self.function_policy(*frame.get(1)?);
+        if policy == FunctionPolicy::Silent {
+            return None;
+        }
+        let response = match crate::frame::parse_request(frame) {
+            Ok((0, function)) => {
+                match policy {
+                    FunctionPolicy::Respond => self.receive_broadcast(&function),
+                    FunctionPolicy::Acknowledge => drop(self.acknowledge(function)),
+                    _ => {}
+                }
+                return None;
+            }
+            Ok((modbus_id, function)) if modbus_id == self.modbus_id => {
+                match (self.take_injection(frame[1]), policy) {
+                    (Some(exception), _) => Response::Exception(exception),
+                    (None, FunctionPolicy::Respond) => self.process(&function),
+                    (None, FunctionPolicy::Acknowledge) => self.acknowledge(function),
+                    (None, _) => Response::Exception(Exception::IllegalFunction),
+                }
+            }
+            Err(RequestFrameError::UnsupportedFunction(_)) if frame[0] == self.modbus_id => {
+                Response::Exception(Exception::IllegalFunction)
+            }
+            Err(RequestFrameError::InvalidFormat) if frame[0] == self.modbus_id => {
+                Response::Exception(Exception::IllegalDataValue)
+            }
+            _ => return None,
+        };
+        crate::frame::encode_response(self.modbus_id, frame[1], &response)
+    }
+
+    /// Executes `function` against the tables and returns the response,
+    /// which is [`Response::Exception`] when the request is rejected.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{DataBlock, Exception, Function, Response, Slave};
+    ///
+    /// // Registers 0x0100..=0x0103.
+    /// let mut slave = Slave::builder(0x01)
+    ///     .input_registers("measurements", DataBlock::new(0x0100, vec![10, 11, 12, 13]))
+    ///     .build()
+    ///     .unwrap();
+    /// let mut read = |starting_address, quantity| {
+    ///     slave.process(&Function::ReadInputRegisters { starting_address, quantity })
+    /// };
+    /// let illegal = Response::Exception(Exception::IllegalDataAddress);
+    ///
+    /// // First and last defined address, and the whole block.
+    /// assert_eq!(read(0x0100, 1), Response::Value(Box::new([10])));
+    /// assert_eq!(read(0x0103, 1), Response::Value(Box::new([13])));
+    /// assert_eq!(read(0x0100, 4), Response::Value(Box::new([10, 11, 12, 13])));
+    ///
+    /// // Just outside either end, and partial overlaps.
+    /// assert_eq!(read(0x00FF, 1), illegal);
+    /// assert_eq!(read(0x0104, 1), illegal);
+    /// assert_eq!(read(0x00FF, 2), illegal);
+    /// assert_eq!(read(0x0103, 2), illegal);
+    /// assert_eq!(read(0x00FF, 6), illegal);
+    ///
+    /// // Quantity is checked before the address.
+    /// assert_eq!(read(0xFFFF, 0), Response::Exception(Exception::IllegalDataValue));
+    /// assert_eq!(read(0x0100, 126), Response::Exception(Exception::IllegalDataValue));
+    /// ```
+    ///
+    pub fn process(&mut self, function: &Function) -> Response {
+        let result = self.execute(function, true);
+        if result.is_ok()
+            && let Some(profile) = self.profile.as_mut()
+        {
+            profile.record(function);
+        }
+        result.unwrap_or_else(Response::Exception)
+    }
+
+    /// Runs every check of `function` and, when `apply` is set, executes it
+    /// against the tables; otherwise the tables are left as they were.
+    pub(crate) fn execute(
+        &mut self,
+        function: &Function,
+        apply: bool,
+    ) -> Result<Response, Exception> {
+        match function {
+            Function::ReadCoils {
+                starting_address,
+                quantity,
+            } => read(&self.coils, *starting_address, *quantity, MAX_READ_COILS)
+                .map(Response::Status),
+            Function::ReadDiscreteInputs {
+                starting_address,
+                quantity,
+            } => read(
+                &self.discrete_inputs,
+                *starting_address,
+                *quantity,
+                MAX_READ_COILS,
+            )
+            .map(Response::Status),
+            Function::ReadHoldingRegisters {
+                starting_address,
+                quantity,
+            } => read(
+                &self.holding_registers,
+                *starting_address,
+                *quantity,
+                MAX_READ_REGISTERS,
+            )
+            .map(Response::Value),
+            Function::ReadInputRegisters {
+                starting_address,
+                quantity,
+            } => read(
+                &self.input_registers,
+                *starting_address,
+                *quantity,
+                MAX_READ_REGISTERS,
+            )
+            .map(Response::Value),
+            Function::WriteSingleCoil { address, value } => self
+                .write_coils(*address, &[*value], 1, apply)
+                .map(|_| Response::WriteEcho {
+                    address: *address,
+                    value_or_quantity: if *value { 0xFF00 } else { 0x0000 },
+                }),
+            Function::WriteSingleRegister { address, value } => self
+                .write_holding_registers(*address, &[*value], 1, apply)
+                .map(|_| Response::WriteEcho {
+                    address: *address,
+                    value_or_quantity: *value,
+                }),
+            Function::WriteMultipleCoils {
+                starting_address,
+                value,
+            } => self
+                .write_coils(*starting_address, value, MAX_WRITE_COILS, apply)
+                .map(|_| Response::WriteEcho {
+                    address: *starting_address,
+                    value_or_quantity: value.len() as u16,
+                }),
+            Function::WriteMultipleRegisters {
+                starting_address,
+                value,
+            } => self
+                .write_holding_registers(*starting_address, value, MAX_WRITE_REGISTERS, apply)
+                .map(|_| Response::WriteEcho {
+                    address: *starting_address,
+                    value_or_quantity: value.len() as u16,
+                }),
+            Function::EncapsulatedInterface { .. } => Err(Exception::IllegalFunction),
+        }
+    }
+
+    /// Returns the accesses counted so far, when enabled with
+    /// [`SlaveBuilder::record_accesses`].
+    pub fn access_profile(&self) -> Option<&AccessProfile> {
+        self.profile.as_ref()
+    }
+
+    /// Returns the accesses counted so far for resetting them, when enabled
+    /// with [`SlaveBuilder::record_accesses`].
+    pub fn access_profile_mut(&mut self) -> Option<&mut AccessProfile> {
+        self.profile.as_mut()
+    }
+
+    /// Uses up one exception injected for `function_code`, if any.
+    fn take_injection(&mut self, function_code: u8) -> Option<Exception> {
+        let (exception, count) = *self.injections.get(&function_code)?;
+        self.inject_exception(function_code, exception, count - 1);
+        Some(exception)
+    }
+
+    /// Writes `values` to the coils from `start` once every check passed,
+    /// and runs the write callbacks; only checks when `apply` is not set.
+    fn write_coils(
+        &mut self,
+        start: u16,
+        values: &[bool],
+        max: u16,
+        apply: bool,
+    ) -> Result<(), Exception> {
+        check_write(&self.coils, start, values.len(), max)?;
+        let words: Vec<u16> = values.iter().map(|v| *v as u16).collect();
+        self.commit(|slave| &mut slave.coils, start, values, &words, apply)
+    }
+
+    /// Writes `values` to the holding registers from `start` once every
+    /// check passed, and runs the write callbacks; only checks when `apply`
+    /// is not set.
+    fn write_holding_registers(
+        &mut self,
+        start: u16,
+        values: &[u16],
+        max: u16,
+        apply: bool,
+    ) -> Result<(), Exception> {
+        check_write(&self.holding_registers, start, values.len(), max)?;
+        if !(0..)
+            .zip(values)
+            .all(|(offset, value)| allows(&self.constraints, start + offset, *value))
+        {
+            return Err(Exception::IllegalDataValue);
+        }
+        self.commit(
+            |slave| &mut slave.holding_registers,
+            start,
+            values,
+            values,
+            apply,
+        )
+    }
+
+    /// Stages `values` in the table `space` selects, runs the write
+    /// validators on the staged model, and either keeps the write and runs
+    /// the write callbacks with `words`, or restores the previous values.
+    /// Without `apply`, the previous values are restored in any case.
+    fn commit<T: Copy>(
+        &mut self,
+        space: fn(&mut Slave) -> &mut AddressSpace<T>,
+        start: u16,
+        values: &[T],
+        words: &[u16],
+        apply: bool,
+    ) -> Result<(), Exception> {
+        let table = space(self).table();
+        let previous = space(self)
+            .range(start, values.len() as u16)
+            .ok_or(Exception::IllegalDataAddress)?;
+        space(self).write(start, values);
+        if let Err(exception) = self
+            .validators
+            .iter()
+            .try_for_each(|WriteValidator(validate)| validate(self, table, start, words))
+        {
+            space(self).write(start, &previous);
+            return Err(exception);
+        }
+        if apply {
+            self.notify(table, start, words);
+        } else {
+            space(self).write(start, &previous);
+        }
+        Ok(())
+    }
+
+    /// Applies a broadcast request, which is never answered, and reports it
+    /// to the broadcast callback.
+    fn receive_broadcast(&mut self, function: &Function) {
+        if function.kind().is_read() {
+            return;
+        }
+        let response = self.process(function);
+        if let Some(BroadcastCallback(callback)) = self.broadcast.as_mut() {
+            callback(function, &response);
+        }
+    }
+
+    /// Runs the write callbacks attached to `values` written from `start`
+    /// to `table`.
+    fn notify(&mut self, table: Table, start: u16, values: &[u16]) {
+        for callback in self.callbacks.iter_mut().filter(|c| c.table == table) {
+            if let Some(offset) = callback.address.checked_sub(start)
+                && let Some(value) = values.get(offset as usize)
+            {
+                (callback.callback)(*value);
+            }
+        }
+    }
+}
+
+
+/// Returns the table, starting address and number of items `function`
+/// accesses, or [`None`] for functions outside the four tables.
+pub(crate) fn span(function: &Function) -> Option<(Table, u16, u16)> {
+    Some(match function {
+        Function::ReadCoils {
+            starting_address,
+            quantity,
+        } => (Table::Coils, *starting_address, *quantity),
+        Function::ReadDiscreteInputs {
+            starting_address,
+            quantity,
+        } => (Table::DiscreteInputs, *starting_address, *quantity),
+        Function::ReadHoldingRegisters {
+            starting_address,
+            quantity,
+        } => (Table::HoldingRegisters, *starting_address, *quantity),
+        Function::ReadInputRegisters {
+            starting_address,
+            quantity,
+        } => (Table::InputRegisters, *starting_address, *quantity),
+        Function::WriteSingleCoil { address, .. } => (Table::Coils, *address, 1),
+        Function::WriteSingleRegister { address, .. } => (Table::HoldingRegisters, *address, 1),
+        Function::WriteMultipleCoils {
+            starting_address,
+            value,
+        } => (Table::Coils, *starting_address, value.len() as u16),
+        Function::WriteMultipleRegisters {
+            starting_address,
+            value,
+        } => (
+            Table::HoldingRegisters,
+            *starting_address,
+            value.len() as u16,
+        ),
+        Function::EncapsulatedInterface { .. } => return None,
+    })
+}
+
+
+/// Reads `quantity` values from `start`, enforcing the protocol limit `max`
+/// before the address range.
+fn read<T: Copy>(
+    space: &AddressSpace<T>,
+    start: u16,
+    quantity: u16,
+    max: u16,
+) -> Result<Box<[T]>, Exception> {
+    if !(1..=max).contains(&quantity) {
+        return Err(Exception::IllegalDataValue);
+    }
+    space
+        .range(start, quantity)
+        .map(Into::into)
+        .ok_or(Exception::IllegalDataAddress)
+}
+
+
+/// Checks a write of `len` values from `start`, enforcing the protocol limit
+/// `max` before the address range.
+fn check_write<T>(
+    space: &AddressSpace<T>,
+    start: u16,
+    len: usize,
+    max: u16,
+) -> Result<(), Exception> {
+    if len == 0 || len > max as usize {
+        return Err(Exception::IllegalDataValue);
+    }
+    if !space.contains(start, len as u16) {
+        return Err(Exception::IllegalDataAddress);
+    }
+    Ok(())
+}
+
+
+/// Returns `true` when every constraint on the holding register at `address`
+/// allows `value`.
+fn allows(constraints: &[(u16, DataConstraint)], address: u16, value: u16) -> bool {
+    constraints
+        .iter()
+        .filter(|(a, _)| *a == address)
+        .all(|(_, constraint)| constraint.allows(value))
+}
@@ -0,0 +1,30 @@
+mod space;
+pub use space::*;
+
+mod builder;
+pub use builder::SlaveBuilder;
+
+mod policy;
+pub use policy::FunctionPolicy;
+
+mod constraint;
+pub use constraint::DataConstraint;
+
+mod handler;
+pub use handler::*;
+
+mod pending;
+
+mod profile;
+pub use profile::{AccessCount, AccessProfile};
+
+mod router;
+pub use router::{FrameHandler, Router};
+
+mod describe;
+pub use describe::{RegisterDescription, RegisterMap};
+
+#[cfg(feature = "async")]
+mod tcp;
+#[cfg(feature = "async")]
+pub use tcp::serve_tcp;
@@ -0,0 +1,88 @@
+//! Deferred execution of requests answered with `Acknowledge`.
+
+use crate::{Exception, Function, Response, Slave};
+
+
+impl Slave {
+    /// Returns the requests acknowledged under
+    /// [`FunctionPolicy::Acknowledge`](crate::FunctionPolicy::Acknowledge)
+    /// and not completed yet, oldest first.
+    pub fn pending(&self) -> impl Iterator<Item = &Function> {
+        self.pending.iter()
+    }
+
+    /// Completes the oldest pending request starting at `address`: executes
+    /// it against the tables like [`process`](Self::process) and returns the
+    /// outcome, or [`None`] when no such request is pending.
+    ///
+    /// The master learns the outcome only by reading the tables, e.g. a
+    /// [`program_complete_register`](crate::SlaveBuilder::program_complete_register).
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{DataBlock, Exception, Function, FunctionPolicy, Request, Response, Slave};
+    ///
+    /// let mut slave = Slave::builder(0x01)
+    ///     .holding_registers("program", DataBlock::new(0x0000, vec![0; 4]))
+    ///     .input_registers("status", DataBlock::new(0x0100, vec![0]))
+    ///     .function_policy(0x10, FunctionPolicy::Acknowledge)
+    ///     .program_complete_register(0x0100)
+    ///     .build()
+    ///     .unwrap();
+    /// let program = Function::WriteMultipleRegisters { starting_address: 0x0000, value: Box::new([1, 2]) };
+    /// let request = Request::new(0x01, &program, std::time::Duration::from_millis(100));
+    /// let frame = request.to_bytes().unwrap();
+    /// let exception = |frame: &[u8], exception| {
+    ///     Response::from_bytes(&request, frame) == Ok(Response::Exception(exception))
+    /// };
+    ///
+    /// assert!(exception(&slave.handle(&frame).unwrap(), Exception::Acknowledge));
+    /// assert_eq!(slave.input_registers().get(0x0100), Some(&0));
+    ///
+    /// // Until the program completed, the device is busy.
+    /// assert!(exception(&slave.handle(&frame).unwrap(), Exception::DeviceBusy));
+    ///
+    /// let outcome = slave.complete_pending(0x0000).unwrap();
+    /// assert!(!matches!(outcome, Response::Exception(_)));
+    /// assert_eq!(slave.holding_registers().range(0x0000, 2), Some(vec![1, 2]));
+    /// assert_eq!(slave.input_registers().get(0x0100), Some(&1));
+    /// ```
+    ///
+    pub fn complete_pending(&mut self, address: u16) -> Option<Response> {
+        let index = self.pending.iter().position(|function| {
+            super::handler::span(function).map(|(_, start, _)| start) == Some(address)
+        })?;
+        let function = self.pending.remove(index);
+        let response = self.process(&function);
+        self.update_program_complete();
+        Some(response)
+    }
+
+    /// Queues `function` for [`complete_pending`](Self::complete_pending)
+    /// and returns the answer to the master: [`Exception::Acknowledge`],
+    /// [`Exception::DeviceBusy`] while an earlier request is still pending,
+    /// or the exception [`process`](Self::process) would answer, so a
+    /// request bound to fail is never accepted.
+    pub(crate) fn acknowledge(&mut self, function: Function) -> Response {
+        if !self.pending.is_empty() {
+            return Response::Exception(Exception::DeviceBusy);
+        }
+        if let Err(exception) = self.execute(&function, false) {
+            return Response::Exception(exception);
+        }
+        self.pending.push(function);
+        self.update_program_complete();
+        Response::Exception(Exception::Acknowledge)
+    }
+
+    /// Sets the program complete register to 1 when nothing is pending and
+    /// to 0 otherwise.
+    fn update_program_complete(&mut self) {
+        if let Some(address) = self.program_complete
+            && let Some(value) = self.input_registers.get_mut(address)
+        {
+            *value = self.pending.is_empty() as u16;
+        }
+    }
+}
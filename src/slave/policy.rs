@@ -0,0 +1,34 @@
+/// ## FunctionPolicy
+///
+/// How a [`Slave`](crate::Slave) treats requests with a given function code,
+/// set per code with [`SlaveBuilder::function_policy`](crate::SlaveBuilder::function_policy).
+///
+/// Installations that must not reveal a function, e.g. the device
+/// identification behind `0x2B`, use [`Silent`](Self::Silent): the slave
+/// then behaves like a device that never heard the request, and the master
+/// runs into its timeout.
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FunctionPolicy {
+    /// Serve the request as usual.
+    #[default]
+    Respond,
+
+    /// Refuse the request with [`Exception::IllegalFunction`](crate::Exception::IllegalFunction),
+    /// without touching the tables.
+    Exception,
+
+    /// Neither serve nor answer the request, broadcasts included.
+    Silent,
+
+    /// Accept a write for a long operation: answer with
+    /// [`Exception::Acknowledge`](crate::Exception::Acknowledge) and queue it
+    /// until the application calls [`Slave::complete_pending`](crate::Slave::complete_pending).
+    /// The write is checked like a served one first, and a failing write is
+    /// refused with its exception right away. Further requests with such a
+    /// policy are answered with [`Exception::DeviceBusy`](crate::Exception::DeviceBusy)
+    /// meanwhile. Only the four write functions can be acknowledged.
+    Acknowledge,
+}
@@ -0,0 +1,117 @@
+//! Per-address access counters of a slave.
+
+use crate::{Function, Table};
+use alloc::{collections::BTreeMap, vec::Vec};
+
+
+/// ## AccessCount
+///
+/// How often one address was read and written by a master.
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AccessCount {
+    /// Successful reads covering the address.
+    pub reads: u64,
+
+    /// Successful writes covering the address, broadcasts included.
+    pub writes: u64,
+}
+
+impl AccessCount {
+    /// Returns the reads and writes together.
+    pub const fn total(&self) -> u64 {
+        self.reads + self.writes
+    }
+}
+
+
+/// ## AccessProfile
+///
+/// Counts the reads and writes of every address a [`Slave`](crate::Slave)
+/// served, recorded when enabled with
+/// [`SlaveBuilder::record_accesses`](crate::SlaveBuilder::record_accesses).
+///
+/// Firmware authors use it to see what masters actually poll, e.g. to move
+/// the hottest registers next to each other so one read covers them.
+/// Requests answered with an exception are not counted.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{AccessCount, DataBlock, Function, Slave, Table};
+///
+/// let mut slave = Slave::builder(0x01)
+///     .holding_registers("config", DataBlock::new(0x0000, vec![0; 8]))
+///     .record_accesses()
+///     .build()
+///     .unwrap();
+/// for _ in 0..3 {
+///     slave.process(&Function::ReadHoldingRegisters { starting_address: 0x0002, quantity: 2 });
+/// }
+/// slave.process(&Function::WriteSingleRegister { address: 0x0003, value: 7 });
+/// slave.process(&Function::ReadHoldingRegisters { starting_address: 0x0007, quantity: 2 });
+///
+/// let profile = slave.access_profile().unwrap();
+/// assert_eq!(profile.count(Table::HoldingRegisters, 0x0003), AccessCount { reads: 3, writes: 1 });
+/// assert_eq!(profile.count(Table::HoldingRegisters, 0x0007), AccessCount::default());
+/// assert_eq!(
+///     profile.hottest(2),
+///     [
+///         (Table::HoldingRegisters, 0x0003, AccessCount { reads: 3, writes: 1 }),
+///         (Table::HoldingRegisters, 0x0002, AccessCount { reads: 3, writes: 0 }),
+///     ]
+/// );
+/// ```
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccessProfile {
+    counts: BTreeMap<(Table, u16), AccessCount>,
+}
+
+impl AccessProfile {
+    /// Returns how often `address` of `table` was accessed.
+    pub fn count(&self, table: Table, address: u16) -> AccessCount {
+        self.counts
+            .get(&(table, address))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Iterates over every accessed address in table and address order.
+    pub fn iter(&self) -> impl Iterator<Item = (Table, u16, AccessCount)> + '_ {
+        self.counts
+            .iter()
+            .map(|((table, address), count)| (*table, *address, *count))
+    }
+
+    /// Returns the `n` most accessed addresses, most accessed first; ties
+    /// are kept in table and address order.
+    pub fn hottest(&self, n: usize) -> Vec<(Table, u16, AccessCount)> {
+        let mut hottest: Vec<_> = self.iter().collect();
+        hottest.sort_by_key(|(_, _, count)| core::cmp::Reverse(count.total()));
+        hottest.truncate(n);
+        hottest
+    }
+
+    /// Forgets every access counted so far.
+    pub fn clear(&mut self) {
+        self.counts.clear();
+    }
+
+    /// Counts the access `function` made, after it was served.
+    pub(crate) fn record(&mut self, function: &Function) {
+        let Some((table, start, len)) = super::handler::span(function) else {
+            return;
+        };
+        let write = !function.kind().is_read();
+        // `start..` would step past 0xFFFF after the last address.
+        for address in (0..len).map(|offset| start.wrapping_add(offset)) {
+            let count = self.counts.entry((table, address)).or_default();
+            if write {
+                count.writes += 1;
+            } else {
+                count.reads += 1;
+            }
+        }
+    }
+}
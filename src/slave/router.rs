@@ -0,0 +1,259 @@
+//! Unit id routing in front of several backends.
+
+use crate::Slave;
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+
+
+/// ## FrameHandler
+///
+/// Something that answers complete RTU request frames, like [`Slave`] and
+/// [`Router`]; what [`serve_tcp`](crate::serve_tcp) serves.
+///
+/// [`handle`](Self::handle) is synchronous and may block, e.g. on the serial
+/// line behind a remote route of a [`Router`], so async code must not call
+/// it on a runtime worker; [`serve_tcp`](crate::serve_tcp) calls it with
+/// `tokio::task::spawn_blocking`.
+///
+pub trait FrameHandler: Send {
+    /// Handles a complete request frame and returns the response frame to
+    /// send, or [`None`] when the frame must stay unanswered.
+    fn handle(&mut self, frame: &[u8]) -> Option<Box<[u8]>>;
+}
+
+impl FrameHandler for Slave {
+    fn handle(&mut self, frame: &[u8]) -> Option<Box<[u8]>> {
+        Slave::handle(self, frame)
+    }
+}
+
+
+/// Application function answering the frames of one unit id.
+type HandlerFn = dyn FnMut(&[u8]) -> Option<Box<[u8]>> + Send;
+
+
+/// Backend behind one unit id.
+enum Route {
+    Slave(Box<Slave>),
+    Handler(Box<HandlerFn>),
+}
+
+impl core::fmt::Debug for Route {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Route::Slave(slave) => f.debug_tuple("Slave").field(slave).finish(),
+            Route::Handler(_) => f.write_str("Handler"),
+        }
+    }
+}
+
+
+/// ## Router
+///
+/// Dispatches request frames to a backend by their unit id, so one endpoint
+/// serves a composite device or acts as a protocol converter. A backend is
+/// a local [`Slave`], a function of the application, or, with the `master`
+/// and `sync` features, a slave on a serial line reached through a
+/// [`Master`](crate::Master).
+///
+/// A router answers frames like a [`Slave`] does, through [`handle`](Self::handle),
+/// so it serves an RTU line the same way and plugs into
+/// [`serve_tcp`](crate::serve_tcp). Frames for unrouted unit ids and frames
+/// failing the CRC check stay unanswered. Broadcasts are passed to every
+/// backend and never answered. Routing a unit id a second time replaces
+/// the earlier backend.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{raw, DataBlock, Router, Slave};
+///
+/// let meter = Slave::builder(0x01)
+///     .input_registers("energy", DataBlock::new(0x0000, vec![1234]))
+///     .build()
+///     .unwrap();
+/// let mut router = Router::new()
+///     // Modbus TCP clients address a directly connected device as 0xFF.
+///     .slave(0xFF, meter)
+///     .handler(0x10, |frame| {
+///         let mut buf = Vec::new();
+///         // Vendor function 0x41 answers with a version number.
+///         Some(raw::frame(frame[0], frame[1], &[0x02], &mut buf).into())
+///     });
+///
+/// let mut buf = Vec::new();
+/// let response = router.handle(raw::frame(0xFF, 0x04, &[0x00, 0x00, 0x00, 0x01], &mut buf));
+/// let response = response.unwrap();
+/// assert_eq!(raw::check(&response).unwrap(), (0xFF, 0x04, &[0x02, 0x04, 0xD2][..]));
+///
+/// let response = router.handle(raw::frame(0x10, 0x41, &[], &mut buf)).unwrap();
+/// assert_eq!(raw::check(&response).unwrap(), (0x10, 0x41, &[0x02][..]));
+///
+/// assert_eq!(router.handle(raw::frame(0x20, 0x41, &[], &mut buf)), None);
+/// assert_eq!(router.get(0xFF).unwrap().input_registers().get(0x0000), Some(&1234));
+/// ```
+///
+#[derive(Debug, Default)]
+pub struct Router {
+    routes: BTreeMap<u8, Route>,
+}
+
+impl Router {
+    /// Creates a router without routes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Routes `unit_id` to `slave`. Frames are readdressed to the id of the
+    /// slave and responses back to `unit_id`, so a slave can answer under
+    /// another id than its own.
+    pub fn slave(mut self, unit_id: u8, slave: Slave) -> Self {
+        self.routes.insert(unit_id, Route::Slave(Box::new(slave)));
+        self
+    }
+
+    /// Routes `unit_id` to `handler`, which receives the request frames as
+    /// they are and returns the response frame, or [`None`] to leave the
+    /// request unanswered.
+    pub fn handler(
+        mut self,
+        unit_id: u8,
+        handler: impl FnMut(&[u8]) -> Option<Box<[u8]>> + Send + 'static,
+    ) -> Self {
+        self.routes
+            .insert(unit_id, Route::Handler(Box::new(handler)));
+        self
+    }
+
+    /// Routes `unit_id` to slave `modbus_id` on the serial line of `master`,
+    /// shared with the application and other routes. Requests are forwarded
+    /// with [`Master::send_raw`](crate::Master::send_raw); when the slave
+    /// does not answer in `timeout`, the router answers with
+    /// [`Exception::GatewayTargetDeviceFailedToRespond`](crate::Exception::GatewayTargetDeviceFailedToRespond),
+    /// and when the line fails otherwise, e.g. with a corrupted response or
+    /// a port error, with
+    /// [`Exception::GatewayPathUnavailable`](crate::Exception::GatewayPathUnavailable).
+    /// Broadcasts are forwarded as broadcasts, once per remote route, without
+    /// waiting for a response.
+    ///
+    /// Handling a frame blocks for up to `timeout` while holding the lock of
+    /// `master`; [`serve_tcp`](crate::serve_tcp) runs it off the async runtime.
+    ///
+    /// ---
+    /// # Examples
+    /// ```ignore
+    /// use modbus_rtu::{Master, Router};
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    ///
+    /// # async fn demo() -> Result<(), Box<dyn std::error::Error>> {
+    /// // A Modbus TCP to RTU gateway for slaves 1 and 2.
+    /// let master = Arc::new(Mutex::new(Master::new_rs485("/dev/ttyUSB0", 19_200)?));
+    /// let router = Router::new()
+    ///     .remote(0x01, master.clone(), 0x01, Duration::from_millis(200))
+    ///     .remote(0x02, master, 0x02, Duration::from_millis(200));
+    ///
+    /// let listener = tokio::net::TcpListener::bind("0.0.0.0:502").await?;
+    /// modbus_rtu::serve_tcp(listener, Arc::new(Mutex::new(router))).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    #[cfg(all(feature = "master", feature = "sync"))]
+    pub fn remote(
+        self,
+        unit_id: u8,
+        master: std::sync::Arc<std::sync::Mutex<crate::Master>>,
+        modbus_id: u8,
+        timeout: core::time::Duration,
+    ) -> Self {
+        self.handler(unit_id, move |frame| {
+            let broadcast = frame[0] == 0;
+            let request = readdress(frame, if broadcast { 0 } else { modbus_id })?;
+            let expected_len = crate::frame::parse_request(&request)
+                .map_or(0, |(_, function)| function.expected_len());
+            let response = master
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .send_raw(&request, expected_len, timeout);
+            match response {
+                _ if broadcast => None,
+                Ok(response) if response.is_empty() => None,
+                Ok(response) => readdress(&response, frame[0]),
+                Err(error) => {
+                    let exception = match error {
+                        crate::error::Error::IO(error)
+                            if error.kind() == std::io::ErrorKind::TimedOut =>
+                        {
+                            crate::Exception::GatewayTargetDeviceFailedToRespond
+                        }
+                        _ => crate::Exception::GatewayPathUnavailable,
+                    };
+                    let mut buf = Vec::new();
+                    crate::raw::frame(frame[0], frame[1] | 0x80, &[exception.as_code()], &mut buf);
+                    Some(buf.into())
+                }
+            }
+        })
+    }
+
+    /// Returns the slave routed to `unit_id`, if any.
+    pub fn get(&self, unit_id: u8) -> Option<&Slave> {
+        match self.routes.get(&unit_id)? {
+            Route::Slave(slave) => Some(slave),
+            Route::Handler(_) => None,
+        }
+    }
+
+    /// Returns the slave routed to `unit_id` for updates by the application.
+    pub fn get_mut(&mut self, unit_id: u8) -> Option<&mut Slave> {
+        match self.routes.get_mut(&unit_id)? {
+            Route::Slave(slave) => Some(slave),
+            Route::Handler(_) => None,
+        }
+    }
+
+    /// Iterates over the routed unit ids in ascending order.
+    pub fn unit_ids(&self) -> impl Iterator<Item = u8> + '_ {
+        self.routes.keys().copied()
+    }
+
+    /// Handles a complete request frame and returns the response frame of
+    /// the backend routed to its unit id, or [`None`] when the frame must
+    /// stay unanswered.
+    pub fn handle(&mut self, frame: &[u8]) -> Option<Box<[u8]>> {
+        crate::raw::check(frame).ok()?;
+        if frame[0] == 0 {
+            for route in self.routes.values_mut() {
+                match route {
+                    Route::Slave(slave) => drop(slave.handle(frame)),
+                    Route::Handler(handler) => drop(handler(frame)),
+                }
+            }
+            return None;
+        }
+        match self.routes.get_mut(&frame[0])? {
+            Route::Slave(slave) if slave.modbus_id() == frame[0] => slave.handle(frame),
+            Route::Slave(slave) => {
+                let response = slave.handle(&readdress(frame, slave.modbus_id())?)?;
+                readdress(&response, frame[0])
+            }
+            Route::Handler(handler) => handler(frame),
+        }
+    }
+}
+
+impl FrameHandler for Router {
+    fn handle(&mut self, frame: &[u8]) -> Option<Box<[u8]>> {
+        Router::handle(self, frame)
+    }
+}
+
+
+/// Returns `frame` addressed to `modbus_id`, with the CRC recomputed, or
+/// [`None`] when `frame` is too short to be one.
+fn readdress(frame: &[u8], modbus_id: u8) -> Option<Box<[u8]>> {
+    let (_, function_code, payload) = crate::raw::check(frame).ok()?;
+    let mut buf = Vec::new();
+    crate::raw::frame(modbus_id, function_code, payload, &mut buf);
+    Some(buf.into())
+}
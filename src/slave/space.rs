@@ -0,0 +1,182 @@
+use crate::{DataBlock, Table};
+use alloc::{string::String, vec::Vec};
+use core::ops::RangeInclusive;
+
+
+/// ## AddressSpace
+///
+/// One table of a [`Slave`](crate::Slave): a set of named [`DataBlock`]s.
+///
+/// A range is served when every address in it is defined by some block, so
+/// adjacent blocks can be read or written in a single request. The block
+/// names group the addresses logically, e.g. `"config"` and `"telemetry"`,
+/// for dispatch in the application and for documentation.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{DataBlock, Slave, Table};
+///
+/// let slave = Slave::builder(0x01)
+///     .holding_registers("setpoints", DataBlock::new(0x0000, vec![1, 2]))
+///     .holding_registers("limits", DataBlock::new(0x0002, vec![3, 4]))
+///     .build()
+///     .unwrap();
+///
+/// let registers = slave.holding_registers();
+/// assert_eq!(registers.range(0x0001, 2), Some(vec![2, 3]));
+/// assert_eq!(registers.range(0x0003, 2), None);
+/// assert_eq!(registers.block("limits").unwrap().start(), 0x0002);
+/// assert_eq!(registers.block_name(0x0003), Some("limits"));
+///
+/// let blocks: Vec<_> = registers.blocks().collect();
+/// assert_eq!(
+///     blocks,
+///     [
+///         ("setpoints", Table::HoldingRegisters, 0x0000..=0x0001),
+///         ("limits", Table::HoldingRegisters, 0x0002..=0x0003),
+///     ]
+/// );
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressSpace<T> {
+    table: Table,
+    blocks: Vec<(String, DataBlock<T>)>,
+}
+
+impl<T> AddressSpace<T> {
+    /// Creates a `table` without blocks.
+    pub const fn new(table: Table) -> Self {
+        Self {
+            table,
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Adds `block` under `name`.
+    pub(crate) fn push(&mut self, name: String, block: DataBlock<T>) {
+        self.blocks.push((name, block));
+    }
+
+    /// Returns the table this address space serves.
+    pub fn table(&self) -> Table {
+        self.table
+    }
+
+    /// Returns the name, table and address range of every block, in
+    /// declaration order. Empty blocks define no address and are left out.
+    pub fn blocks(&self) -> impl Iterator<Item = (&str, Table, RangeInclusive<u16>)> {
+        self.blocks.iter().filter_map(|(name, block)| {
+            let range = block.start()..=block.last()?;
+            Some((name.as_str(), self.table, range))
+        })
+    }
+
+    /// Returns every block with its name in declaration order, empty blocks
+    /// included.
+    pub(crate) fn named(&self) -> impl Iterator<Item = (&str, &DataBlock<T>)> {
+        self.blocks
+            .iter()
+            .map(|(name, block)| (name.as_str(), block))
+    }
+
+    /// Returns the name of the block defining `address`.
+    pub fn block_name(&self, address: u16) -> Option<&str> {
+        self.blocks
+            .iter()
+            .find(|(_, block)| block.contains(address, 1))
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the block named `name`.
+    pub fn block(&self, name: &str) -> Option<&DataBlock<T>> {
+        self.blocks
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, block)| block)
+    }
+
+    /// Returns the block named `name` for updates by the application.
+    pub fn block_mut(&mut self, name: &str) -> Option<&mut DataBlock<T>> {
+        self.blocks
+            .iter_mut()
+            .find(|(n, _)| n == name)
+            .map(|(_, block)| block)
+    }
+
+    /// Returns the value at `address`, if a block defines it.
+    pub fn get(&self, address: u16) -> Option<&T> {
+        self.blocks.iter().find_map(|(_, block)| block.get(address))
+    }
+
+    /// Returns a mutable reference to the value at `address`, if a block
+    /// defines it.
+    pub fn get_mut(&mut self, address: u16) -> Option<&mut T> {
+        self.blocks
+            .iter_mut()
+            .find_map(|(_, block)| block.get_mut(address))
+    }
+
+    /// Returns `true` when every one of `quantity` addresses from `start` is
+    /// defined by some block.
+    pub fn contains(&self, start: u16, quantity: u16) -> bool {
+        self.segments(start, quantity).is_some()
+    }
+
+    /// Splits `quantity` addresses from `start` into runs served by single
+    /// blocks, as `(block index, start, length)`, or returns [`None`] when an
+    /// address is undefined.
+    fn segments(&self, start: u16, quantity: u16) -> Option<Vec<(usize, u16, u16)>> {
+        let end = start as u32 + quantity as u32;
+        let mut address = start as u32;
+        let mut segments = Vec::new();
+        while address < end {
+            let (index, last) =
+                self.blocks
+                    .iter()
+                    .enumerate()
+                    .find_map(|(index, (_, block))| {
+                        block
+                            .contains(address as u16, 1)
+                            .then(|| (index, block.last()))
+                    })?;
+            let length = (last? as u32 + 1).min(end) - address;
+            segments.push((index, address as u16, length as u16));
+            address += length;
+        }
+        Some(segments)
+    }
+}
+
+impl<T: Copy> AddressSpace<T> {
+    /// Returns the values of `quantity` addresses from `start`, or [`None`]
+    /// unless all of them are defined.
+    pub fn range(&self, start: u16, quantity: u16) -> Option<Vec<T>> {
+        let mut values = Vec::with_capacity(quantity as usize);
+        for (index, start, length) in self.segments(start, quantity)? {
+            values.extend_from_slice(self.blocks[index].1.range(start, length)?);
+        }
+        Some(values)
+    }
+
+    /// Writes `values` from `start` and returns `true`, or leaves the table
+    /// untouched and returns `false` unless every address is defined.
+    pub fn write(&mut self, start: u16, values: &[T]) -> bool {
+        let Some(segments) = u16::try_from(values.len())
+            .ok()
+            .and_then(|quantity| self.segments(start, quantity))
+        else {
+            return false;
+        };
+        let mut values = values;
+        for (index, start, length) in segments {
+            let (head, tail) = values.split_at(length as usize);
+            if let Some(target) = self.blocks[index].1.range_mut(start, length) {
+                target.copy_from_slice(head);
+            }
+            values = tail;
+        }
+        true
+    }
+}
@@ -0,0 +1,116 @@
+//! Modbus TCP server in front of a slave data model.
+
+use crate::FrameHandler;
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+
+/// Length of the MBAP header, unit id included.
+const MBAP_LEN: usize = 7;
+
+
+/// Serves `handler`, typically a [`Slave`](crate::Slave) or a
+/// [`Router`](crate::Router), to Modbus TCP clients connecting to
+/// `listener`, so one data model is reachable both as an RTU slave and as a
+/// TCP server.
+///
+/// Every connection runs on its own task. Requests are unpacked from their
+/// MBAP header into an RTU frame addressed to the unit id and passed to
+/// [`FrameHandler::handle`], so a slave applies its policies, constraints
+/// and callbacks as on the serial line; the response goes back with the transaction id of the
+/// request. A slave leaves requests for other unit ids unanswered, like
+/// frames for other slaves; a router can map any unit id to it. A connection sending a malformed header is closed.
+///
+/// The handler stays shared with the application, which locks it to update
+/// the tables, e.g. from the RTU side. Since that lock and the handler
+/// itself may block, e.g. a [`Router`](crate::Router) forwarding to a serial
+/// line, every request is handled with [`tokio::task::spawn_blocking`] and
+/// never on a runtime worker; the application must not hold the lock across
+/// an `.await` either.
+///
+/// ---
+/// # Errors
+/// Returns the error of [`TcpListener::accept`]; errors of single
+/// connections only close them.
+///
+/// ---
+/// # Examples
+/// ```ignore
+/// use modbus_rtu::{DataBlock, Slave};
+/// use std::sync::{Arc, Mutex};
+///
+/// # async fn demo() -> std::io::Result<()> {
+/// let slave = Slave::builder(0x01)
+///     .holding_registers("config", DataBlock::new(0x0000, vec![0; 16]))
+///     .build()
+///     .unwrap();
+/// let slave = Arc::new(Mutex::new(slave));
+///
+/// let listener = tokio::net::TcpListener::bind("0.0.0.0:502").await?;
+/// modbus_rtu::serve_tcp(listener, slave).await
+/// # }
+/// ```
+///
+pub async fn serve_tcp<H: FrameHandler + 'static>(
+    listener: TcpListener,
+    handler: Arc<Mutex<H>>,
+) -> std::io::Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let handler = Arc::clone(&handler);
+        tokio::spawn(async move {
+            let _ = connection(stream, handler).await;
+        });
+    }
+}
+
+
+/// Answers the requests of one client until it disconnects or sends a
+/// malformed header.
+async fn connection<H: FrameHandler + 'static>(
+    mut stream: TcpStream,
+    handler: Arc<Mutex<H>>,
+) -> std::io::Result<()> {
+    stream.set_nodelay(true)?;
+    let mut header = [0; MBAP_LEN];
+    let mut frame = Vec::new();
+    loop {
+        match stream.read_exact(&mut header).await {
+            Err(error) if error.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+            result => result?,
+        };
+        // The length counts the unit id and the PDU.
+        let len = u16::from_be_bytes([header[4], header[5]]) as usize;
+        if header[2..4] != [0, 0] || !(2..=crate::limits::MAX_PDU + 1).contains(&len) {
+            return Err(std::io::ErrorKind::InvalidData.into());
+        }
+        frame.clear();
+        frame.push(header[6]);
+        frame.resize(len, 0);
+        stream.read_exact(&mut frame[1..]).await?;
+        frame.extend_from_slice(&crate::crc::generate(&frame).to_le_bytes());
+
+        let handler = Arc::clone(&handler);
+        let (request, response) = tokio::task::spawn_blocking(move || {
+            let response = handler
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .handle(&frame);
+            (frame, response)
+        })
+        .await
+        .map_err(std::io::Error::other)?;
+        frame = request;
+        let Some(response) = response else {
+            continue;
+        };
+        let pdu = &response[1..response.len() - 2];
+        let mut reply = Vec::with_capacity(MBAP_LEN + pdu.len());
+        reply.extend_from_slice(&header[..4]);
+        reply.extend_from_slice(&(pdu.len() as u16 + 1).to_be_bytes());
+        reply.push(header[6]);
+        reply.extend_from_slice(pdu);
+        stream.write_all(&reply).await?;
+    }
+}
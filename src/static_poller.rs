@@ -0,0 +1,122 @@
+/// Scheduled request kept by a [`StaticPoller`].
+#[derive(Debug, Clone, Copy)]
+struct StaticEntry<'a> {
+    frame: &'a [u8],
+    interval_ms: u32,
+    due_ms: u64,
+}
+
+
+/// ## StaticPoller
+///
+/// Allocation-free cyclic schedule of up to `N` prepared request frames, for
+/// embedded masters that drive the transport themselves.
+///
+/// Frames are borrowed, typically from `static` arrays, and time is supplied
+/// by the caller as a millisecond tick, so the schedule needs neither a heap
+/// nor a system clock. Each entry is due `interval_ms` after its previous due
+/// time; an entry that fell more than one interval behind is rescheduled from
+/// the current tick instead of firing repeatedly to catch up.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{frame, StaticPoller};
+///
+/// // Read 2 holding registers from slave 1, and 1 input register from slave 2.
+/// static READ_1: [u8; 8] = frame::encode_read_holding(0x01, 0x0000, 2);
+/// static READ_2: [u8; 8] = frame::encode_read_input(0x02, 0x0000, 1);
+///
+/// let mut poller: StaticPoller<'_, 4> = StaticPoller::new();
+/// assert_eq!(poller.add(&READ_1, 100), Some(0));
+/// assert_eq!(poller.add(&READ_2, 500), Some(1));
+///
+/// assert_eq!(poller.poll(0), Some((0, &READ_1[..])));
+/// assert_eq!(poller.poll(0), Some((1, &READ_2[..])));
+/// assert_eq!(poller.poll(50), None);
+/// assert_eq!(poller.poll(100), Some((0, &READ_1[..])));
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct StaticPoller<'a, const N: usize> {
+    entries: [Option<StaticEntry<'a>>; N],
+    len: usize,
+}
+
+impl<'a, const N: usize> StaticPoller<'a, N> {
+    /// Creates an empty schedule.
+    pub const fn new() -> Self {
+        Self {
+            entries: [None; N],
+            len: 0,
+        }
+    }
+
+    /// Adds `frame`, a complete request frame, to be sent every `interval_ms`
+    /// starting immediately. Returns the index of the entry, or [`None`] when
+    /// all `N` slots are taken.
+    pub fn add(&mut self, frame: &'a [u8], interval_ms: u32) -> Option<usize> {
+        let index = self.len;
+        *self.entries.get_mut(index)? = Some(StaticEntry {
+            frame,
+            interval_ms,
+            due_ms: 0,
+        });
+        self.len += 1;
+        Some(index)
+    }
+
+    /// Returns the number of scheduled frames.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` when no frame is scheduled.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the maximum number of frames, `N`.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the frame of entry `index`.
+    pub fn frame(&self, index: usize) -> Option<&'a [u8]> {
+        self.entries.get(index)?.map(|entry| entry.frame)
+    }
+
+    /// Returns the tick at which the next entry falls due, or [`None`] when
+    /// the schedule is empty.
+    pub fn next_due(&self) -> Option<u64> {
+        self.entries
+            .iter()
+            .flatten()
+            .map(|entry| entry.due_ms)
+            .min()
+    }
+
+    /// Returns the most overdue entry at tick `now_ms` and schedules its next
+    /// run, or [`None`] when nothing is due yet.
+    pub fn poll(&mut self, now_ms: u64) -> Option<(usize, &'a [u8])> {
+        let (index, entry) = self
+            .entries
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, entry)| Some((index, entry.as_mut()?)))
+            .filter(|(_, entry)| entry.due_ms <= now_ms)
+            .min_by_key(|(_, entry)| entry.due_ms)?;
+        let interval = entry.interval_ms as u64;
+        entry.due_ms = match entry.due_ms + interval {
+            next if next + interval <= now_ms => now_ms + interval,
+            next => next,
+        };
+        Some((index, entry.frame))
+    }
+}
+
+impl<const N: usize> Default for StaticPoller<'_, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
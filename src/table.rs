@@ -0,0 +1,177 @@
+use alloc::{format, string::String};
+
+
+/// ## Table
+///
+/// The four primary tables of the Modbus data model.
+///
+/// Coils and holding registers can be written by a master; discrete inputs
+/// and input registers are read-only. Coils and discrete inputs hold single
+/// bits, the register tables hold 16-bit words.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Table {
+    /// Read-write single-bit table.
+    Coils,
+
+    /// Read-only single-bit table.
+    DiscreteInputs,
+
+    /// Read-write 16-bit table.
+    HoldingRegisters,
+
+    /// Read-only 16-bit table.
+    InputRegisters,
+}
+
+impl Table {
+    /// Returns `true` for the single-bit tables.
+    pub const fn is_bit(&self) -> bool {
+        matches!(self, Table::Coils | Table::DiscreteInputs)
+    }
+
+    /// Returns `true` for the tables a master can write to.
+    pub const fn is_writable(&self) -> bool {
+        matches!(self, Table::Coils | Table::HoldingRegisters)
+    }
+
+    /// Returns the function that reads this table.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{FunctionKind, Table};
+    ///
+    /// assert_eq!(Table::InputRegisters.read_kind(), FunctionKind::ReadInputRegisters);
+    /// assert_eq!(FunctionKind::ReadInputRegisters.table(), Some(Table::InputRegisters));
+    /// ```
+    ///
+    pub const fn read_kind(&self) -> crate::FunctionKind {
+        match self {
+            Table::Coils => crate::FunctionKind::ReadCoils,
+            Table::DiscreteInputs => crate::FunctionKind::ReadDiscreteInputs,
+            Table::HoldingRegisters => crate::FunctionKind::ReadHoldingRegisters,
+            Table::InputRegisters => crate::FunctionKind::ReadInputRegisters,
+        }
+    }
+
+    /// Builds the request reading `quantity` items of this table starting at
+    /// `starting_address`.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{Function, Table};
+    ///
+    /// let function = Table::HoldingRegisters.read(0x0010, 4);
+    /// assert_eq!(function, Function::ReadHoldingRegisters { starting_address: 0x0010, quantity: 4 });
+    /// ```
+    ///
+    pub const fn read(&self, starting_address: u16, quantity: u16) -> crate::Function {
+        match self {
+            Table::Coils => crate::Function::ReadCoils {
+                starting_address,
+                quantity,
+            },
+            Table::DiscreteInputs => crate::Function::ReadDiscreteInputs {
+                starting_address,
+                quantity,
+            },
+            Table::HoldingRegisters => crate::Function::ReadHoldingRegisters {
+                starting_address,
+                quantity,
+            },
+            Table::InputRegisters => crate::Function::ReadInputRegisters {
+                starting_address,
+                quantity,
+            },
+        }
+    }
+
+    /// Maps a PLC-style reference number onto its table and zero-based
+    /// protocol address.
+    ///
+    /// The leading digit selects the table (`0` coils, `1` discrete inputs,
+    /// `3` input registers, `4` holding registers) and the remaining digits
+    /// are the one-based address. Five-digit references (`40001`-`49999`)
+    /// cover the first 9999 addresses; six-digit references
+    /// (`400001`-`465536`) the whole address space. The number of digits,
+    /// including leading zeros, decides the convention.
+    ///
+    /// Returns [`None`] for malformed references, addresses out of range, and
+    /// `6xxxxx` extended memory references, which map onto file records
+    /// (functions `0x14`/`0x15`) that this crate does not implement.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{Function, Table};
+    ///
+    /// assert_eq!(Table::from_reference("40001"), Some((Table::HoldingRegisters, 0)));
+    /// assert_eq!(Table::from_reference("465536"), Some((Table::HoldingRegisters, 0xFFFF)));
+    /// assert_eq!(Table::from_reference("000017"), Some((Table::Coils, 16)));
+    /// assert_eq!(Table::from_reference("30000"), None);
+    /// assert_eq!(Table::from_reference("600001"), None);
+    ///
+    /// let (table, address) = Table::from_reference("300101").unwrap();
+    /// assert_eq!(
+    ///     table.read(address, 2),
+    ///     Function::ReadInputRegisters { starting_address: 100, quantity: 2 }
+    /// );
+    /// ```
+    ///
+    pub fn from_reference(reference: &str) -> Option<(Table, u16)> {
+        if !matches!(reference.len(), 5 | 6) || !reference.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let table = match reference.as_bytes()[0] {
+            b'0' => Table::Coils,
+            b'1' => Table::DiscreteInputs,
+            b'3' => Table::InputRegisters,
+            b'4' => Table::HoldingRegisters,
+            _ => return None,
+        };
+        let number: u32 = reference[1..].parse().ok()?;
+        let address = u16::try_from(number.checked_sub(1)?).ok()?;
+        Some((table, address))
+    }
+
+    /// Returns the six-digit PLC-style reference of `address` in this table,
+    /// the inverse of [`from_reference`](Self::from_reference).
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::Table;
+    ///
+    /// assert_eq!(Table::HoldingRegisters.reference(0), "400001");
+    /// assert_eq!(Table::Coils.reference(0xFFFF), "065536");
+    /// ```
+    ///
+    pub fn reference(&self, address: u16) -> String {
+        let digit = match self {
+            Table::Coils => 0,
+            Table::DiscreteInputs => 1,
+            Table::InputRegisters => 3,
+            Table::HoldingRegisters => 4,
+        };
+        format!("{digit}{:05}", address as u32 + 1)
+    }
+}
+
+impl core::fmt::Display for Table {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Table::Coils => "Coils",
+                Table::DiscreteInputs => "Discrete Inputs",
+                Table::HoldingRegisters => "Holding Registers",
+                Table::InputRegisters => "Input Registers",
+            }
+        )
+    }
+}
@@ -0,0 +1,1149 @@
+//! Device templates declared in TOML files.
+
+use crate::error::TemplateError;
+use crate::limits::{MAX_READ_COILS, MAX_READ_REGISTERS};
+use crate::{DeviceImage, DeviceTemplate, Function, Table, WordOrder};
+
+
+/// ## DeviceProfile
+///
+/// Everything a master needs to know about a device model: its named
+/// registers with their data types and scaling, the groups they are polled
+/// in, and the protocol quirks of its firmware.
+///
+/// Profiles are written once per model, e.g. for a common energy meter or
+/// drive, and shared as TOML files:
+///
+/// ```toml
+/// manufacturer = "Acme"
+/// model = "EM-100"
+/// word_order = "low_first"
+/// max_gap = 8
+///
+/// [quirks]
+/// swapped_crc = true
+/// spacing_ms = 20
+///
+/// [[register]]
+/// name = "voltage"
+/// table = "input_registers"
+/// address = 0
+/// type = "u16"
+/// scale = 0.1
+/// unit = "V"
+/// aliases = ["U", "L1-N"]
+/// description = "Phase voltage"
+///
+/// [[register]]
+/// name = "energy"
+/// table = "input_registers"
+/// address = 8
+/// type = "u32"
+/// unit = "Wh"
+///
+/// [[derived]]
+/// name = "power"
+/// operation = "product"
+/// inputs = ["voltage", "current"]
+/// unit = "W"
+///
+/// [[group]]
+/// name = "fast"
+/// interval_ms = 500
+/// registers = ["voltage"]
+/// ```
+///
+/// [`template`](Self::template) and [`group_template`](Self::group_template)
+/// turn a profile into the [`DeviceTemplate`]s to read, and
+/// [`values`](Self::values) scales the [`DeviceImage`] read back into named
+/// engineering values, followed by the values derived from them. A [`ChangeDetector`] turns successive images into
+/// change events, filtered by the deadband and hysteresis of each register.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{DeviceImage, DeviceProfile, Response};
+///
+/// let profile = DeviceProfile::from_toml(r#"
+///     model = "EM-100"
+///     word_order = "low_first"
+///     max_gap = 1
+///
+///     [[register]]
+///     name = "voltage"
+///     table = "input_registers"
+///     address = 0
+///     scale = 0.5
+///
+///     [[register]]
+///     name = "energy"
+///     table = "input_registers"
+///     address = 2
+///     type = "u32"
+/// "#).unwrap();
+///
+/// let template = profile.template();
+/// let responses = vec![Response::Value(Box::new([461, 0, 0x86A0, 0x0001]))];
+/// let image = DeviceImage::decode(&template, responses).unwrap();
+///
+/// assert_eq!(profile.values(&image), vec![("voltage", 230.5), ("energy", 100_000.0)]);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct DeviceProfile {
+    /// Manufacturer of the device.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manufacturer: Option<String>,
+
+    /// Model designation of the device.
+    pub model: String,
+
+    /// Order of the registers of 32-bit values; most significant first when
+    /// omitted.
+    #[serde(default)]
+    pub word_order: WordOrder,
+
+    /// Deviations of the firmware from the specification.
+    #[serde(default)]
+    pub quirks: DeviceQuirks,
+
+    /// Most addresses no register is declared at that one read may span, so
+    /// that close registers are read together; `0` when omitted, so reads
+    /// never touch undeclared addresses.
+    #[serde(default)]
+    pub max_gap: u16,
+
+    /// The registers of the device.
+    #[serde(default, rename = "register")]
+    pub registers: Vec<RegisterProfile>,
+
+    /// Values computed from the registers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub derived: Vec<DerivedProfile>,
+
+    /// Groups of registers polled together.
+    #[serde(default, rename = "group")]
+    pub groups: Vec<PollGroup>,
+}
+
+impl DeviceProfile {
+    /// Reads and parses the TOML file at `path`.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`TemplateError`] when the file cannot be read or is not a
+    /// valid profile.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, TemplateError> {
+        Self::from_toml(&std::fs::read_to_string(path).map_err(TemplateError::IO)?)
+    }
+
+    /// Parses a profile from TOML text and checks its consistency.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`TemplateError::Syntax`] when `text` is not a valid profile,
+    /// or the inconsistency found by [`validate`](Self::validate).
+    pub fn from_toml(text: &str) -> Result<Self, TemplateError> {
+        let profile: Self = toml::from_str(text).map_err(TemplateError::Syntax)?;
+        profile.validate()?;
+        Ok(profile)
+    }
+
+    /// Formats the profile as TOML text.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`TemplateError::Serialize`] when the profile cannot be
+    /// represented in TOML.
+    pub fn to_toml(&self) -> Result<String, TemplateError> {
+        toml::to_string(self).map_err(TemplateError::Serialize)
+    }
+
+    /// Checks that register names and aliases are unique, that every data
+    /// type suits its table and fits the address space, that lookup tables
+    /// convert both ways, that derived values are named uniquely too and
+    /// only use registers and values derived before them, and that groups
+    /// only name declared registers.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns the first inconsistency found as a [`TemplateError`].
+    pub fn validate(&self) -> Result<(), TemplateError> {
+        for (i, register) in self.registers.iter().enumerate() {
+            let names = core::iter::once(&register.name).chain(&register.aliases);
+            for (j, name) in names.enumerate() {
+                if self.registers[..i].iter().any(|r| r.is_named(name))
+                    || register.aliases[j..].contains(name)
+                {
+                    return Err(TemplateError::DuplicateRegister(name.clone()));
+                }
+            }
+            if register.table.is_bit() != (register.data_type == DataType::Bool) {
+                return Err(TemplateError::TypeMismatch {
+                    register: register.name.clone(),
+                    table: register.table,
+                });
+            }
+            if register.address as u32 + register.quantity() as u32 > 0x1_0000 {
+                return Err(TemplateError::AddressOverflow(register.name.clone()));
+            }
+            if !register.lookup.is_empty() && !monotonic(&register.lookup) {
+                return Err(TemplateError::InvalidLookup(register.name.clone()));
+            }
+        }
+        for (i, derived) in self.derived.iter().enumerate() {
+            let earlier = &self.derived[..i];
+            if self.register(&derived.name).is_some()
+                || earlier.iter().any(|d| d.name == derived.name)
+            {
+                return Err(TemplateError::DuplicateRegister(derived.name.clone()));
+            }
+            if derived.inputs.is_empty() {
+                return Err(TemplateError::NoInputs(derived.name.clone()));
+            }
+            if let Some(missing) = derived.inputs.iter().find(|&input| {
+                self.register(input).is_none() && !earlier.iter().any(|d| d.name == *input)
+            }) {
+                return Err(TemplateError::UnknownInput {
+                    derived: derived.name.clone(),
+                    input: missing.clone(),
+                });
+            }
+        }
+        for group in &self.groups {
+            if let Some(missing) = group
+                .registers
+                .iter()
+                .find(|&name| self.register(name).is_none())
+            {
+                return Err(TemplateError::UnknownRegister {
+                    group: group.name.clone(),
+                    register: missing.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the register named `name`, or known under the alias `name`.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::DeviceProfile;
+    ///
+    /// let profile = DeviceProfile::from_toml(r#"
+    ///     model = "EM-100"
+    ///
+    ///     [[register]]
+    ///     name = "voltage"
+    ///     table = "input_registers"
+    ///     address = 0
+    ///     unit = "V"
+    ///     aliases = ["U", "L1-N"]
+    ///     description = "Phase voltage"
+    /// "#).unwrap();
+    ///
+    /// let register = profile.register("L1-N").unwrap();
+    /// assert_eq!(register.name, "voltage");
+    /// assert_eq!(register.unit.as_deref(), Some("V"));
+    /// assert_eq!(register.description.as_deref(), Some("Phase voltage"));
+    /// ```
+    ///
+    pub fn register(&self, name: &str) -> Option<&RegisterProfile> {
+        self.registers
+            .iter()
+            .find(|register| register.is_named(name))
+    }
+
+    /// Returns the template covering every register, with one range per run
+    /// of registers at most [`max_gap`](Self::max_gap) addresses apart.
+    pub fn template(&self) -> DeviceTemplate {
+        covering(self.registers.iter(), self.max_gap)
+    }
+
+    /// Returns the template covering the registers of the group named
+    /// `name`.
+    pub fn group_template(&self, name: &str) -> Option<DeviceTemplate> {
+        let group = self.groups.iter().find(|group| group.name == name)?;
+        Some(self.covering_group(group))
+    }
+
+    /// Returns the scaled value of every register found in `image`, in
+    /// declaration order, followed by the derived values whose inputs were
+    /// all found.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{DeviceImage, DeviceProfile, Response};
+    ///
+    /// let profile = DeviceProfile::from_toml(r#"
+    ///     model = "EM-100"
+    ///
+    ///     [[register]]
+    ///     name = "voltage"
+    ///     table = "input_registers"
+    ///     address = 0
+    ///     scale = 0.5
+    ///
+    ///     [[register]]
+    ///     name = "current"
+    ///     table = "input_registers"
+    ///     address = 1
+    ///     scale = 0.25
+    ///     aliases = ["I"]
+    ///
+    ///     [[derived]]
+    ///     name = "power"
+    ///     operation = "product"
+    ///     inputs = ["voltage", "I"]
+    ///     unit = "W"
+    /// "#).unwrap();
+    ///
+    /// let template = profile.template();
+    /// let responses = vec![Response::Value(Box::new([460, 40]))];
+    /// let image = DeviceImage::decode(&template, responses).unwrap();
+    ///
+    /// assert_eq!(profile.values(&image), vec![("voltage", 230.0), ("current", 10.0), ("power", 2300.0)]);
+    /// ```
+    ///
+    pub fn values(&self, image: &DeviceImage) -> Vec<(&str, f64)> {
+        let mut values: Vec<(&str, f64)> = self
+            .registers
+            .iter()
+            .filter_map(|register| {
+                Some((
+                    register.name.as_str(),
+                    register.value(image, self.word_order)?,
+                ))
+            })
+            .collect();
+        for derived in &self.derived {
+            let inputs: Option<Vec<f64>> = derived
+                .inputs
+                .iter()
+                .map(|input| {
+                    let name = self.register(input).map_or(input.as_str(), |r| &r.name);
+                    values
+                        .iter()
+                        .find(|(n, _)| *n == name)
+                        .map(|(_, value)| *value)
+                })
+                .collect();
+            if let Some(value) = inputs.and_then(|inputs| derived.evaluate(&inputs)) {
+                values.push((derived.name.as_str(), value));
+            }
+        }
+        values
+    }
+
+    /// Returns the change filter of the register or derived value named
+    /// `name`, and whether the value is boolean.
+    fn change_filter(&self, name: &str) -> Option<(&ChangeFilter, bool)> {
+        match self.register(name) {
+            Some(register) => Some((&register.filter, register.data_type == DataType::Bool)),
+            None => self
+                .derived
+                .iter()
+                .find(|derived| derived.name == name)
+                .map(|derived| (&derived.filter, false)),
+        }
+    }
+
+    /// Builds a slave answering to `modbus_id` that exposes every register
+    /// of the profile as a block of its own, named after the register and
+    /// initialized to zero, e.g. to simulate the device.
+    ///
+    /// ---
+    /// # Errors
+    /// Returns [`ModelError`](crate::error::ModelError) when registers
+    /// overlap.
+    #[cfg(feature = "slave")]
+    pub fn slave(&self, modbus_id: u8) -> Result<crate::Slave, crate::error::ModelError> {
+        use crate::DataBlock;
+
+        self.registers
+            .iter()
+            .fold(crate::Slave::builder(modbus_id), |builder, register| {
+                let name = register.name.as_str();
+                let (address, quantity) = (register.address, register.quantity() as usize);
+                match register.table {
+                    Table::Coils => {
+                        builder.coils(name, DataBlock::new(address, vec![false; quantity]))
+                    }
+                    Table::DiscreteInputs => builder
+                        .discrete_inputs(name, DataBlock::new(address, vec![false; quantity])),
+                    Table::HoldingRegisters => {
+                        builder.holding_registers(name, DataBlock::new(address, vec![0; quantity]))
+                    }
+                    Table::InputRegisters => {
+                        builder.input_registers(name, DataBlock::new(address, vec![0; quantity]))
+                    }
+                }
+            })
+            .build()
+    }
+
+    /// Generates Rust source for slave firmware implementing the profile:
+    /// an address constant per register and per alias, named after it in
+    /// upper case, and a `slave(modbus_id)` function building the data model like
+    /// [`slave`](Self::slave). Names that map to the same constant, e.g.
+    /// `"Temp-1"` and `"temp 1"`, are told apart by a suffix, `TEMP_1_2`
+    /// for the one declared later.
+    ///
+    /// Meant for build scripts, so that the master profile and the firmware
+    /// of the device are generated from the same file; the generated code
+    /// needs this crate with the `slave` feature only.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::DeviceProfile;
+    ///
+    /// let profile = DeviceProfile::from_toml(r#"
+    ///     model = "EM-100"
+    ///
+    ///     [[register]]
+    ///     name = "voltage"
+    ///     table = "input_registers"
+    ///     address = 0x10
+    ///     unit = "V"
+    ///     aliases = ["U"]
+    /// "#).unwrap();
+    ///
+    /// let source = profile.to_rust();
+    /// assert!(source.contains("pub const VOLTAGE: u16 = 0x0010;"));
+    /// assert!(source.contains("pub const U: u16 = VOLTAGE;"));
+    /// assert!(source.contains("pub fn slave(modbus_id: u8)"));
+    /// ```
+    ///
+    /// In `build.rs`, with this crate as a build dependency with the
+    /// `template` feature:
+    ///
+    /// ```ignore
+    /// let profile = modbus_rtu::DeviceProfile::load("em100.toml").unwrap();
+    /// let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("em100.rs");
+    /// std::fs::write(out, profile.to_rust()).unwrap();
+    /// println!("cargo::rerun-if-changed=em100.toml");
+    /// ```
+    ///
+    /// and in the firmware:
+    ///
+    /// ```ignore
+    /// mod em100 {
+    ///     include!(concat!(env!("OUT_DIR"), "/em100.rs"));
+    /// }
+    ///
+    /// let mut slave = em100::slave(0x01).unwrap();
+    /// *slave.input_registers_mut().get_mut(em100::VOLTAGE).unwrap() = 2301;
+    /// ```
+    ///
+    pub fn to_rust(&self) -> String {
+        use core::fmt::Write;
+
+        let mut source = format!(
+            "// Generated by modbus-rtu from device profile {:?}; do not edit.\n",
+            self.model
+        );
+        let constants = self.constant_names();
+        for (register, (constant, aliases)) in self.registers.iter().zip(&constants) {
+            let mut doc = format!("{} ({}", register.name, register.data_type);
+            if let Some(unit) = &register.unit {
+                let _ = write!(doc, ", {unit}");
+            }
+            doc.push(')');
+            if let Some(description) = &register.description {
+                let _ = write!(doc, ": {description}");
+            }
+            source.push('\n');
+            for line in doc.lines() {
+                let line = format!("/// {}", line.replace('\r', " "));
+                let _ = writeln!(source, "{}", line.trim_end());
+            }
+            let _ = writeln!(
+                source,
+                "pub const {constant}: u16 = 0x{:04X};",
+                register.address
+            );
+            for alias in aliases {
+                let _ = write!(
+                    source,
+                    "/// Alias of [`{constant}`].\npub const {alias}: u16 = {constant};\n"
+                );
+            }
+        }
+        source.push_str(
+            "\n/// Builds the data model of the device, every register initialized to zero.\n\
+             pub fn slave(modbus_id: u8) -> Result<::modbus_rtu::Slave, ::modbus_rtu::error::ModelError> {\n\
+             \x20   ::modbus_rtu::Slave::builder(modbus_id)\n",
+        );
+        for (register, (constant, _)) in self.registers.iter().zip(&constants) {
+            let (method, zero) = match register.table {
+                Table::Coils => ("coils", "false"),
+                Table::DiscreteInputs => ("discrete_inputs", "false"),
+                Table::HoldingRegisters => ("holding_registers", "0"),
+                Table::InputRegisters => ("input_registers", "0"),
+            };
+            let _ = writeln!(
+                source,
+                "        .{method}({:?}, ::modbus_rtu::DataBlock::new({constant}, vec![{zero}; {}]))",
+                register.name,
+                register.quantity()
+            );
+        }
+        source.push_str("        .build()\n}\n");
+        source
+    }
+
+    /// Returns the names of the address constants of every register and of
+    /// its aliases, in declaration order, suffixed with `_2`, `_3`, ... where
+    /// they would collide with a name taken before.
+    fn constant_names(&self) -> Vec<(String, Vec<String>)> {
+        let mut taken = std::collections::HashSet::new();
+        let mut unique = |name: &str| {
+            let base = constant_name(name);
+            let mut constant = base.clone();
+            let mut suffix = 1;
+            while !taken.insert(constant.clone()) {
+                suffix += 1;
+                constant = format!("{base}_{suffix}");
+            }
+            constant
+        };
+        self.registers
+            .iter()
+            .map(|register| {
+                let constant = unique(&register.name);
+                let aliases = register.aliases.iter().map(|alias| unique(alias)).collect();
+                (constant, aliases)
+            })
+            .collect()
+    }
+
+    /// Applies the [`quirks`](Self::quirks) of the device to the handling of
+    /// slave `modbus_id` by `master`.
+    #[cfg(all(feature = "master", feature = "sync"))]
+    pub fn apply_quirks(&self, master: &mut crate::Master, modbus_id: u8) {
+        master.set_accept_swapped_crc(modbus_id, self.quirks.swapped_crc);
+        master.set_responder_alias(modbus_id, self.quirks.responder_id);
+        master.set_slave_spacing(
+            modbus_id,
+            core::time::Duration::from_millis(self.quirks.spacing_ms),
+        );
+    }
+
+    /// Schedules the reads of every group on `poller`, for slave
+    /// `modbus_id` answering within `timeout`.
+    #[cfg(all(feature = "master", feature = "sync"))]
+    pub fn add_polls(
+        &self,
+        poller: &mut crate::Poller,
+        modbus_id: u8,
+        timeout: core::time::Duration,
+    ) {
+        for group in &self.groups {
+            let interval = core::time::Duration::from_millis(group.interval_ms);
+            for function in self.covering_group(group).functions() {
+                poller.add(modbus_id, function, timeout, interval);
+            }
+        }
+    }
+
+    /// Returns the template covering the registers of `group`.
+    fn covering_group(&self, group: &PollGroup) -> DeviceTemplate {
+        covering(
+            group
+                .registers
+                .iter()
+                .filter_map(|name| self.register(name)),
+            self.max_gap,
+        )
+    }
+}
+
+
+/// Deviations of a device from the specification, see
+/// [`DeviceProfile::apply_quirks`].
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct DeviceQuirks {
+    /// The device sends its CRC most significant byte first.
+    #[serde(default)]
+    pub swapped_crc: bool,
+
+    /// The device answers with this id instead of the one addressed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub responder_id: Option<u8>,
+
+    /// Least time in milliseconds between the end of a response and the
+    /// next request to the device.
+    #[serde(default)]
+    pub spacing_ms: u64,
+}
+
+
+/// Data type of a [`RegisterProfile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataType {
+    /// A single coil or discrete input.
+    Bool,
+
+    /// An unsigned 16-bit register.
+    #[default]
+    U16,
+
+    /// A signed 16-bit register.
+    I16,
+
+    /// An unsigned 32-bit value in two registers.
+    U32,
+
+    /// A signed 32-bit value in two registers.
+    I32,
+
+    /// An IEEE 754 single precision value in two registers.
+    F32,
+}
+
+impl DataType {
+    /// Returns how many coils or registers a value occupies.
+    pub const fn quantity(&self) -> u16 {
+        match self {
+            DataType::Bool | DataType::U16 | DataType::I16 => 1,
+            DataType::U32 | DataType::I32 | DataType::F32 => 2,
+        }
+    }
+}
+
+impl core::fmt::Display for DataType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            DataType::Bool => "bool",
+            DataType::U16 => "u16",
+            DataType::I16 => "i16",
+            DataType::U32 => "u32",
+            DataType::I32 => "i32",
+            DataType::F32 => "f32",
+        };
+        write!(f, "{name}")
+    }
+}
+
+
+/// A named value of a device and how to scale it.
+///
+/// Engineering values are `raw * scale + offset`, or follow the
+/// [`lookup`](Self::lookup) table of nonlinear sensors.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct RegisterProfile {
+    /// Name identifying the value within the profile.
+    pub name: String,
+
+    /// Table holding the value.
+    pub table: Table,
+
+    /// Address of the first coil or register of the value.
+    pub address: u16,
+
+    /// Data type of the value; `u16` when omitted.
+    #[serde(default, rename = "type")]
+    pub data_type: DataType,
+
+    /// Factor applied to the raw value; `1.0` when omitted.
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+
+    /// Offset added to the scaled value; `0.0` when omitted.
+    #[serde(default)]
+    pub offset: f64,
+
+    /// Points `[raw, engineering]` of a piecewise linear conversion, e.g.
+    /// the curve of a thermistor, replacing `scale` and `offset` when given.
+    ///
+    /// Raw values must ascend and engineering values must either ascend or
+    /// descend, so the conversion works both ways. Values between two
+    /// points are interpolated; values beyond the table take the value of
+    /// its nearest end.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lookup: Vec<[f64; 2]>,
+
+    /// Unit of the engineering value, e.g. `"kWh"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+
+    /// Free-form description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Filter applied before a change of the value is reported.
+    #[serde(flatten)]
+    pub filter: ChangeFilter,
+
+    /// Further names the value is known under, e.g. the labels of the
+    /// device manual or of a control system; unique within the profile like
+    /// the name.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+}
+
+impl RegisterProfile {
+    /// Returns `true` when `name` is the name or one of the aliases of the
+    /// value.
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name == name || self.aliases.iter().any(|alias| alias == name)
+    }
+
+    /// Returns how many coils or registers the value occupies.
+    pub const fn quantity(&self) -> u16 {
+        self.data_type.quantity()
+    }
+
+    /// Returns the request reading the value.
+    pub fn read(&self) -> Function {
+        self.table.read(self.address, self.quantity())
+    }
+
+    /// Scales the raw registers (or the coil, as `0` or `1`) of the value.
+    ///
+    /// Returns [`None`] when `words` is too short for the data type.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::{DeviceProfile, WordOrder};
+    ///
+    /// // An NTC thermistor read through a 10-bit ADC: temperature falls as
+    /// // the raw value rises.
+    /// let profile = DeviceProfile::from_toml(r#"
+    ///     model = "TH-1"
+    ///
+    ///     [[register]]
+    ///     name = "temperature"
+    ///     table = "input_registers"
+    ///     address = 0
+    ///     unit = "°C"
+    ///     lookup = [[100, 85.0], [500, 25.0], [900, -20.0]]
+    /// "#).unwrap();
+    /// let register = profile.register("temperature").unwrap();
+    ///
+    /// assert_eq!(register.decode(&[300], WordOrder::HighFirst), Some(55.0));
+    /// assert_eq!(register.decode(&[1000], WordOrder::HighFirst), Some(-20.0));
+    /// assert_eq!(register.encode(25.0, WordOrder::HighFirst), vec![500]);
+    /// ```
+    ///
+    pub fn decode(&self, words: &[u16], word_order: WordOrder) -> Option<f64> {
+        let raw = match (self.data_type, words) {
+            (DataType::Bool | DataType::U16, &[word, ..]) => word as f64,
+            (DataType::I16, &[word, ..]) => word as i16 as f64,
+            (DataType::U32, &[first, second, ..]) => word_order.join(first, second) as f64,
+            (DataType::I32, &[first, second, ..]) => word_order.join(first, second) as i32 as f64,
+            (DataType::F32, &[first, second, ..]) => {
+                f32::from_bits(word_order.join(first, second)) as f64
+            }
+            _ => return None,
+        };
+        if self.lookup.is_empty() {
+            Some(raw * self.scale + self.offset)
+        } else {
+            Some(interpolate(&self.lookup, raw, 0))
+        }
+    }
+
+    /// Converts an engineering value back into raw registers (or the coil,
+    /// as `0` or `1`), rounding integers and saturating at the limits of the
+    /// data type.
+    pub fn encode(&self, value: f64, word_order: WordOrder) -> Vec<u16> {
+        let raw = if self.lookup.is_empty() {
+            (value - self.offset) / self.scale
+        } else {
+            interpolate(&self.lookup, value, 1)
+        };
+        match self.data_type {
+            DataType::Bool => vec![(raw != 0.0) as u16],
+            DataType::U16 => vec![raw.round() as u16],
+            DataType::I16 => vec![raw.round() as i16 as u16],
+            DataType::U32 => word_order.split(raw.round() as u32).to_vec(),
+            DataType::I32 => word_order.split(raw.round() as i32 as u32).to_vec(),
+            DataType::F32 => word_order.split((raw as f32).to_bits()).to_vec(),
+        }
+    }
+
+    /// Returns the request writing the engineering value `value`, or
+    /// [`None`] when the table is read-only.
+    pub fn write(&self, value: f64, word_order: WordOrder) -> Option<Function> {
+        let words = self.encode(value, word_order);
+        match (self.table, &words[..]) {
+            (Table::Coils, &[word]) => Some(Function::WriteSingleCoil {
+                address: self.address,
+                value: word != 0,
+            }),
+            (Table::HoldingRegisters, &[word]) => Some(Function::WriteSingleRegister {
+                address: self.address,
+                value: word,
+            }),
+            (Table::HoldingRegisters, _) => Some(Function::WriteMultipleRegisters {
+                starting_address: self.address,
+                value: words.into_boxed_slice(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the scaled value from `image`, or [`None`] when the image
+    /// does not hold it.
+    pub fn value(&self, image: &DeviceImage, word_order: WordOrder) -> Option<f64> {
+        let words = match self.table {
+            Table::Coils => vec![*image.coils().get(self.address)? as u16],
+            Table::DiscreteInputs => vec![*image.discrete_inputs().get(self.address)? as u16],
+            Table::HoldingRegisters => image
+                .holding_registers()
+                .range(self.address, self.quantity())?
+                .to_vec(),
+            Table::InputRegisters => image
+                .input_registers()
+                .range(self.address, self.quantity())?
+                .to_vec(),
+        };
+        self.decode(&words, word_order)
+    }
+}
+
+
+/// How much a value has to change before a [`ChangeDetector`] reports it,
+/// keeping noisy sensors from flooding consumers with events and alarms
+/// from chattering.
+///
+/// Both deadbands apply to analog values, the larger one winning; a value
+/// is reported once it moves more than the deadband away from the value
+/// last reported. Boolean values are reported once they were read in their
+/// new state `hysteresis` times more in a row.
+#[derive(Debug, Clone, Copy, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub struct ChangeFilter {
+    /// Absolute deadband in engineering units; `0.0` when omitted.
+    #[serde(default)]
+    pub deadband: f64,
+
+    /// Deadband in percent of the value last reported; `0.0` when omitted.
+    #[serde(default)]
+    pub deadband_percent: f64,
+
+    /// Further consecutive reads confirming a new boolean state before it is
+    /// reported; `0` when omitted.
+    #[serde(default)]
+    pub hysteresis: u32,
+}
+
+impl ChangeFilter {
+    /// Returns `true` when the analog `value` moved past the deadband around
+    /// the value `last` reported.
+    pub fn exceeds_deadband(&self, last: f64, value: f64) -> bool {
+        let deadband = self
+            .deadband
+            .max(last.abs() * self.deadband_percent / 100.0);
+        if deadband > 0.0 {
+            (value - last).abs() > deadband
+        } else {
+            value != last
+        }
+    }
+}
+
+
+/// ## ChangeDetector
+///
+/// Turns the values of successive [`DeviceImage`]s of one device into change
+/// events, applying the [`ChangeFilter`] of every register and derived
+/// value.
+///
+/// The first image reports every value; later images report the values
+/// that passed their filter, compared to the value last reported.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::{ChangeDetector, DeviceImage, DeviceProfile, Function, Response};
+///
+/// let profile = DeviceProfile::from_toml(r#"
+///     model = "TH-1"
+///
+///     [[register]]
+///     name = "temperature"
+///     table = "input_registers"
+///     address = 0
+///     scale = 0.1
+///     deadband = 0.5
+///
+///     [[register]]
+///     name = "door"
+///     table = "discrete_inputs"
+///     address = 0
+///     type = "bool"
+///     hysteresis = 1
+/// "#).unwrap();
+/// let template = profile.template();
+/// let image = |temperature: u16, door: bool| {
+///     let responses = template.functions().into_iter().map(|function| match function {
+///         Function::ReadDiscreteInputs { .. } => Response::Status(Box::new([door])),
+///         _ => Response::Value(Box::new([temperature])),
+///     });
+///     DeviceImage::decode(&template, responses).unwrap()
+/// };
+///
+/// let mut detector = ChangeDetector::new();
+/// assert_eq!(detector.changes(&profile, &image(215, false)).len(), 2);
+///
+/// // 0.3 degrees stay within the deadband, one open door within the hysteresis.
+/// assert!(detector.changes(&profile, &image(218, true)).is_empty());
+/// assert_eq!(detector.changes(&profile, &image(221, true)), vec![("temperature", 22.1), ("door", 1.0)]);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ChangeDetector {
+    reported: std::collections::HashMap<String, f64>,
+    confirmations: std::collections::HashMap<String, u32>,
+}
+
+impl ChangeDetector {
+    /// Creates a detector that has not reported anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the values of `image` that changed, in declaration order,
+    /// and remembers them as reported.
+    pub fn changes<'a>(
+        &mut self,
+        profile: &'a DeviceProfile,
+        image: &DeviceImage,
+    ) -> Vec<(&'a str, f64)> {
+        let mut changes = Vec::new();
+        for (name, value) in profile.values(image) {
+            let Some((filter, boolean)) = profile.change_filter(name) else {
+                continue;
+            };
+            let changed = match self.reported.get(name) {
+                None => true,
+                Some(&last) if !boolean => filter.exceeds_deadband(last, value),
+                Some(&last) if value == last => {
+                    self.confirmations.remove(name);
+                    false
+                }
+                Some(_) => {
+                    let confirmations = self.confirmations.entry(name.to_owned()).or_default();
+                    *confirmations += 1;
+                    *confirmations > filter.hysteresis
+                }
+            };
+            if changed {
+                self.reported.insert(name.to_owned(), value);
+                self.confirmations.remove(name);
+                changes.push((name, value));
+            }
+        }
+        changes
+    }
+
+    /// Returns the value last reported for the register named `name`.
+    pub fn reported(&self, name: &str) -> Option<f64> {
+        self.reported.get(name).copied()
+    }
+
+    /// Forgets every value reported, so the next image reports all of them.
+    pub fn reset(&mut self) {
+        self.reported.clear();
+        self.confirmations.clear();
+    }
+}
+
+
+/// How a [`DerivedProfile`] combines its inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    /// The sum of the inputs, e.g. the total of three phases.
+    Sum,
+
+    /// The first input minus the others.
+    Difference,
+
+    /// The product of the inputs, e.g. voltage times current.
+    Product,
+
+    /// The first input divided by the others.
+    Quotient,
+
+    /// The arithmetic mean of the inputs.
+    Mean,
+
+    /// The smallest input.
+    Min,
+
+    /// The largest input.
+    Max,
+}
+
+
+/// A value computed from other values of a device, e.g. power from voltage
+/// and current, evaluated by [`DeviceProfile::values`] after the registers.
+///
+/// Engineering values are `operation(inputs) * scale + offset`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DerivedProfile {
+    /// Name identifying the value within the profile, unique among
+    /// register names and aliases.
+    pub name: String,
+
+    /// How the inputs are combined.
+    pub operation: Operation,
+
+    /// Names or aliases of the registers, or names of values derived
+    /// before this one, in operand order.
+    pub inputs: Vec<String>,
+
+    /// Factor applied to the result; `1.0` when omitted.
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+
+    /// Offset added to the scaled result; `0.0` when omitted.
+    #[serde(default)]
+    pub offset: f64,
+
+    /// Unit of the engineering value, e.g. `"kW"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+
+    /// Free-form description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Filter applied before a change of the value is reported.
+    #[serde(flatten)]
+    pub filter: ChangeFilter,
+}
+
+impl DerivedProfile {
+    /// Combines the engineering values `inputs`, given in the order of
+    /// [`inputs`](Self::inputs), and scales the result.
+    ///
+    /// Returns [`None`] without inputs or when the result is not finite,
+    /// e.g. after a division by zero.
+    pub fn evaluate(&self, inputs: &[f64]) -> Option<f64> {
+        let (&first, rest) = inputs.split_first()?;
+        let result = match self.operation {
+            Operation::Sum => inputs.iter().sum(),
+            Operation::Difference => rest.iter().fold(first, |a, b| a - b),
+            Operation::Product => inputs.iter().product(),
+            Operation::Quotient => rest.iter().fold(first, |a, b| a / b),
+            Operation::Mean => inputs.iter().sum::<f64>() / inputs.len() as f64,
+            Operation::Min => rest.iter().fold(first, |a, &b| a.min(b)),
+            Operation::Max => rest.iter().fold(first, |a, &b| a.max(b)),
+        };
+        Some(result * self.scale + self.offset).filter(|value| value.is_finite())
+    }
+}
+
+
+/// Registers of a device polled together at a fixed interval.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PollGroup {
+    /// Name identifying the group within the profile.
+    pub name: String,
+
+    /// Polling interval in milliseconds.
+    pub interval_ms: u64,
+
+    /// Names of the registers in the group.
+    pub registers: Vec<String>,
+}
+
+
+/// Default scale of a [`RegisterProfile`].
+fn default_scale() -> f64 {
+    1.0
+}
+
+
+/// Returns `true` for lookup tables of at least two points with strictly
+/// ascending raw values and strictly ascending or descending engineering
+/// values.
+fn monotonic(points: &[[f64; 2]]) -> bool {
+    let ascending = |i: usize| points.windows(2).all(|pair| pair[0][i] < pair[1][i]);
+    let descending = |i: usize| points.windows(2).all(|pair| pair[0][i] > pair[1][i]);
+    points.len() >= 2 && ascending(0) && (ascending(1) || descending(1))
+}
+
+
+/// Maps `x` through the lookup table `points`, from coordinate `from` of
+/// the points to the other one, interpolating between two points and
+/// taking the nearest end beyond the table.
+fn interpolate(points: &[[f64; 2]], x: f64, from: usize) -> f64 {
+    let to = 1 - from;
+    for pair in points.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if (a[from].min(b[from])..=a[from].max(b[from])).contains(&x) {
+            return a[to] + (x - a[from]) * (b[to] - a[to]) / (b[from] - a[from]);
+        }
+    }
+    let (first, last) = (points[0], points[points.len() - 1]);
+    if (x - first[from]).abs() <= (x - last[from]).abs() {
+        first[to]
+    } else {
+        last[to]
+    }
+}
+
+
+/// Turns a register name into the name of its address constant, e.g.
+/// `"Phase 1 voltage"` into `PHASE_1_VOLTAGE`.
+fn constant_name(name: &str) -> String {
+    let mut constant: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !constant.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        constant.insert(0, 'R');
+    }
+    constant
+}
+
+
+/// Returns the template covering `registers` with contiguous runs, table by
+/// table. A run ends where the next register lies more than `max_gap`
+/// addresses past it, or where it would no longer fit a single read.
+fn covering<'a>(
+    registers: impl Iterator<Item = &'a RegisterProfile>,
+    max_gap: u16,
+) -> DeviceTemplate {
+    let mut ranges: Vec<(Table, core::ops::Range<u16>)> = registers
+        .map(|register| {
+            let end = register.address.saturating_add(register.quantity());
+            (register.table, register.address..end)
+        })
+        .collect();
+    ranges.sort_by_key(|(table, range)| (*table, range.start));
+    let mut template = DeviceTemplate::new();
+    let mut runs = ranges.into_iter().peekable();
+    while let Some((table, mut run)) = runs.next() {
+        let max = if table.is_bit() {
+            MAX_READ_COILS
+        } else {
+            MAX_READ_REGISTERS
+        };
+        while let Some((_, range)) = runs.next_if(|(next, range)| {
+            *next == table
+                && range.start <= run.end.saturating_add(max_gap)
+                && range.end.max(run.end) - run.start <= max
+        }) {
+            run.end = run.end.max(range.end);
+        }
+        template = template.also(table, run);
+    }
+    template
+}
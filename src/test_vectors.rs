@@ -0,0 +1,190 @@
+//! Canonical request/response frames for conformance testing.
+//!
+//! Every supported function is covered by the example from the Modbus
+//! application protocol specification, framed for slave `0x11` with the
+//! RTU CRC appended, plus one exception response. Downstream slave
+//! implementations and transports can replay [`ALL`] to verify that they
+//! produce and accept byte-identical frames.
+//!
+//! ---
+//! # Examples
+//! Every vector is accepted by this crate's own codec:
+//! ```rust
+//! use modbus_rtu::{frame, test_vectors, Request, Response};
+//!
+//! for vector in test_vectors::ALL {
+//!     let (modbus_id, function) = frame::parse_request(vector.request).unwrap();
+//!     let request = Request::new(modbus_id, &function, std::time::Duration::from_millis(100));
+//!     assert_eq!(&frame::encode(&request).unwrap()[..], vector.request, "{}", vector.name);
+//!     assert!(Response::from_bytes(&request, vector.response).is_ok(), "{}", vector.name);
+//! }
+//! ```
+
+use alloc::vec::Vec;
+
+
+/// A request frame and the response frame a conforming slave returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestVector {
+    /// Short identifier of the scenario.
+    pub name: &'static str,
+
+    /// Complete request frame, including slave id and CRC.
+    pub request: &'static [u8],
+
+    /// Complete response frame, including slave id and CRC.
+    pub response: &'static [u8],
+}
+
+
+/// Read Coils 20-38.
+pub const READ_COILS: TestVector = TestVector {
+    name: "read_coils",
+    request: &[0x11, 0x01, 0x00, 0x13, 0x00, 0x13, 0x8E, 0x92],
+    response: &[0x11, 0x01, 0x03, 0xCD, 0x6B, 0x05, 0x40, 0x12],
+};
+
+/// Read Discrete Inputs 197-218.
+pub const READ_DISCRETE_INPUTS: TestVector = TestVector {
+    name: "read_discrete_inputs",
+    request: &[0x11, 0x02, 0x00, 0xC4, 0x00, 0x16, 0xBA, 0xA9],
+    response: &[0x11, 0x02, 0x03, 0xAC, 0xDB, 0x35, 0x20, 0x18],
+};
+
+/// Read Holding Registers 108-110.
+pub const READ_HOLDING_REGISTERS: TestVector = TestVector {
+    name: "read_holding_registers",
+    request: &[0x11, 0x03, 0x00, 0x6B, 0x00, 0x03, 0x76, 0x87],
+    response: &[
+        0x11, 0x03, 0x06, 0x02, 0x2B, 0x00, 0x00, 0x00, 0x64, 0xC8, 0xBA,
+    ],
+};
+
+/// Read Input Register 9.
+pub const READ_INPUT_REGISTERS: TestVector = TestVector {
+    name: "read_input_registers",
+    request: &[0x11, 0x04, 0x00, 0x08, 0x00, 0x01, 0xB2, 0x98],
+    response: &[0x11, 0x04, 0x02, 0x00, 0x0A, 0xF8, 0xF4],
+};
+
+/// Write Single Coil 173 ON.
+pub const WRITE_SINGLE_COIL: TestVector = TestVector {
+    name: "write_single_coil",
+    request: &[0x11, 0x05, 0x00, 0xAC, 0xFF, 0x00, 0x4E, 0x8B],
+    response: &[0x11, 0x05, 0x00, 0xAC, 0xFF, 0x00, 0x4E, 0x8B],
+};
+
+/// Write Single Register 2 to `0x0003`.
+pub const WRITE_SINGLE_REGISTER: TestVector = TestVector {
+    name: "write_single_register",
+    request: &[0x11, 0x06, 0x00, 0x01, 0x00, 0x03, 0x9A, 0x9B],
+    response: &[0x11, 0x06, 0x00, 0x01, 0x00, 0x03, 0x9A, 0x9B],
+};
+
+/// Write Multiple Coils 20-29.
+pub const WRITE_MULTIPLE_COILS: TestVector = TestVector {
+    name: "write_multiple_coils",
+    request: &[
+        0x11, 0x0F, 0x00, 0x13, 0x00, 0x0A, 0x02, 0xCD, 0x01, 0xBF, 0x0B,
+    ],
+    response: &[0x11, 0x0F, 0x00, 0x13, 0x00, 0x0A, 0x26, 0x99],
+};
+
+/// Write Multiple Registers 2-3 to `0x000A` and `0x0102`.
+pub const WRITE_MULTIPLE_REGISTERS: TestVector = TestVector {
+    name: "write_multiple_registers",
+    request: &[
+        0x11, 0x10, 0x00, 0x01, 0x00, 0x02, 0x04, 0x00, 0x0A, 0x01, 0x02, 0xC6, 0xF0,
+    ],
+    response: &[0x11, 0x10, 0x00, 0x01, 0x00, 0x02, 0x12, 0x98],
+};
+
+/// Read Device Identification (MEI type `0x0E`), basic device identification.
+pub const READ_DEVICE_IDENTIFICATION: TestVector = TestVector {
+    name: "read_device_identification",
+    request: &[0x11, 0x2B, 0x0E, 0x01, 0x00, 0xB1, 0xB4],
+    response: b"\x11\x2B\x0E\x01\x01\x00\x00\x03\
+        \x00\x16Company identification\
+        \x01\x0DProduct code XX\
+        \x02\x05V2.11\
+        \xDE\xE7",
+};
+
+/// Read Coils beyond the implemented range, answered with Illegal Data Address.
+pub const EXCEPTION_ILLEGAL_DATA_ADDRESS: TestVector = TestVector {
+    name: "exception_illegal_data_address",
+    request: &[0x11, 0x01, 0x04, 0xA1, 0x00, 0x01, 0xAF, 0x88],
+    response: &[0x11, 0x81, 0x02, 0xC0, 0x54],
+};
+
+
+/// Every test vector.
+pub const ALL: &[TestVector] = &[
+    READ_COILS,
+    READ_DISCRETE_INPUTS,
+    READ_HOLDING_REGISTERS,
+    READ_INPUT_REGISTERS,
+    WRITE_SINGLE_COIL,
+    WRITE_SINGLE_REGISTER,
+    WRITE_MULTIPLE_COILS,
+    WRITE_MULTIPLE_REGISTERS,
+    READ_DEVICE_IDENTIFICATION,
+    EXCEPTION_ILLEGAL_DATA_ADDRESS,
+];
+
+
+/// A vector for which the implementation under test produced a different
+/// response frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The failing vector.
+    pub vector: TestVector,
+
+    /// The frame actually produced.
+    pub actual: Vec<u8>,
+}
+
+impl core::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}: expected {:02X?}, got {:02X?}",
+            self.vector.name, self.vector.response, self.actual
+        )
+    }
+}
+
+
+/// Feeds the request of every vector in `vectors` to `respond` (a slave
+/// handler, or a transport loop-back to a device configured like the
+/// specification examples) and returns the vectors whose response frame
+/// differed.
+///
+/// ---
+/// # Examples
+/// ```rust
+/// use modbus_rtu::test_vectors;
+///
+/// // A "slave" that replays the canonical answers passes trivially.
+/// let mismatches = test_vectors::check(test_vectors::ALL, |request| {
+///     let vector = test_vectors::ALL.iter().find(|v| v.request == request).unwrap();
+///     vector.response.to_vec()
+/// });
+/// assert!(mismatches.is_empty());
+///
+/// // One that never answers fails every vector.
+/// assert_eq!(test_vectors::check(test_vectors::ALL, |_| Vec::new()).len(), test_vectors::ALL.len());
+/// ```
+///
+pub fn check(vectors: &[TestVector], mut respond: impl FnMut(&[u8]) -> Vec<u8>) -> Vec<Mismatch> {
+    vectors
+        .iter()
+        .filter_map(|vector| {
+            let actual = respond(vector.request);
+            (actual != vector.response).then_some(Mismatch {
+                vector: *vector,
+                actual,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,35 @@
+/// ## Warning
+///
+/// A defect of a response frame that does not affect the integrity of the
+/// decoded data, reported by
+/// [`Response::from_bytes_permissive`](crate::Response::from_bytes_permissive).
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Warning {
+    /// The byte count of a read response declares more data bytes than the
+    /// request needs; the extra bytes are ignored.
+    ByteCountTooLarge { declared: u8, needed: usize },
+
+    /// The frame carries bytes after the end of its payload, before the
+    /// CRC; they are ignored.
+    TrailingBytes(usize),
+
+    /// Bits of the last data byte of a coil or discrete input response that
+    /// lie beyond the requested quantity are set instead of zero.
+    NonZeroPadding,
+}
+
+impl core::fmt::Display for Warning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ByteCountTooLarge { declared, needed } => write!(
+                f,
+                "byte count declares {declared} bytes but only {needed} are needed."
+            ),
+            Self::TrailingBytes(count) => {
+                write!(f, "{count} unexpected byte(s) after the payload.")
+            }
+            Self::NonZeroPadding => f.write_str("padding bits of the last data byte are set."),
+        }
+    }
+}
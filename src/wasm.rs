@@ -0,0 +1,84 @@
+//! JavaScript bindings for frame tooling.
+//!
+//! Enabled with the `wasm` feature. Combined with `default-features = false`
+//! the crate builds for `wasm32-unknown-unknown`, since only the master
+//! depends on a serial port and on wall-clock timing. The functions below
+//! take and return plain byte arrays and strings so they can be called
+//! directly from JavaScript through `wasm-bindgen`.
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+
+/// Summary of a parsed request frame.
+#[wasm_bindgen]
+pub struct RequestInfo {
+    modbus_id: u8,
+    function_code: u8,
+    summary: String,
+}
+
+#[wasm_bindgen]
+impl RequestInfo {
+    /// Slave id the request is addressed to.
+    #[wasm_bindgen(getter, js_name = modbusId)]
+    pub fn modbus_id(&self) -> u8 {
+        self.modbus_id
+    }
+
+    /// Function code of the request.
+    #[wasm_bindgen(getter, js_name = functionCode)]
+    pub fn function_code(&self) -> u8 {
+        self.function_code
+    }
+
+    /// Human readable description, e.g. `slave 17, Read Holding Registers at 0x006B..=0x006D`.
+    #[wasm_bindgen(getter)]
+    pub fn summary(&self) -> String {
+        self.summary.clone()
+    }
+}
+
+
+/// Computes the Modbus CRC16 of `bytes`.
+#[wasm_bindgen]
+pub fn crc16(bytes: &[u8]) -> u16 {
+    crate::crc::generate(bytes)
+}
+
+
+/// Completes a request frame from the slave id and PDU (function code and
+/// data) by validating the PDU and appending the CRC.
+#[wasm_bindgen(js_name = encodeRequest)]
+pub fn encode_request(modbus_id: u8, pdu: &[u8]) -> Result<Vec<u8>, String> {
+    let function =
+        crate::Function::from_bytes(pdu, crate::BitOrder::LsbFirst).map_err(|e| e.to_string())?;
+    let request = crate::Request::new(modbus_id, &function, core::time::Duration::ZERO);
+    crate::frame::encode(&request)
+        .map(Vec::from)
+        .map_err(|e| e.to_string())
+}
+
+
+/// Parses a complete request frame.
+#[wasm_bindgen(js_name = parseRequest)]
+pub fn parse_request(frame: &[u8]) -> Result<RequestInfo, String> {
+    let (modbus_id, function) = crate::frame::parse_request(frame).map_err(|e| e.to_string())?;
+    let request = crate::Request::new(modbus_id, &function, core::time::Duration::ZERO);
+    Ok(RequestInfo {
+        modbus_id,
+        function_code: function.as_code(),
+        summary: request.summary().to_string(),
+    })
+}
+
+
+/// Decodes `response` as the answer to the request frame `request` and
+/// returns its textual form.
+#[wasm_bindgen(js_name = decodeResponse)]
+pub fn decode_response(request: &[u8], response: &[u8]) -> Result<String, String> {
+    let (modbus_id, function) = crate::frame::parse_request(request).map_err(|e| e.to_string())?;
+    let request = crate::Request::new(modbus_id, &function, core::time::Duration::ZERO);
+    crate::Response::from_bytes(&request, response)
+        .map(|response| response.to_string())
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,63 @@
+/// ## WordOrder
+///
+/// Order in which the two registers of a 32-bit value are transmitted.
+///
+/// The Modbus specification says nothing about values wider than a
+/// register. Most devices send the most significant register first
+/// ([`WordOrder::HighFirst`], "ABCD"); many others send the least
+/// significant one first ([`WordOrder::LowFirst`], "CDAB"). Bytes within a
+/// register are always big-endian.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum WordOrder {
+    /// The most significant register comes first.
+    #[default]
+    HighFirst,
+
+    /// The least significant register comes first.
+    LowFirst,
+}
+
+impl WordOrder {
+    /// Joins two consecutive registers, in the order they were received, into
+    /// a 32-bit value.
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::WordOrder;
+    ///
+    /// assert_eq!(WordOrder::HighFirst.join(0x1234, 0x5678), 0x1234_5678);
+    /// assert_eq!(WordOrder::LowFirst.join(0x1234, 0x5678), 0x5678_1234);
+    /// ```
+    ///
+    pub const fn join(&self, first: u16, second: u16) -> u32 {
+        let (high, low) = match self {
+            WordOrder::HighFirst => (first, second),
+            WordOrder::LowFirst => (second, first),
+        };
+        (high as u32) << 16 | low as u32
+    }
+
+    /// Splits a 32-bit value into two registers, in the order they are
+    /// transmitted; the inverse of [`join`](Self::join).
+    ///
+    /// ---
+    /// # Examples
+    /// ```rust
+    /// use modbus_rtu::WordOrder;
+    ///
+    /// assert_eq!(WordOrder::HighFirst.split(0x1234_5678), [0x1234, 0x5678]);
+    /// assert_eq!(WordOrder::LowFirst.split(0x1234_5678), [0x5678, 0x1234]);
+    /// ```
+    ///
+    pub const fn split(&self, value: u32) -> [u16; 2] {
+        let (high, low) = ((value >> 16) as u16, value as u16);
+        match self {
+            WordOrder::HighFirst => [high, low],
+            WordOrder::LowFirst => [low, high],
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Property-based round trips between `Request::to_bytes` and `frame::parse_request`.
+
+use modbus_rtu::{Function, Request, frame, limits};
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+
+/// Quantities around the edges of `1..=max`, plus arbitrary ones in between.
+fn quantity(max: u16) -> impl Strategy<Value = u16> + Clone {
+    prop_oneof![Just(0), Just(1), Just(max), Just(max + 1), 1..=max]
+}
+
+
+/// Reads of coils, discrete inputs, holding and input registers.
+fn read() -> impl Strategy<Value = Function> {
+    let bits = (any::<u16>(), quantity(limits::MAX_READ_COILS));
+    let words = (any::<u16>(), quantity(limits::MAX_READ_REGISTERS));
+    prop_oneof![
+        bits.clone()
+            .prop_map(|(starting_address, quantity)| Function::ReadCoils {
+                starting_address,
+                quantity,
+            }),
+        bits.prop_map(
+            |(starting_address, quantity)| Function::ReadDiscreteInputs {
+                starting_address,
+                quantity,
+            }
+        ),
+        words.clone().prop_map(
+            |(starting_address, quantity)| Function::ReadHoldingRegisters {
+                starting_address,
+                quantity,
+            }
+        ),
+        words.prop_map(
+            |(starting_address, quantity)| Function::ReadInputRegisters {
+                starting_address,
+                quantity,
+            }
+        ),
+    ]
+}
+
+
+/// Single and multiple writes of coils and holding registers.
+fn write() -> impl Strategy<Value = Function> {
+    let coils =
+        quantity(limits::MAX_WRITE_COILS).prop_flat_map(|len| vec(any::<bool>(), usize::from(len)));
+    let registers = quantity(limits::MAX_WRITE_REGISTERS)
+        .prop_flat_map(|len| vec(any::<u16>(), usize::from(len)));
+    prop_oneof![
+        (any::<u16>(), any::<bool>())
+            .prop_map(|(address, value)| Function::WriteSingleCoil { address, value }),
+        (any::<u16>(), any::<u16>())
+            .prop_map(|(address, value)| Function::WriteSingleRegister { address, value }),
+        (any::<u16>(), coils).prop_map(|(starting_address, value)| {
+            Function::WriteMultipleCoils {
+                starting_address,
+                value: value.into_boxed_slice(),
+            }
+        }),
+        (any::<u16>(), registers).prop_map(|(starting_address, value)| {
+            Function::WriteMultipleRegisters {
+                starting_address,
+                value: value.into_boxed_slice(),
+            }
+        }),
+    ]
+}
+
+
+/// Every function this crate can encode, boundary quantities included.
+fn function() -> impl Strategy<Value = Function> {
+    let encapsulated =
+        (any::<u8>(), vec(any::<u8>(), 0..limits::MAX_PDU)).prop_map(|(mei_type, data)| {
+            Function::EncapsulatedInterface {
+                mei_type,
+                data: data.into_boxed_slice(),
+            }
+        });
+    prop_oneof![read(), write(), encapsulated]
+}
+
+
+proptest! {
+    #[test]
+    fn parse_request_inverts_to_bytes(modbus_id in 1..=247u8, function in function()) {
+        let request = Request::new(modbus_id, &function, std::time::Duration::from_millis(100));
+        if let Ok(bytes) = request.to_bytes() {
+            prop_assert_eq!(frame::parse_request(&bytes), Ok((modbus_id, function)));
+        }
+    }
+
+    #[test]
+    fn single_byte_corruption_fails(
+        modbus_id in 1..=247u8,
+        function in function(),
+        index in any::<prop::sample::Index>(),
+        flip in 1..=u8::MAX,
+    ) {
+        let request = Request::new(modbus_id, &function, std::time::Duration::from_millis(100));
+        if let Ok(bytes) = request.to_bytes() {
+            let mut bytes = bytes.into_vec();
+            let index = index.index(bytes.len());
+            bytes[index] ^= flip;
+            prop_assert!(frame::parse_request(&bytes).is_err());
+        }
+    }
+}
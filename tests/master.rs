@@ -0,0 +1,52 @@
+//! Regression tests of the blocking master.
+#![cfg(all(feature = "master", feature = "sync"))]
+
+use modbus_rtu::Master;
+
+
+#[test]
+fn open_url_rejects_invalid_line_settings() {
+    for query in [
+        "baud=0",
+        "baud=fast",
+        "data_bits=9",
+        "parity=mark",
+        "stop_bits=3",
+    ] {
+        let error = Master::open_url(&format!("rtu:///dev/null?{query}")).unwrap_err();
+        assert_eq!(error.to_string(), format!("invalid parameter \"{query}\""));
+    }
+}
+
+
+#[cfg(all(unix, feature = "slave"))]
+#[test]
+fn backoff_defers_every_request_of_a_failing_slave() {
+    use modbus_rtu::{DataBlock, Function, PollMode, Poller, Slave, VirtualBus};
+    use std::time::{Duration, Instant};
+
+    let slave = Slave::builder(0x01)
+        .holding_registers("setpoints", DataBlock::new(0x0000, vec![0; 4]))
+        .build()
+        .unwrap();
+    let (_bus, mut master) = VirtualBus::spawn(slave, 115_200u32).unwrap();
+
+    let mut poller = Poller::new(PollMode::FixedDelay);
+    poller.set_backoff(Some(Duration::from_secs(1)));
+    let timeout = Duration::from_millis(50);
+    let interval = Duration::from_millis(200);
+    for starting_address in [0x0000, 0x0002] {
+        let read = Function::ReadHoldingRegisters {
+            starting_address,
+            quantity: 2,
+        };
+        poller.add(0x02, read, timeout, interval);
+    }
+
+    let (id, result) = poller.poll(&mut master).unwrap();
+    assert_eq!(id, 0);
+    assert!(result.is_err());
+    assert_eq!(poller.backoff(0x02), 2);
+    // The second request to the silent slave waits out the doubled interval too.
+    assert!(poller.next_due().unwrap() > Instant::now() + Duration::from_millis(300));
+}
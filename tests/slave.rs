@@ -0,0 +1,145 @@
+//! Regression tests of the slave request handler.
+#![cfg(feature = "slave")]
+
+use modbus_rtu::error::ModelConflict;
+use modbus_rtu::{
+    DataBlock, DataConstraint, Exception, Function, FunctionPolicy, Request, Response, Slave, Table,
+};
+
+
+/// Builds a slave with holding registers `0x0000..=0x0003`.
+fn slave() -> modbus_rtu::SlaveBuilder {
+    Slave::builder(0x01).holding_registers("config", DataBlock::new(0x0000, vec![0; 4]))
+}
+
+
+/// Encodes `function` as a request frame for slave 1.
+fn frame(function: &Function) -> Box<[u8]> {
+    Request::new(0x01, function, std::time::Duration::from_millis(100))
+        .to_bytes()
+        .unwrap()
+}
+
+
+#[test]
+fn replay_ignores_truncated_frames() {
+    let mut slave = slave().replay_duplicate_writes().build().unwrap();
+    assert_eq!(slave.handle(&[0x01]), None);
+    assert_eq!(slave.handle(&[]), None);
+
+    let write = frame(&Function::WriteSingleRegister {
+        address: 0x0000,
+        value: 1,
+    });
+    let response = slave.handle(&write);
+    assert!(response.is_some());
+    slave.holding_registers_mut().write(0x0000, &[0]);
+
+    // Noise between a write and its retry keeps the write cached.
+    assert_eq!(slave.handle(&[0x01]), None);
+    assert_eq!(slave.handle(&write), response);
+    assert_eq!(slave.holding_registers().get(0x0000), Some(&0));
+}
+
+
+#[test]
+fn access_profile_counts_the_last_address() {
+    let mut slave = Slave::builder(0x01)
+        .holding_registers("top", DataBlock::new(0xFFFE, vec![0; 2]))
+        .record_accesses()
+        .build()
+        .unwrap();
+    slave.process(&Function::ReadHoldingRegisters {
+        starting_address: 0xFFFF,
+        quantity: 1,
+    });
+    slave.process(&Function::WriteMultipleRegisters {
+        starting_address: 0xFFFE,
+        value: Box::new([1, 2]),
+    });
+
+    let profile = slave.access_profile().unwrap();
+    let count = profile.count(Table::HoldingRegisters, 0xFFFF);
+    assert_eq!((count.reads, count.writes), (1, 1));
+    assert_eq!(profile.count(Table::HoldingRegisters, 0xFFFE).writes, 1);
+    assert_eq!(profile.count(Table::HoldingRegisters, 0x0000).total(), 0);
+}
+
+
+#[test]
+fn acknowledge_refuses_failing_writes_at_once() {
+    let mut slave = slave()
+        .constrain(0x0000, DataConstraint::Range { min: 0, max: 10 })
+        .function_policy(0x10, FunctionPolicy::Acknowledge)
+        .build()
+        .unwrap();
+    let exception = |slave: &mut Slave, function: Function| {
+        let request = Request::new(0x01, &function, std::time::Duration::from_millis(100));
+        let response = slave.handle(&request.to_bytes().unwrap()).unwrap();
+        match Response::from_bytes(&request, &response).unwrap() {
+            Response::Exception(exception) => Some(exception),
+            _ => None,
+        }
+    };
+
+    let outside = Function::WriteMultipleRegisters {
+        starting_address: 0x0003,
+        value: Box::new([1, 2]),
+    };
+    assert_eq!(
+        exception(&mut slave, outside),
+        Some(Exception::IllegalDataAddress)
+    );
+    let forbidden = Function::WriteMultipleRegisters {
+        starting_address: 0x0000,
+        value: Box::new([11]),
+    };
+    assert_eq!(
+        exception(&mut slave, forbidden),
+        Some(Exception::IllegalDataValue)
+    );
+    assert_eq!(slave.pending().count(), 0);
+
+    let allowed = Function::WriteMultipleRegisters {
+        starting_address: 0x0000,
+        value: Box::new([10]),
+    };
+    assert_eq!(exception(&mut slave, allowed), Some(Exception::Acknowledge));
+    assert_eq!(slave.holding_registers().get(0x0000), Some(&0));
+    assert_eq!(slave.pending().count(), 1);
+}
+
+
+#[test]
+fn acknowledge_is_rejected_for_reads() {
+    let error = slave()
+        .function_policy(0x03, FunctionPolicy::Acknowledge)
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        error.conflicts(),
+        [ModelConflict::AcknowledgedNonWrite {
+            function_code: 0x03
+        }]
+    );
+}
+
+
+#[test]
+fn duplicate_names_are_reported_once_with_every_block() {
+    let error = slave()
+        .holding_registers("config", DataBlock::new(0x0010, vec![0; 2]))
+        .holding_registers("config", DataBlock::new(0x0020, vec![0; 2]))
+        .build()
+        .unwrap_err();
+    let conflict = ModelConflict::DuplicateName {
+        table: Table::HoldingRegisters,
+        name: "config".into(),
+        starts: vec![0x0000, 0x0010, 0x0020],
+    };
+    assert_eq!(
+        conflict.to_string(),
+        "Holding Registers declare 3 blocks named \"config\", starting at 0x0000, 0x0010, 0x0020."
+    );
+    assert_eq!(error.conflicts(), [conflict]);
+}
@@ -0,0 +1,106 @@
+//! Regression tests of device profiles.
+#![cfg(feature = "template")]
+
+use modbus_rtu::{DeviceImage, DeviceProfile, Function, Response};
+
+
+/// Parses a profile of `max_gap` with u16 input registers at `addresses`.
+fn sparse(max_gap: u16, addresses: &[u16]) -> DeviceProfile {
+    let mut text = format!("model = \"SPARSE\"\nmax_gap = {max_gap}\n");
+    for address in addresses {
+        text.push_str(&format!(
+            "[[register]]\nname = \"r{address}\"\ntable = \"input_registers\"\naddress = {address}\n"
+        ));
+    }
+    DeviceProfile::from_toml(&text).unwrap()
+}
+
+
+/// Returns the starting address and quantity of every read of `profile`.
+fn reads(profile: &DeviceProfile) -> Vec<(u16, u16)> {
+    profile
+        .template()
+        .functions()
+        .into_iter()
+        .map(|function| match function {
+            Function::ReadInputRegisters {
+                starting_address,
+                quantity,
+            } => (starting_address, quantity),
+            function => panic!("unexpected {function:?}"),
+        })
+        .collect()
+}
+
+
+#[test]
+fn sparse_registers_are_read_in_contiguous_runs() {
+    let profile = sparse(0, &[0, 1, 100, 5000, 5001, 5002]);
+    assert_eq!(reads(&profile), [(0, 2), (100, 1), (5000, 3)]);
+
+    let responses =
+        [[10, 11].as_slice(), &[12], &[13, 14, 15]].map(|values| Response::Value(values.into()));
+    let image = DeviceImage::decode(&profile.template(), responses).unwrap();
+    assert_eq!(image.input_registers().get(2), None);
+    assert_eq!(
+        profile.values(&image),
+        [
+            ("r0", 10.0),
+            ("r1", 11.0),
+            ("r100", 12.0),
+            ("r5000", 13.0),
+            ("r5001", 14.0),
+            ("r5002", 15.0)
+        ]
+    );
+}
+
+
+#[test]
+fn max_gap_joins_close_registers() {
+    assert_eq!(reads(&sparse(3, &[0, 4, 9, 10])), [(0, 5), (9, 2)]);
+    assert_eq!(reads(&sparse(4, &[0, 4, 9, 10])), [(0, 11)]);
+}
+
+
+#[test]
+fn runs_split_at_the_quantity_limit() {
+    assert_eq!(reads(&sparse(200, &[0, 120, 130])), [(0, 121), (130, 1)]);
+}
+
+
+#[test]
+fn generated_source_parses_with_colliding_names_and_multiline_descriptions() {
+    let profile = DeviceProfile::from_toml(
+        r#"
+        model = "TH\n2"
+
+        [[register]]
+        name = "Temp-1"
+        table = "input_registers"
+        address = 0
+        description = "Sensor one.\n\nMounted outside."
+
+        [[register]]
+        name = "temp 1"
+        table = "input_registers"
+        address = 1
+        aliases = ["TEMP_1", "temp_1_2"]
+        "#,
+    )
+    .unwrap();
+
+    let source = profile.to_rust();
+    let file = syn::parse_file(&source).unwrap();
+    let constants: Vec<String> = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Const(item) => Some(item.ident.to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(constants, ["TEMP_1", "TEMP_1_2", "TEMP_1_3", "TEMP_1_2_2"]);
+    assert!(source.contains("/// Temp-1 (u16): Sensor one.\n///\n/// Mounted outside.\n"));
+    assert!(source.contains("DataBlock::new(TEMP_1_2, "));
+}